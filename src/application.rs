@@ -1,9 +1,18 @@
-use crate::{Decode, Encode};
-
-use byteorder::{ReadBytesExt, WriteBytesExt};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
 
+pub mod confirmed;
+pub mod error;
+pub mod segmentation;
 pub mod service;
+#[cfg(feature = "std")]
+pub mod transaction;
+pub use confirmed::*;
+pub use error::*;
+pub use segmentation::*;
 pub use service::*;
+#[cfg(feature = "std")]
+pub use transaction::*;
 
 use tracing::trace;
 
@@ -22,7 +31,7 @@ use tracing::trace;
 ///     }
 /// ```
 ///
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum BACnetPDU {
     ConfirmedRequest,   // = 0x00;
     UnconfirmedRequest, // = 0x01;
@@ -36,7 +45,7 @@ pub enum BACnetPDU {
 
 #[allow(dead_code)]
 impl BACnetPDU {
-    fn as_u8(&self) -> u8 {
+    pub(crate) fn as_u8(&self) -> u8 {
         match self {
             Self::ConfirmedRequest => 0,
             Self::UnconfirmedRequest => 1,
@@ -50,31 +59,162 @@ impl BACnetPDU {
     }
 }
 
+impl TryFrom<u8> for BACnetPDU {
+    type Error = Error;
+
+    fn try_from(value: u8) -> crate::Result<Self> {
+        match value {
+            0 => Ok(Self::ConfirmedRequest),
+            1 => Ok(Self::UnconfirmedRequest),
+            2 => Ok(Self::SimpleACK),
+            3 => Ok(Self::ComplexACK),
+            4 => Ok(Self::SegmentACK),
+            5 => Ok(Self::Error),
+            6 => Ok(Self::Reject),
+            7 => Ok(Self::Abort),
+            t => Err(Error::Unsupported(format!(
+                "APDU type not supported: {}",
+                t
+            ))),
+        }
+    }
+}
+
+impl BACnetPDU {
+    /// Number of protocol control octets between the type octet and the service choice,
+    /// excluding the sequence number and window size of segmented messages (Chapter 20.1)
+    fn pci_len(&self) -> usize {
+        match self {
+            Self::ConfirmedRequest => 2, // Max Segments/APDU, Invoke ID
+            Self::UnconfirmedRequest => 0,
+            Self::SimpleACK | Self::ComplexACK | Self::Error | Self::Reject | Self::Abort => 1, // Invoke ID
+            Self::SegmentACK => 3, // Invoke ID, Sequence Number, Actual Window Size
+        }
+    }
+
+    /// Whether the PDU carries a service choice (or reason for Reject and Abort)
+    fn has_service_choice(&self) -> bool {
+        !matches!(self, Self::SegmentACK)
+    }
+}
+
+impl core::fmt::Display for BACnetPDU {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::ConfirmedRequest => "Confirmed-Request",
+            Self::UnconfirmedRequest => "Unconfirmed-Request",
+            Self::SimpleACK => "SimpleACK",
+            Self::ComplexACK => "ComplexACK",
+            Self::SegmentACK => "SegmentACK",
+            Self::Error => "Error",
+            Self::Reject => "Reject",
+            Self::Abort => "Abort",
+        })
+    }
+}
+
+/// Segmented message flag of the first APDU octet (20.1.2.1)
+const SEGMENTED_MESSAGE: u8 = 0b0000_1000;
+
+/// More follows flag of the first APDU octet, clear in the last segment (20.1.2.2)
+const MORE_FOLLOWS: u8 = 0b0000_0100;
+
+/// Segmented response accepted flag of a BACnet-Confirmed-Request-PDU (20.1.2.3)
+const SEGMENTED_RESPONSE_ACCEPTED: u8 = 0b0000_0010;
+
 /// BACnet-Unconfirmed-Request-PDU struct (Chapter 21)
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BACnetUnconfirmedRequestPDU {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct APDU {
     apdu_type: u8,
+    /// Lower four bits of the first octet
+    flags: u8,
+    /// Protocol control octets between the first octet and the service choice
+    pci: Vec<u8>,
+    /// Service choice, or the reason of a Reject or Abort PDU
     pub service_choice: u8,
     user_data: Vec<u8>,
 }
 
 impl APDU {
     pub fn new(apdu_type: u8, service_choice: u8, user_data: Vec<u8>) -> Self {
+        let pci = match BACnetPDU::try_from(apdu_type) {
+            Ok(BACnetPDU::ConfirmedRequest) => vec![0x05, 0x00], // Up to 1476 octets, Invoke ID 0
+            Ok(t) => vec![0x00; t.pci_len()],
+            Err(_) => vec![],
+        };
         Self {
             apdu_type,
+            flags: 0,
+            pci,
             service_choice,
             user_data,
         }
     }
+
+    /// The PDU type, None for reserved types
+    pub fn pdu_type(&self) -> Option<BACnetPDU> {
+        BACnetPDU::try_from(self.apdu_type).ok()
+    }
+
+    /// Invoke ID of every PDU type except Unconfirmed-Request
+    pub fn invoke_id(&self) -> Option<u8> {
+        match self.pdu_type()? {
+            BACnetPDU::ConfirmedRequest => self.pci.get(1).copied(),
+            BACnetPDU::UnconfirmedRequest => None,
+            _ => self.pci.first().copied(),
+        }
+    }
+
+    /// Service parameters following the service choice
+    pub fn user_data(&self) -> &[u8] {
+        &self.user_data
+    }
+
+    /// Whether the sender of this APDU waits for a reply (confirmed requests)
+    pub fn expects_reply(&self) -> bool {
+        self.apdu_type == BACnetPDU::ConfirmedRequest.as_u8()
+    }
+}
+
+/// Formatted as the PDU type followed by the service and invoke ID, e.g.
+/// "Confirmed-Request(ReadProperty, invoke-id=1)"
+impl core::fmt::Display for APDU {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Some(pdu_type) = self.pdu_type() else {
+            return write!(f, "Reserved(type={})", self.apdu_type);
+        };
+        write!(f, "{}(", pdu_type)?;
+        let name = match pdu_type {
+            BACnetPDU::UnconfirmedRequest => unconfirmed_service_name(self.service_choice),
+            _ => confirmed_service_name(self.service_choice),
+        };
+        match (pdu_type, name) {
+            (BACnetPDU::SegmentACK, _) => (),
+            (BACnetPDU::Reject | BACnetPDU::Abort, _) => {
+                write!(f, "reason={}", self.service_choice)?
+            }
+            (_, Some(name)) => f.write_str(name)?,
+            (_, None) => write!(f, "service={}", self.service_choice)?,
+        }
+        match (pdu_type, self.invoke_id()) {
+            (BACnetPDU::SegmentACK, Some(id)) => write!(f, "invoke-id={})", id),
+            (_, Some(id)) => write!(f, ", invoke-id={})", id),
+            (_, None) => f.write_str(")"),
+        }
+    }
 }
 
 impl Encode for APDU {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
-        writer.write_u8(self.apdu_type << 4)?;
-        writer.write_u8(self.service_choice)?;
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(self.apdu_type << 4 | self.flags)?;
+        writer.write_all(&self.pci)?;
+        if self.pdu_type().is_none_or(|t| t.has_service_choice()) {
+            writer.write_u8(self.service_choice)?;
+        }
         writer.write_all(&self.user_data)?;
         Ok(())
     }
@@ -82,20 +222,50 @@ impl Encode for APDU {
     fn len(&self) -> usize {
         let mut l = 0;
         l += 1; // Type
-        l += 1; // Service Choice
+        l += self.pci.len(); // Protocol Control Information
+        if self.pdu_type().is_none_or(|t| t.has_service_choice()) {
+            l += 1; // Service Choice
+        }
         l += self.user_data.len(); // Content
         l
     }
 }
 
 impl Decode for APDU {
-    fn decode<T: std::io::Read + Sized>(reader: &mut T) -> std::io::Result<Self> {
-        let apdu_type = reader.read_u8()? >> 4;
-        let service_choice = reader.read_u8()?;
-        let mut content = Vec::new(); // TODO: What capacity?
-        reader.read_to_end(&mut content)?;
-        trace!("APDU Type: {}", apdu_type);
-        Ok(APDU::new(apdu_type, service_choice, content))
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let first = reader.read_u8()?;
+        let apdu_type = first >> 4;
+        let flags = first & 0b0000_1111;
+        let pdu_type = BACnetPDU::try_from(apdu_type)?;
+        trace!("APDU Type: {}", pdu_type);
+
+        let mut pci_len = pdu_type.pci_len();
+        if flags & SEGMENTED_MESSAGE != 0
+            && matches!(
+                pdu_type,
+                BACnetPDU::ConfirmedRequest | BACnetPDU::ComplexACK
+            )
+        {
+            pci_len += 2; // Sequence Number, Proposed Window Size
+        }
+        let mut pci = vec![0u8; pci_len];
+        reader.read_exact(&mut pci)?;
+
+        let service_choice = match pdu_type.has_service_choice() {
+            true => reader.read_u8()?,
+            false => 0,
+        };
+        let mut user_data = Vec::new();
+        reader.read_to_end(&mut user_data)?;
+        Ok(Self {
+            apdu_type,
+            flags,
+            pci,
+            service_choice,
+            user_data,
+        })
     }
 }
 
@@ -103,7 +273,6 @@ impl Decode for APDU {
 mod tests {
     use super::*;
     use crate::{Decode, Encode};
-    use bytes::{BufMut, BytesMut};
     use hex;
 
     #[test]
@@ -111,30 +280,90 @@ mod tests {
         let content = vec![0, 0, 0];
         let apdu = APDU::new(1, 8, content);
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         apdu.encode(&mut w).expect("Write APDU to buffer");
-        assert_eq!(w.into_inner().to_vec(), vec![16, 8, 0, 0, 0]);
+        assert_eq!(w, vec![16, 8, 0, 0, 0]);
     }
 
     #[test]
     fn test_who_is() {
-        let mut data = hex::decode("1008").unwrap();
+        let data = hex::decode("1008").unwrap();
 
-        let apdu = APDU::decode(&mut std::io::Cursor::new(&mut data)).expect("Decode APDU");
+        let apdu = APDU::decode(&mut data.as_slice()).expect("Decode APDU");
 
         assert_eq!(apdu.apdu_type, 0x01);
         assert_eq!(apdu.service_choice, 0x08);
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         apdu.encode(&mut w).expect("Write APDU to buffer");
-        assert_eq!(w.into_inner().to_vec(), data);
+        assert_eq!(w, data);
+    }
+
+    #[test]
+    fn test_pdu_type_unconfirmed_who_is() {
+        let data = hex::decode("1008").unwrap();
+        let apdu = APDU::decode_slice(&data).expect("Decode APDU");
+
+        assert_eq!(apdu.pdu_type(), Some(BACnetPDU::UnconfirmedRequest));
+        assert!(!apdu.expects_reply());
+        assert_eq!(apdu.invoke_id(), None);
+        assert_eq!(apdu.service_choice, 0x08);
+    }
+
+    #[test]
+    fn test_pdu_type_complex_ack_read_property() {
+        // ReadProperty-ACK analog-input,1 present-value 72.5
+        let data = hex::decode("30010c0c0000000119553e44429100003f").unwrap();
+        let apdu = APDU::decode_slice(&data).expect("Decode APDU");
+
+        assert_eq!(apdu.pdu_type(), Some(BACnetPDU::ComplexACK));
+        assert_eq!(apdu.invoke_id(), Some(0x01));
+        assert_eq!(apdu.service_choice, 0x0c);
+        assert_eq!(apdu.user_data()[0], 0x0c);
+        assert_eq!(apdu.encode_vec().unwrap(), data);
+        assert_eq!(apdu.len(), data.len());
+    }
+
+    #[test]
+    fn test_confirmed_request_header() {
+        let data = hex::decode("0005010c0c000000011955").unwrap();
+        let apdu = APDU::decode_slice(&data).expect("Decode APDU");
+
+        assert_eq!(apdu.pdu_type(), Some(BACnetPDU::ConfirmedRequest));
+        assert!(apdu.expects_reply());
+        assert_eq!(apdu.invoke_id(), Some(0x01));
+        assert_eq!(apdu.service_choice, 0x0c);
+        assert_eq!(
+            apdu.user_data(),
+            &[0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55]
+        );
+        assert_eq!(apdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_segment_ack_has_no_service_choice() {
+        let data = hex::decode("40010203").unwrap();
+        let apdu = APDU::decode_slice(&data).expect("Decode APDU");
+
+        assert_eq!(apdu.pdu_type(), Some(BACnetPDU::SegmentACK));
+        assert_eq!(apdu.invoke_id(), Some(0x01));
+        assert_eq!(apdu.encode_vec().unwrap(), data);
+        assert_eq!(apdu.len(), 4);
+    }
+
+    #[test]
+    fn test_decode_reserved_pdu_type() {
+        let err = APDU::decode_slice(&[0x80, 0x00]).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        let err = APDU::decode_slice(&[0x00, 0x05]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
     }
 
     #[test]
     fn test_i_am() {
-        let mut data = hex::decode("1000c4020002572204009100210f").unwrap();
+        let data = hex::decode("1000c4020002572204009100210f").unwrap();
 
-        let apdu = APDU::decode(&mut std::io::Cursor::new(&mut data)).expect("Decode APDU");
+        let apdu = APDU::decode(&mut data.as_slice()).expect("Decode APDU");
 
         assert_eq!(apdu.apdu_type, 0x01);
         assert_eq!(apdu.service_choice, 0x00);
@@ -143,8 +372,24 @@ mod tests {
             vec![196, 2, 0, 2, 87, 34, 4, 0, 145, 0, 33, 15]
         );
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         apdu.encode(&mut w).expect("Write APDU to buffer");
-        assert_eq!(w.into_inner().to_vec(), data);
+        assert_eq!(w, data);
+    }
+
+    #[test]
+    fn test_display_apdu() {
+        let who_is = APDU::decode_slice(&hex::decode("1008").unwrap()).unwrap();
+        assert_eq!(who_is.to_string(), "Unconfirmed-Request(Who-Is)");
+
+        let read_property =
+            APDU::decode_slice(&hex::decode("0005010c0c000000011955").unwrap()).unwrap();
+        assert_eq!(
+            read_property.to_string(),
+            "Confirmed-Request(ReadProperty, invoke-id=1)"
+        );
+
+        let abort = APDU::decode_slice(&hex::decode("700203").unwrap()).unwrap();
+        assert_eq!(abort.to_string(), "Abort(reason=3, invoke-id=2)");
     }
 }
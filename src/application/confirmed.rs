@@ -0,0 +1,431 @@
+use crate::application::{
+    APDU, BACnetPDU, ConfirmedService, MORE_FOLLOWS, SEGMENTED_MESSAGE, SEGMENTED_RESPONSE_ACCEPTED,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// Maximum APDU length accepted (20.1.2.5), ordered by length
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub enum MaxApduLengthAccepted {
+    Up50,   // = 0;
+    Up128,  // = 1;
+    Up206,  // = 2;
+    Up480,  // = 3;
+    Up1024, // = 4;
+    #[default]
+    Up1476, // = 5;
+}
+
+impl MaxApduLengthAccepted {
+    /// The length of a 4-bit code, None for reserved codes
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::try_from(code).ok()
+    }
+
+    /// The 4-bit code of the length
+    pub fn to_code(&self) -> u8 {
+        u8::from(*self)
+    }
+
+    /// Maximum APDU length in octets
+    pub fn max_bytes(&self) -> usize {
+        match self {
+            Self::Up50 => 50,
+            Self::Up128 => 128,
+            Self::Up206 => 206,
+            Self::Up480 => 480,
+            Self::Up1024 => 1024,
+            Self::Up1476 => 1476,
+        }
+    }
+
+    /// Length usable between two devices, the smaller of the lengths both accept
+    pub fn negotiate(local: Self, remote: Self) -> Self {
+        local.min(remote)
+    }
+}
+
+impl TryFrom<u8> for MaxApduLengthAccepted {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Up50),
+            1 => Ok(Self::Up128),
+            2 => Ok(Self::Up206),
+            3 => Ok(Self::Up480),
+            4 => Ok(Self::Up1024),
+            5 => Ok(Self::Up1476),
+            v => Err(Error::InvalidValue(format!(
+                "Max APDU length accepted not supported: {}",
+                v
+            ))),
+        }
+    }
+}
+
+impl From<MaxApduLengthAccepted> for u8 {
+    fn from(value: MaxApduLengthAccepted) -> Self {
+        match value {
+            MaxApduLengthAccepted::Up50 => 0,
+            MaxApduLengthAccepted::Up128 => 1,
+            MaxApduLengthAccepted::Up206 => 2,
+            MaxApduLengthAccepted::Up480 => 3,
+            MaxApduLengthAccepted::Up1024 => 4,
+            MaxApduLengthAccepted::Up1476 => 5,
+        }
+    }
+}
+
+/// Maximum number of segments accepted (20.1.2.4)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum MaxSegmentsAccepted {
+    #[default]
+    Unspecified, // = 0;
+    Up2,        // = 1;
+    Up4,        // = 2;
+    Up8,        // = 3;
+    Up16,       // = 4;
+    Up32,       // = 5;
+    Up64,       // = 6;
+    MoreThan64, // = 7;
+}
+
+impl MaxSegmentsAccepted {
+    /// Largest number of segments accepted, None when unspecified or more than 64
+    pub fn max_segments(&self) -> Option<usize> {
+        match self {
+            Self::Unspecified | Self::MoreThan64 => None,
+            Self::Up2 => Some(2),
+            Self::Up4 => Some(4),
+            Self::Up8 => Some(8),
+            Self::Up16 => Some(16),
+            Self::Up32 => Some(32),
+            Self::Up64 => Some(64),
+        }
+    }
+}
+
+impl From<u8> for MaxSegmentsAccepted {
+    fn from(value: u8) -> Self {
+        match value & 0b0111 {
+            0 => Self::Unspecified,
+            1 => Self::Up2,
+            2 => Self::Up4,
+            3 => Self::Up8,
+            4 => Self::Up16,
+            5 => Self::Up32,
+            6 => Self::Up64,
+            _ => Self::MoreThan64,
+        }
+    }
+}
+
+impl From<MaxSegmentsAccepted> for u8 {
+    fn from(value: MaxSegmentsAccepted) -> Self {
+        match value {
+            MaxSegmentsAccepted::Unspecified => 0,
+            MaxSegmentsAccepted::Up2 => 1,
+            MaxSegmentsAccepted::Up4 => 2,
+            MaxSegmentsAccepted::Up8 => 3,
+            MaxSegmentsAccepted::Up16 => 4,
+            MaxSegmentsAccepted::Up32 => 5,
+            MaxSegmentsAccepted::Up64 => 6,
+            MaxSegmentsAccepted::MoreThan64 => 7,
+        }
+    }
+}
+
+/// BACnet-Confirmed-Request-PDU (20.1.2)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfirmedRequest {
+    pub segmented_message: bool,
+    pub more_follows: bool,
+    pub segmented_response_accepted: bool,
+    pub max_segments: MaxSegmentsAccepted,
+    pub max_apdu: MaxApduLengthAccepted,
+    pub invoke_id: u8,
+    /// Present if the message is segmented
+    pub sequence_number: Option<u8>,
+    /// Present if the message is segmented
+    pub proposed_window_size: Option<u8>,
+    pub service_choice: u8,
+    pub service_request: Vec<u8>,
+}
+
+impl ConfirmedRequest {
+    /// Unsegmented request accepting responses of up to 1476 octets
+    pub fn new(invoke_id: u8, service_choice: u8, service_request: Vec<u8>) -> Self {
+        Self {
+            segmented_message: false,
+            more_follows: false,
+            segmented_response_accepted: false,
+            max_segments: MaxSegmentsAccepted::default(),
+            max_apdu: MaxApduLengthAccepted::default(),
+            invoke_id,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_choice,
+            service_request,
+        }
+    }
+
+    fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.segmented_message {
+            flags |= SEGMENTED_MESSAGE;
+        }
+        if self.more_follows {
+            flags |= MORE_FOLLOWS;
+        }
+        if self.segmented_response_accepted {
+            flags |= SEGMENTED_RESPONSE_ACCEPTED;
+        }
+        flags
+    }
+
+    fn pci(&self) -> Vec<u8> {
+        let mut pci = vec![
+            u8::from(self.max_segments) << 4 | u8::from(self.max_apdu),
+            self.invoke_id,
+        ];
+        if self.segmented_message {
+            pci.push(self.sequence_number.unwrap_or(0));
+            pci.push(self.proposed_window_size.unwrap_or(1));
+        }
+        pci
+    }
+}
+
+impl Encode for ConfirmedRequest {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(BACnetPDU::ConfirmedRequest.as_u8() << 4 | self.flags())?;
+        writer.write_all(&self.pci())?;
+        writer.write_u8(self.service_choice)?;
+        writer.write_all(&self.service_request)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1; // Type and flags
+        l += 2; // Max Segments/APDU, Invoke ID
+        if self.segmented_message {
+            l += 2; // Sequence Number, Proposed Window Size
+        }
+        l += 1; // Service Choice
+        l += self.service_request.len();
+        l
+    }
+}
+
+impl Decode for ConfirmedRequest {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let apdu = APDU::decode(reader)?;
+        Self::try_from(apdu)
+    }
+}
+
+impl TryFrom<APDU> for ConfirmedRequest {
+    type Error = Error;
+
+    fn try_from(apdu: APDU) -> Result<Self, Self::Error> {
+        if apdu.pdu_type() != Some(BACnetPDU::ConfirmedRequest) {
+            return Err(Error::InvalidValue(format!(
+                "Not a Confirmed-Request: {:?}",
+                apdu.pdu_type()
+            )));
+        }
+        let (control, invoke_id) = match apdu.pci[..] {
+            [control, invoke_id, ..] => (control, invoke_id),
+            _ => return Err(Error::Truncated),
+        };
+        let segmented_message = apdu.flags & SEGMENTED_MESSAGE != 0;
+        let (sequence_number, proposed_window_size) = match segmented_message {
+            true => (apdu.pci.get(2).copied(), apdu.pci.get(3).copied()),
+            false => (None, None),
+        };
+        Ok(Self {
+            segmented_message,
+            more_follows: apdu.flags & MORE_FOLLOWS != 0,
+            segmented_response_accepted: apdu.flags & SEGMENTED_RESPONSE_ACCEPTED != 0,
+            max_segments: MaxSegmentsAccepted::from(control >> 4),
+            max_apdu: MaxApduLengthAccepted::try_from(control & 0b1111)?,
+            invoke_id,
+            sequence_number,
+            proposed_window_size,
+            service_choice: apdu.service_choice,
+            service_request: apdu.user_data,
+        })
+    }
+}
+
+/// Protocol control information of a Confirmed-Request a reply is built from (20.1.2)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConfirmedRequestHeader {
+    pub segmented_response_accepted: bool,
+    pub max_segments: MaxSegmentsAccepted,
+    pub max_apdu: MaxApduLengthAccepted,
+    pub invoke_id: u8,
+}
+
+impl ConfirmedRequest {
+    pub fn header(&self) -> ConfirmedRequestHeader {
+        ConfirmedRequestHeader {
+            segmented_response_accepted: self.segmented_response_accepted,
+            max_segments: self.max_segments,
+            max_apdu: self.max_apdu,
+            invoke_id: self.invoke_id,
+        }
+    }
+}
+
+/// Confirmed-Request decoded into its header and service request, for dispatching received
+/// requests by service
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfirmedServiceRequest {
+    pub header: ConfirmedRequestHeader,
+    pub service: ConfirmedService,
+}
+
+impl TryFrom<ConfirmedRequest> for ConfirmedServiceRequest {
+    type Error = Error;
+
+    fn try_from(request: ConfirmedRequest) -> crate::Result<Self> {
+        if request.segmented_message {
+            return Err(Error::Unsupported(
+                "Segmented Confirmed-Request must be reassembled first".into(),
+            ));
+        }
+        Ok(Self {
+            header: request.header(),
+            service: ConfirmedService::decode_service(
+                request.service_choice,
+                &mut request.service_request.as_slice(),
+            )?,
+        })
+    }
+}
+
+impl Decode for ConfirmedServiceRequest {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        Self::try_from(ConfirmedRequest::decode(reader)?)
+    }
+}
+
+impl From<ConfirmedRequest> for APDU {
+    fn from(request: ConfirmedRequest) -> Self {
+        Self {
+            apdu_type: BACnetPDU::ConfirmedRequest.as_u8(),
+            flags: request.flags(),
+            pci: request.pci(),
+            service_choice: request.service_choice,
+            user_data: request.service_request,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ReadProperty;
+    use crate::encoding::{ObjectIdentifier, ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_read_property_confirmed_request() {
+        // ReadProperty analog-input,1 present-value
+        let request =
+            ConfirmedRequest::new(42, 0x0c, vec![0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55]);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("00052a0c0c000000011955").unwrap());
+        assert_eq!(data.len(), request.len());
+
+        let decoded = ConfirmedRequest::decode_slice(&data).expect("Decode request");
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.max_apdu.max_bytes(), 1476);
+
+        let apdu = APDU::from(request);
+        assert_eq!(apdu.invoke_id(), Some(42));
+        assert_eq!(apdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_read_property_service_request() {
+        let data = hex::decode("02452a0c0c000000011955").unwrap();
+        let request = ConfirmedServiceRequest::decode_slice(&data).expect("Decode request");
+
+        assert_eq!(
+            request.header,
+            ConfirmedRequestHeader {
+                segmented_response_accepted: true,
+                max_segments: MaxSegmentsAccepted::Up16,
+                max_apdu: MaxApduLengthAccepted::Up1476,
+                invoke_id: 42,
+            }
+        );
+        assert_eq!(
+            request.service,
+            ConfirmedService::ReadProperty(ReadProperty::new(
+                ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap(),
+                PropertyIdentifier::PresentValue,
+            ))
+        );
+
+        // Unknown service choice
+        let err = ConfirmedServiceRequest::decode_slice(&[0x00, 0x05, 0x01, 0x40]).unwrap_err();
+        assert!(matches!(err, Error::UnknownService(0x40)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_max_apdu_length_accepted_codes() {
+        let max_apdu = MaxApduLengthAccepted::from_code(5).unwrap();
+        assert_eq!(max_apdu, MaxApduLengthAccepted::Up1476);
+        assert_eq!(max_apdu.max_bytes(), 1476);
+        assert_eq!(max_apdu.to_code(), 5);
+        assert_eq!(MaxApduLengthAccepted::from_code(0).unwrap().max_bytes(), 50);
+        assert_eq!(MaxApduLengthAccepted::from_code(6), None);
+    }
+
+    #[test]
+    fn test_negotiate_max_apdu_length_accepted() {
+        let local = MaxApduLengthAccepted::Up1476;
+        let remote = MaxApduLengthAccepted::Up480;
+        assert_eq!(
+            MaxApduLengthAccepted::negotiate(local, remote).max_bytes(),
+            480
+        );
+        assert_eq!(
+            MaxApduLengthAccepted::negotiate(remote, local).max_bytes(),
+            480
+        );
+        assert_eq!(MaxApduLengthAccepted::negotiate(local, local), local);
+    }
+
+    #[test]
+    fn test_segmented_confirmed_request() {
+        let mut request = ConfirmedRequest::new(7, 0x0e, vec![0x0c; 10]);
+        request.segmented_message = true;
+        request.more_follows = true;
+        request.segmented_response_accepted = true;
+        request.max_segments = MaxSegmentsAccepted::Up16;
+        request.max_apdu = MaxApduLengthAccepted::Up480;
+        request.sequence_number = Some(0);
+        request.proposed_window_size = Some(4);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(&data[..6], &[0x0e, 0x43, 0x07, 0x00, 0x04, 0x0e]);
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ConfirmedRequest::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_decode_confirmed_request_invalid() {
+        assert!(ConfirmedRequest::decode_slice(&hex::decode("1008").unwrap()).is_err());
+        assert!(ConfirmedRequest::decode_slice(&[0x00, 0x0f, 0x01, 0x0c]).is_err());
+    }
+}
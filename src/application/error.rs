@@ -0,0 +1,363 @@
+use crate::application::{APDU, BACnetPDU};
+use crate::encoding::{ApplicationValue, decode_application_value};
+use crate::{Encode, Error};
+
+/// BACnetErrorClass (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorClass {
+    Device,        // = 0;
+    Object,        // = 1;
+    Property,      // = 2;
+    Resources,     // = 3;
+    Security,      // = 4;
+    Services,      // = 5;
+    VT,            // = 6;
+    Communication, // = 7;
+    Unknown(u32),
+}
+
+impl From<u32> for ErrorClass {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Device,
+            1 => Self::Object,
+            2 => Self::Property,
+            3 => Self::Resources,
+            4 => Self::Security,
+            5 => Self::Services,
+            6 => Self::VT,
+            7 => Self::Communication,
+            v => Self::Unknown(v),
+        }
+    }
+}
+
+impl From<ErrorClass> for u32 {
+    fn from(value: ErrorClass) -> Self {
+        match value {
+            ErrorClass::Device => 0,
+            ErrorClass::Object => 1,
+            ErrorClass::Property => 2,
+            ErrorClass::Resources => 3,
+            ErrorClass::Security => 4,
+            ErrorClass::Services => 5,
+            ErrorClass::VT => 6,
+            ErrorClass::Communication => 7,
+            ErrorClass::Unknown(v) => v,
+        }
+    }
+}
+
+/// BACnetErrorCode (21), codes without a variant are kept as their numeric value
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    Other,                             // = 0;
+    ConfigurationInProgress,           // = 2;
+    DeviceBusy,                        // = 3;
+    InvalidDataType,                   // = 9;
+    OperationalProblem,                // = 25;
+    ReadAccessDenied,                  // = 27;
+    ServiceRequestDenied,              // = 29;
+    Timeout,                           // = 30;
+    UnknownObject,                     // = 31;
+    UnknownProperty,                   // = 32;
+    UnsupportedObjectType,             // = 36;
+    ValueOutOfRange,                   // = 37;
+    WriteAccessDenied,                 // = 40;
+    InvalidArrayIndex,                 // = 42;
+    OptionalFunctionalityNotSupported, // = 45;
+    PropertyIsNotAnArray,              // = 50;
+    Unknown(u32),
+}
+
+impl From<u32> for ErrorCode {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Other,
+            2 => Self::ConfigurationInProgress,
+            3 => Self::DeviceBusy,
+            9 => Self::InvalidDataType,
+            25 => Self::OperationalProblem,
+            27 => Self::ReadAccessDenied,
+            29 => Self::ServiceRequestDenied,
+            30 => Self::Timeout,
+            31 => Self::UnknownObject,
+            32 => Self::UnknownProperty,
+            36 => Self::UnsupportedObjectType,
+            37 => Self::ValueOutOfRange,
+            40 => Self::WriteAccessDenied,
+            42 => Self::InvalidArrayIndex,
+            45 => Self::OptionalFunctionalityNotSupported,
+            50 => Self::PropertyIsNotAnArray,
+            v => Self::Unknown(v),
+        }
+    }
+}
+
+impl From<ErrorCode> for u32 {
+    fn from(value: ErrorCode) -> Self {
+        match value {
+            ErrorCode::Other => 0,
+            ErrorCode::ConfigurationInProgress => 2,
+            ErrorCode::DeviceBusy => 3,
+            ErrorCode::InvalidDataType => 9,
+            ErrorCode::OperationalProblem => 25,
+            ErrorCode::ReadAccessDenied => 27,
+            ErrorCode::ServiceRequestDenied => 29,
+            ErrorCode::Timeout => 30,
+            ErrorCode::UnknownObject => 31,
+            ErrorCode::UnknownProperty => 32,
+            ErrorCode::UnsupportedObjectType => 36,
+            ErrorCode::ValueOutOfRange => 37,
+            ErrorCode::WriteAccessDenied => 40,
+            ErrorCode::InvalidArrayIndex => 42,
+            ErrorCode::OptionalFunctionalityNotSupported => 45,
+            ErrorCode::PropertyIsNotAnArray => 50,
+            ErrorCode::Unknown(v) => v,
+        }
+    }
+}
+
+/// Error parameters of a BACnet-Error-PDU (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BACnetError {
+    pub class: ErrorClass,
+    pub code: ErrorCode,
+}
+
+impl BACnetError {
+    pub fn new(class: ErrorClass, code: ErrorCode) -> Self {
+        Self { class, code }
+    }
+
+    fn values(&self) -> [ApplicationValue; 2] {
+        [
+            ApplicationValue::Enumerated(self.class.into()),
+            ApplicationValue::Enumerated(self.code.into()),
+        ]
+    }
+}
+
+impl Encode for BACnetError {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        for value in self.values() {
+            value.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.values().iter().map(|v| v.len()).sum()
+    }
+}
+
+impl TryFrom<&[u8]> for BACnetError {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> crate::Result<Self> {
+        let enumerated = |data| match decode_application_value(data)? {
+            (ApplicationValue::Enumerated(v), rest) => Ok((v, rest)),
+            (v, _) => Err(Error::InvalidValue(format!(
+                "Expected Enumerated, found {:?}",
+                v
+            ))),
+        };
+        let (class, rest) = enumerated(data)?;
+        let (code, _) = enumerated(rest)?;
+        Ok(Self::new(class.into(), code.into()))
+    }
+}
+
+impl TryFrom<&APDU> for BACnetError {
+    type Error = Error;
+
+    fn try_from(apdu: &APDU) -> crate::Result<Self> {
+        match apdu.pdu_type() {
+            Some(BACnetPDU::Error) => Self::try_from(apdu.user_data()),
+            t => Err(Error::InvalidValue(format!("Not an Error PDU: {:?}", t))),
+        }
+    }
+}
+
+/// BACnetRejectReason (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RejectReason {
+    Other,                    // = 0;
+    BufferOverflow,           // = 1;
+    InconsistentParameters,   // = 2;
+    InvalidParameterDataType, // = 3;
+    InvalidTag,               // = 4;
+    MissingRequiredParameter, // = 5;
+    ParameterOutOfRange,      // = 6;
+    TooManyArguments,         // = 7;
+    UndefinedEnumeration,     // = 8;
+    UnrecognizedService,      // = 9;
+    Unknown(u8),
+}
+
+impl From<u8> for RejectReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InconsistentParameters,
+            3 => Self::InvalidParameterDataType,
+            4 => Self::InvalidTag,
+            5 => Self::MissingRequiredParameter,
+            6 => Self::ParameterOutOfRange,
+            7 => Self::TooManyArguments,
+            8 => Self::UndefinedEnumeration,
+            9 => Self::UnrecognizedService,
+            v => Self::Unknown(v),
+        }
+    }
+}
+
+impl From<RejectReason> for u8 {
+    fn from(value: RejectReason) -> Self {
+        match value {
+            RejectReason::Other => 0,
+            RejectReason::BufferOverflow => 1,
+            RejectReason::InconsistentParameters => 2,
+            RejectReason::InvalidParameterDataType => 3,
+            RejectReason::InvalidTag => 4,
+            RejectReason::MissingRequiredParameter => 5,
+            RejectReason::ParameterOutOfRange => 6,
+            RejectReason::TooManyArguments => 7,
+            RejectReason::UndefinedEnumeration => 8,
+            RejectReason::UnrecognizedService => 9,
+            RejectReason::Unknown(v) => v,
+        }
+    }
+}
+
+/// BACnetAbortReason (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AbortReason {
+    Other,                         // = 0;
+    BufferOverflow,                // = 1;
+    InvalidApduInThisState,        // = 2;
+    PreemptedByHigherPriorityTask, // = 3;
+    SegmentationNotSupported,      // = 4;
+    SecurityError,                 // = 5;
+    InsufficientSecurity,          // = 6;
+    WindowSizeOutOfRange,          // = 7;
+    ApplicationExceededReplyTime,  // = 8;
+    OutOfResources,                // = 9;
+    TsmTimeout,                    // = 10;
+    ApduTooLong,                   // = 11;
+    Unknown(u8),
+}
+
+impl From<u8> for AbortReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InvalidApduInThisState,
+            3 => Self::PreemptedByHigherPriorityTask,
+            4 => Self::SegmentationNotSupported,
+            5 => Self::SecurityError,
+            6 => Self::InsufficientSecurity,
+            7 => Self::WindowSizeOutOfRange,
+            8 => Self::ApplicationExceededReplyTime,
+            9 => Self::OutOfResources,
+            10 => Self::TsmTimeout,
+            11 => Self::ApduTooLong,
+            v => Self::Unknown(v),
+        }
+    }
+}
+
+impl From<AbortReason> for u8 {
+    fn from(value: AbortReason) -> Self {
+        match value {
+            AbortReason::Other => 0,
+            AbortReason::BufferOverflow => 1,
+            AbortReason::InvalidApduInThisState => 2,
+            AbortReason::PreemptedByHigherPriorityTask => 3,
+            AbortReason::SegmentationNotSupported => 4,
+            AbortReason::SecurityError => 5,
+            AbortReason::InsufficientSecurity => 6,
+            AbortReason::WindowSizeOutOfRange => 7,
+            AbortReason::ApplicationExceededReplyTime => 8,
+            AbortReason::OutOfResources => 9,
+            AbortReason::TsmTimeout => 10,
+            AbortReason::ApduTooLong => 11,
+            AbortReason::Unknown(v) => v,
+        }
+    }
+}
+
+impl APDU {
+    /// Reason of a BACnet-Reject-PDU
+    pub fn reject_reason(&self) -> Option<RejectReason> {
+        match self.pdu_type()? {
+            BACnetPDU::Reject => Some(self.service_choice.into()),
+            _ => None,
+        }
+    }
+
+    /// Reason of a BACnet-Abort-PDU
+    pub fn abort_reason(&self) -> Option<AbortReason> {
+        match self.pdu_type()? {
+            BACnetPDU::Abort => Some(self.service_choice.into()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+
+    #[test]
+    fn test_decode_error_unknown_object() {
+        // Error ReadProperty, class object, code unknown-object
+        let data = hex::decode("50010c9101911f").unwrap();
+        let apdu = APDU::decode_slice(&data).expect("Decode APDU");
+
+        assert_eq!(apdu.invoke_id(), Some(1));
+        assert_eq!(apdu.service_choice, 0x0c);
+        let error = BACnetError::try_from(&apdu).expect("Decode error");
+        assert_eq!(
+            error,
+            BACnetError::new(ErrorClass::Object, ErrorCode::UnknownObject)
+        );
+        assert_eq!(error.encode_vec().unwrap(), &data[3..]);
+    }
+
+    #[test]
+    fn test_decode_error_unknown_code() {
+        let error = BACnetError::try_from(&[0x91, 0x02, 0x92, 0x01, 0x00][..]).unwrap();
+        assert_eq!(error.class, ErrorClass::Property);
+        assert_eq!(error.code, ErrorCode::Unknown(256));
+        assert!(BACnetError::try_from(&[0x91, 0x02][..]).is_err());
+        let error = BACnetError::try_from(&[0x91, 0x40, 0x91, 0x00][..]).unwrap();
+        assert_eq!(error.class, ErrorClass::Unknown(64));
+        assert_eq!(u32::from(error.class), 64);
+    }
+
+    #[test]
+    fn test_decode_abort_buffer_overflow() {
+        let apdu = APDU::decode_slice(&[0x71, 0x05, 0x01]).expect("Decode APDU");
+
+        assert_eq!(apdu.pdu_type(), Some(BACnetPDU::Abort));
+        assert_eq!(apdu.invoke_id(), Some(5));
+        assert_eq!(apdu.abort_reason(), Some(AbortReason::BufferOverflow));
+        assert_eq!(apdu.reject_reason(), None);
+    }
+
+    #[test]
+    fn test_decode_reject_unrecognized_service() {
+        let apdu = APDU::decode_slice(&[0x60, 0x05, 0x09]).expect("Decode APDU");
+
+        assert_eq!(
+            apdu.reject_reason(),
+            Some(RejectReason::UnrecognizedService)
+        );
+        assert_eq!(RejectReason::from(200), RejectReason::Unknown(200));
+        assert_eq!(u8::from(AbortReason::Unknown(200)), 200);
+    }
+}
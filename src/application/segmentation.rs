@@ -0,0 +1,330 @@
+use crate::application::{APDU, BACnetPDU, ConfirmedRequest, MaxSegmentsAccepted};
+use crate::{Encode, Error};
+use alloc::vec::Vec;
+
+/// Octets of a segmented Confirmed-Request header: type, max segments/APDU, invoke ID,
+/// sequence number, proposed window size and service choice (20.1.2)
+const SEGMENTED_HEADER_LEN: usize = 6;
+
+/// Sequence numbers are a single octet, a message has at most 256 segments (20.1.2.7)
+const MAX_SEQUENCE_SEGMENTS: usize = 256;
+
+/// Split a request into segments of at most `max_apdu` octets (5.2), a request that fits is
+/// returned unchanged. Fails if `max_apdu` leaves no room for data, `window_size` is not within
+/// 1..=127 (20.1.2.8), or the request needs more segments than the peer's `max_segments` or the
+/// sequence number allows
+pub fn segment_request(
+    request: &ConfirmedRequest,
+    max_apdu: usize,
+    max_segments: MaxSegmentsAccepted,
+    window_size: u8,
+) -> crate::Result<Vec<ConfirmedRequest>> {
+    if max_apdu <= SEGMENTED_HEADER_LEN {
+        return Err(Error::InvalidValue(format!(
+            "Max APDU length too small to segment: {}",
+            max_apdu
+        )));
+    }
+    if !(1..=127).contains(&window_size) {
+        return Err(Error::InvalidValue(format!(
+            "Window size out of range: {}",
+            window_size
+        )));
+    }
+    if request.len() <= max_apdu {
+        return Ok(vec![request.clone()]);
+    }
+
+    let chunks: Vec<&[u8]> = request
+        .service_request
+        .chunks(max_apdu - SEGMENTED_HEADER_LEN)
+        .collect();
+    let count = chunks.len();
+    let limit = max_segments
+        .max_segments()
+        .map_or(MAX_SEQUENCE_SEGMENTS, |max| max.min(MAX_SEQUENCE_SEGMENTS));
+    if count > limit {
+        return Err(Error::InvalidValue(format!(
+            "Request needs {} segments, at most {} accepted",
+            count, limit
+        )));
+    }
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| ConfirmedRequest {
+            segmented_message: true,
+            more_follows: i + 1 < count,
+            sequence_number: Some(i as u8),
+            proposed_window_size: Some(window_size),
+            service_request: chunk.to_vec(),
+            ..request.clone()
+        })
+        .collect())
+}
+
+/// Negative acknowledgement flag of a BACnet-SegmentACK-PDU (20.1.6.1)
+const NEGATIVE_ACK: u8 = 0b0000_0010;
+
+/// Server flag of a BACnet-SegmentACK-PDU, set if it is sent by the server (20.1.6.2)
+const SERVER: u8 = 0b0000_0001;
+
+/// BACnet-SegmentACK-PDU (20.1.6), acknowledging the segments received up to `sequence_number`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SegmentAck {
+    /// The segment after `sequence_number` was not received in order and must be sent again
+    pub negative: bool,
+    /// Sent by the server of the transaction, acknowledging segments of a request
+    pub server: bool,
+    pub invoke_id: u8,
+    pub sequence_number: u8,
+    pub actual_window_size: u8,
+}
+
+impl From<SegmentAck> for APDU {
+    fn from(ack: SegmentAck) -> Self {
+        let mut flags = 0;
+        if ack.negative {
+            flags |= NEGATIVE_ACK;
+        }
+        if ack.server {
+            flags |= SERVER;
+        }
+        APDU {
+            apdu_type: BACnetPDU::SegmentACK.as_u8(),
+            flags,
+            pci: vec![ack.invoke_id, ack.sequence_number, ack.actual_window_size],
+            service_choice: 0,
+            user_data: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<&APDU> for SegmentAck {
+    type Error = Error;
+
+    fn try_from(apdu: &APDU) -> Result<Self, Self::Error> {
+        if apdu.pdu_type() != Some(BACnetPDU::SegmentACK) {
+            return Err(Error::InvalidValue(format!(
+                "Not a Segment-ACK: {:?}",
+                apdu.pdu_type()
+            )));
+        }
+        let [invoke_id, sequence_number, actual_window_size] = apdu.pci[..] else {
+            return Err(Error::Truncated);
+        };
+        Ok(Self {
+            negative: apdu.flags & NEGATIVE_ACK != 0,
+            server: apdu.flags & SERVER != 0,
+            invoke_id,
+            sequence_number,
+            actual_window_size,
+        })
+    }
+}
+
+/// Outcome of passing a segment to the reassembler
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Reassembly {
+    /// The segment was accepted, more are expected
+    Incomplete,
+    /// The segment was already received and has been ignored
+    Duplicate,
+    /// The segment is not the next one expected and has been discarded
+    OutOfOrder { expected: u8 },
+    /// The last segment was received, this is the complete request
+    Complete(ConfirmedRequest),
+}
+
+/// Reassembles the segments of a single segmented request (5.4)
+#[derive(Clone, Debug, Default)]
+pub struct Reassembler {
+    first: Option<ConfirmedRequest>,
+    next_sequence: u8,
+    data: Vec<u8>,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sequence number of the next segment expected
+    pub fn next_sequence(&self) -> u8 {
+        self.next_sequence
+    }
+
+    pub fn push(&mut self, segment: ConfirmedRequest) -> crate::Result<Reassembly> {
+        if !segment.segmented_message {
+            return Ok(Reassembly::Complete(segment));
+        }
+        match &self.first {
+            Some(first) if first.invoke_id != segment.invoke_id => {
+                return Err(Error::InvalidValue(format!(
+                    "Segment for invoke ID {} while reassembling {}",
+                    segment.invoke_id, first.invoke_id
+                )));
+            }
+            _ => (),
+        }
+
+        let sequence = segment.sequence_number.unwrap_or(0);
+        if sequence != self.next_sequence {
+            // A segment from the current window that was already received
+            let window = segment.proposed_window_size.unwrap_or(1);
+            if self.next_sequence.wrapping_sub(sequence) <= window && self.first.is_some() {
+                return Ok(Reassembly::Duplicate);
+            }
+            return Ok(Reassembly::OutOfOrder {
+                expected: self.next_sequence,
+            });
+        }
+
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.data.extend_from_slice(&segment.service_request);
+        let more_follows = segment.more_follows;
+        if self.first.is_none() {
+            self.first = Some(segment);
+        }
+        if more_follows {
+            return Ok(Reassembly::Incomplete);
+        }
+
+        let first = self.first.take().expect("First segment");
+        let request = ConfirmedRequest {
+            segmented_message: false,
+            more_follows: false,
+            sequence_number: None,
+            proposed_window_size: None,
+            service_request: core::mem::take(&mut self.data),
+            ..first
+        };
+        self.next_sequence = 0;
+        Ok(Reassembly::Complete(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+
+    #[test]
+    fn test_segment_and_reassemble_900_octets() {
+        let payload: Vec<u8> = (0..900).map(|i| i as u8).collect();
+        let request = ConfirmedRequest::new(3, 0x0e, payload.clone());
+
+        let segments = segment_request(&request, 480, MaxSegmentsAccepted::Up2, 2).unwrap();
+        assert_eq!(segments.len(), 2);
+        for (i, segment) in segments.iter().enumerate() {
+            assert!(segment.len() <= 480);
+            assert_eq!(segment.sequence_number, Some(i as u8));
+            assert_eq!(segment.more_follows, i == 0);
+        }
+
+        let mut reassembler = Reassembler::new();
+        assert_eq!(
+            reassembler.push(segments[0].clone()).unwrap(),
+            Reassembly::Incomplete
+        );
+        match reassembler.push(segments[1].clone()) {
+            Ok(Reassembly::Complete(r)) => {
+                assert_eq!(r.service_request, payload);
+                assert_eq!(r, request);
+            }
+            r => panic!("Not complete: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_reassemble_duplicate_and_out_of_order() {
+        let request = ConfirmedRequest::new(3, 0x0e, vec![0xaa; 1000]);
+        let segments = segment_request(&request, 206, MaxSegmentsAccepted::Up8, 4).unwrap();
+        assert_eq!(segments.len(), 5);
+
+        let mut reassembler = Reassembler::new();
+        assert_eq!(
+            reassembler.push(segments[1].clone()).unwrap(),
+            Reassembly::OutOfOrder { expected: 0 }
+        );
+        assert_eq!(
+            reassembler.push(segments[0].clone()).unwrap(),
+            Reassembly::Incomplete
+        );
+        assert_eq!(
+            reassembler.push(segments[0].clone()).unwrap(),
+            Reassembly::Duplicate
+        );
+        assert_eq!(
+            reassembler.push(segments[2].clone()).unwrap(),
+            Reassembly::OutOfOrder { expected: 1 }
+        );
+        for segment in &segments[1..4] {
+            assert_eq!(
+                reassembler.push(segment.clone()).unwrap(),
+                Reassembly::Incomplete
+            );
+        }
+        assert!(matches!(
+            reassembler.push(segments[4].clone()),
+            Ok(Reassembly::Complete(r)) if r == request
+        ));
+    }
+
+    #[test]
+    fn test_segment_ack() {
+        let ack = SegmentAck {
+            negative: false,
+            server: true,
+            invoke_id: 3,
+            sequence_number: 1,
+            actual_window_size: 2,
+        };
+        let apdu = APDU::from(ack);
+        assert_eq!(apdu.encode_vec().unwrap(), vec![0x41, 0x03, 0x01, 0x02]);
+        assert_eq!(SegmentAck::try_from(&apdu).unwrap(), ack);
+
+        let apdu = APDU::decode_slice(&[0x42, 0x03, 0x01, 0x02]).unwrap();
+        let nak = SegmentAck::try_from(&apdu).unwrap();
+        assert!(nak.negative && !nak.server);
+    }
+
+    #[test]
+    fn test_request_that_fits_is_not_segmented() {
+        let request = ConfirmedRequest::new(1, 0x0c, vec![0x0c; 7]);
+        assert_eq!(
+            segment_request(&request, 480, MaxSegmentsAccepted::Up2, 1).unwrap(),
+            vec![request]
+        );
+    }
+
+    #[test]
+    fn test_segment_request_limits() {
+        let request = ConfirmedRequest::new(1, 0x0e, vec![0x55; 1000]);
+        let unlimited = MaxSegmentsAccepted::Unspecified;
+
+        // No room for data after the segment header
+        let err = segment_request(&request, 6, unlimited, 1).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        // Window size 0
+        let err = segment_request(&request, 480, unlimited, 0).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        // 3 segments of 474 octets, the peer accepts 2
+        let err = segment_request(&request, 480, MaxSegmentsAccepted::Up2, 1).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        assert_eq!(
+            segment_request(&request, 480, MaxSegmentsAccepted::Up4, 1)
+                .unwrap()
+                .len(),
+            3
+        );
+        // 1000 segments of a single octet would wrap the sequence number
+        let err = segment_request(&request, 7, unlimited, 1).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let request = ConfirmedRequest::new(1, 0x0e, vec![0x55; 256]);
+        assert_eq!(
+            segment_request(&request, 7, unlimited, 1).unwrap().len(),
+            256
+        );
+    }
+}
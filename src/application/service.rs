@@ -1,73 +1,722 @@
-use crate::{Decode, Encode};
-use byteorder::ReadBytesExt;
+use crate::application::{APDU, BACnetPDU, ConfirmedRequest, SEGMENTED_MESSAGE};
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, Tag, decode_application_value, decode_context,
+    decode_unsigned, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+pub mod acknowledge_alarm;
+pub mod atomic_read_file;
+pub mod atomic_write_file;
+pub mod cov_notification;
+pub mod create_object;
+pub mod delete_object;
+pub mod device_communication_control;
+pub mod event_notification;
+pub mod get_alarm_summary;
+pub mod get_event_information;
+pub mod read_property;
+pub mod read_property_multiple;
+pub mod read_range;
+pub mod reinitialize_device;
+pub mod subscribe_cov;
+pub mod time_synchronization;
+pub mod who_has;
+pub mod write_property;
+pub mod write_property_multiple;
+pub use acknowledge_alarm::*;
+pub use atomic_read_file::*;
+pub use atomic_write_file::*;
+pub use cov_notification::*;
+pub use create_object::*;
+pub use delete_object::*;
+pub use device_communication_control::*;
+pub use event_notification::*;
+pub use get_alarm_summary::*;
+pub use get_event_information::*;
+pub use read_property::*;
+pub use read_property_multiple::*;
+pub use read_range::*;
+pub use reinitialize_device::*;
+pub use subscribe_cov::*;
+pub use time_synchronization::*;
+pub use who_has::*;
+pub use write_property::*;
+pub use write_property_multiple::*;
+
+/// Request parameters of a confirmed service (21)
+pub trait ConfirmedRequestService: Encode {
+    /// Confirmed service choice (21)
+    const SERVICE_CHOICE: u8;
+
+    /// Confirmed-Request APDU carrying this request
+    fn confirmed_request(&self, invoke_id: u8) -> crate::Result<ConfirmedRequest> {
+        Ok(ConfirmedRequest::new(
+            invoke_id,
+            Self::SERVICE_CHOICE,
+            self.encode_vec()?,
+        ))
+    }
+}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Service {}
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum UnconfirmedService {
-    IAm(IAm),                           // = 0;
-    IHave,                              // = 1;
-    UnconfirmedCovNotification,         // = 2;
-    UnconfirmedEventNotification,       // = 3;
-    UnconfirmedPrivateTransfer,         // = 4;
-    UnconfirmedTextMessage,             // = 5;
-    TimeSynchronization,                // = 6;
-    WhoHas,                             // = 7;
-    WhoIs(),                            // = 8;
-    UtcTimeSynchronization,             // = 9;
-    WriteGroup,                         // = 10;
-    UnconfirmedCovNotificationMultiple, // = 11;
+    IAm(IAm),                                        // = 0;
+    IHave(IHave),                                    // = 1;
+    UnconfirmedCovNotification(CovNotification),     // = 2;
+    UnconfirmedEventNotification(EventNotification), // = 3;
+    UnconfirmedPrivateTransfer,                      // = 4;
+    UnconfirmedTextMessage,                          // = 5;
+    TimeSynchronization(TimeSynchronization),        // = 6;
+    WhoHas(WhoHas),                                  // = 7;
+    WhoIs(WhoIs),                                    // = 8;
+    UtcTimeSynchronization(TimeSynchronization),     // = 9;
+    WriteGroup,                                      // = 10;
+    UnconfirmedCovNotificationMultiple,              // = 11;
 }
 
 impl Decode for UnconfirmedService {
-    fn decode<T: std::io::Read + Sized>(reader: &mut T) -> std::io::Result<Self> {
-        // TODO: Add checks
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
         let type_ = reader.read_u8()?;
 
         match type_ {
             0x00 => Ok(Self::IAm(IAm::decode(reader)?)),
-            0x08 => Ok(Self::WhoIs()),
-            _t => unimplemented!(),
+            0x01 => Ok(Self::IHave(IHave::decode(reader)?)),
+            0x02 => Ok(Self::UnconfirmedCovNotification(CovNotification::decode(
+                reader,
+            )?)),
+            0x03 => Ok(Self::UnconfirmedEventNotification(
+                EventNotification::decode(reader)?,
+            )),
+            0x06 => Ok(Self::TimeSynchronization(TimeSynchronization::decode(
+                reader,
+            )?)),
+            0x07 => Ok(Self::WhoHas(WhoHas::decode(reader)?)),
+            0x08 => Ok(Self::WhoIs(WhoIs::decode(reader)?)),
+            0x09 => Ok(Self::UtcTimeSynchronization(TimeSynchronization::decode(
+                reader,
+            )?)),
+            t => Err(Error::UnknownService(t)),
         }
     }
 }
 
 impl Encode for UnconfirmedService {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
         match self {
             Self::IAm(a) => a.encode(writer),
-            Self::WhoIs() => Ok(()),
-            _ => unimplemented!(),
+            Self::IHave(i) => i.encode(writer),
+            Self::UnconfirmedCovNotification(n) => n.encode(writer),
+            Self::UnconfirmedEventNotification(n) => n.encode(writer),
+            Self::TimeSynchronization(t) | Self::UtcTimeSynchronization(t) => t.encode(writer),
+            Self::WhoHas(w) => w.encode(writer),
+            Self::WhoIs(w) => w.encode(writer),
+            s => Err(Error::Unsupported(format!(
+                "Unconfirmed service not supported: {:?}",
+                s
+            ))),
         }
     }
 
     fn len(&self) -> usize {
         match self {
             Self::IAm(a) => a.len(),
-            Self::WhoIs() => 0,
-            _ => unimplemented!(),
+            Self::IHave(i) => i.len(),
+            Self::UnconfirmedCovNotification(n) => n.len(),
+            Self::UnconfirmedEventNotification(n) => n.len(),
+            Self::TimeSynchronization(t) | Self::UtcTimeSynchronization(t) => t.len(),
+            Self::WhoHas(w) => w.len(),
+            Self::WhoIs(w) => w.len(),
+            _ => 0,
+        }
+    }
+}
+
+/// Service request of a Confirmed-Request PDU (21), decoded according to its service choice
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfirmedService {
+    AcknowledgeAlarm(AcknowledgeAlarm),                     // = 0;
+    ConfirmedCovNotification(CovNotification),              // = 1;
+    ConfirmedEventNotification(EventNotification),          // = 2;
+    GetAlarmSummary(GetAlarmSummary),                       // = 3;
+    SubscribeCov(SubscribeCov),                             // = 5;
+    AtomicReadFile(AtomicReadFile),                         // = 6;
+    AtomicWriteFile(AtomicWriteFile),                       // = 7;
+    CreateObject(CreateObject),                             // = 10;
+    DeleteObject(DeleteObject),                             // = 11;
+    ReadProperty(ReadProperty),                             // = 12;
+    ReadPropertyMultiple(ReadPropertyMultiple),             // = 14;
+    WriteProperty(WriteProperty),                           // = 15;
+    WritePropertyMultiple(WritePropertyMultiple),           // = 16;
+    DeviceCommunicationControl(DeviceCommunicationControl), // = 17;
+    ReinitializeDevice(ReinitializeDevice),                 // = 20;
+    ReadRange(ReadRange),                                   // = 26;
+    GetEventInformation(GetEventInformation),               // = 29;
+}
+
+impl ConfirmedService {
+    /// Decode the request parameters in `reader` of the service with `service_choice`
+    pub fn decode_service<T: crate::io::Read + Sized>(
+        service_choice: u8,
+        reader: &mut T,
+    ) -> crate::Result<Self> {
+        match service_choice {
+            AcknowledgeAlarm::SERVICE_CHOICE => {
+                Ok(Self::AcknowledgeAlarm(AcknowledgeAlarm::decode(reader)?))
+            }
+            CovNotification::CONFIRMED_SERVICE_CHOICE => Ok(Self::ConfirmedCovNotification(
+                CovNotification::decode(reader)?,
+            )),
+            EventNotification::CONFIRMED_SERVICE_CHOICE => Ok(Self::ConfirmedEventNotification(
+                EventNotification::decode(reader)?,
+            )),
+            GetAlarmSummary::SERVICE_CHOICE => Ok(Self::GetAlarmSummary(GetAlarmSummary)),
+            SubscribeCov::SERVICE_CHOICE => Ok(Self::SubscribeCov(SubscribeCov::decode(reader)?)),
+            AtomicReadFile::SERVICE_CHOICE => {
+                Ok(Self::AtomicReadFile(AtomicReadFile::decode(reader)?))
+            }
+            AtomicWriteFile::SERVICE_CHOICE => {
+                Ok(Self::AtomicWriteFile(AtomicWriteFile::decode(reader)?))
+            }
+            CreateObject::SERVICE_CHOICE => Ok(Self::CreateObject(CreateObject::decode(reader)?)),
+            DeleteObject::SERVICE_CHOICE => Ok(Self::DeleteObject(DeleteObject::decode(reader)?)),
+            ReadProperty::SERVICE_CHOICE => Ok(Self::ReadProperty(ReadProperty::decode(reader)?)),
+            ReadPropertyMultiple::SERVICE_CHOICE => Ok(Self::ReadPropertyMultiple(
+                ReadPropertyMultiple::decode(reader)?,
+            )),
+            WriteProperty::SERVICE_CHOICE => {
+                Ok(Self::WriteProperty(WriteProperty::decode(reader)?))
+            }
+            WritePropertyMultiple::SERVICE_CHOICE => Ok(Self::WritePropertyMultiple(
+                WritePropertyMultiple::decode(reader)?,
+            )),
+            DeviceCommunicationControl::SERVICE_CHOICE => Ok(Self::DeviceCommunicationControl(
+                DeviceCommunicationControl::decode(reader)?,
+            )),
+            ReinitializeDevice::SERVICE_CHOICE => Ok(Self::ReinitializeDevice(
+                ReinitializeDevice::decode(reader)?,
+            )),
+            ReadRange::SERVICE_CHOICE => Ok(Self::ReadRange(ReadRange::decode(reader)?)),
+            GetEventInformation::SERVICE_CHOICE => Ok(Self::GetEventInformation(
+                GetEventInformation::decode(reader)?,
+            )),
+            t => Err(Error::UnknownService(t)),
+        }
+    }
+}
+
+impl Decode for ConfirmedService {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let service_choice = reader.read_u8()?;
+        Self::decode_service(service_choice, reader)
+    }
+}
+
+/// ACK of a ComplexACK PDU (21), decoded according to its service choice
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComplexAck {
+    GetAlarmSummary(GetAlarmSummaryAck),           // = 3;
+    AtomicReadFile(AtomicReadFileAck),             // = 6;
+    AtomicWriteFile(AtomicWriteFileAck),           // = 7;
+    CreateObject(CreateObjectAck),                 // = 10;
+    ReadProperty(ReadPropertyAck),                 // = 12;
+    ReadPropertyMultiple(ReadPropertyMultipleAck), // = 14;
+    ReadRange(ReadRangeAck),                       // = 26;
+    GetEventInformation(GetEventInformationAck),   // = 29;
+}
+
+impl ComplexAck {
+    /// Decode the ACK parameters in `reader` of the service with `service_choice`
+    pub fn decode_service<T: crate::io::Read + Sized>(
+        service_choice: u8,
+        reader: &mut T,
+    ) -> crate::Result<Self> {
+        match service_choice {
+            GetAlarmSummary::SERVICE_CHOICE => {
+                Ok(Self::GetAlarmSummary(GetAlarmSummaryAck::decode(reader)?))
+            }
+            AtomicReadFile::SERVICE_CHOICE => {
+                Ok(Self::AtomicReadFile(AtomicReadFileAck::decode(reader)?))
+            }
+            AtomicWriteFile::SERVICE_CHOICE => {
+                Ok(Self::AtomicWriteFile(AtomicWriteFileAck::decode(reader)?))
+            }
+            CreateObject::SERVICE_CHOICE => {
+                Ok(Self::CreateObject(CreateObjectAck::decode(reader)?))
+            }
+            ReadProperty::SERVICE_CHOICE => {
+                Ok(Self::ReadProperty(ReadPropertyAck::decode(reader)?))
+            }
+            ReadPropertyMultiple::SERVICE_CHOICE => Ok(Self::ReadPropertyMultiple(
+                ReadPropertyMultipleAck::decode(reader)?,
+            )),
+            ReadRange::SERVICE_CHOICE => Ok(Self::ReadRange(ReadRangeAck::decode(reader)?)),
+            GetEventInformation::SERVICE_CHOICE => Ok(Self::GetEventInformation(
+                GetEventInformationAck::decode(reader)?,
+            )),
+            t => Err(Error::UnknownService(t)),
+        }
+    }
+}
+
+impl Decode for ComplexAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let service_choice = reader.read_u8()?;
+        Self::decode_service(service_choice, reader)
+    }
+}
+
+impl TryFrom<&APDU> for ComplexAck {
+    type Error = Error;
+
+    fn try_from(apdu: &APDU) -> crate::Result<Self> {
+        if apdu.pdu_type() != Some(BACnetPDU::ComplexACK) {
+            return Err(Error::InvalidValue(format!(
+                "Not a ComplexACK: {:?}",
+                apdu.pdu_type()
+            )));
+        }
+        if apdu.flags & SEGMENTED_MESSAGE != 0 {
+            return Err(Error::Unsupported(
+                "Segmented ComplexACK must be reassembled first".into(),
+            ));
+        }
+        Self::decode_service(apdu.service_choice, &mut apdu.user_data())
+    }
+}
+
+/// Name of a BACnetConfirmedServiceChoice (21), None for unknown choices
+pub fn confirmed_service_name(service_choice: u8) -> Option<&'static str> {
+    Some(match service_choice {
+        0 => "AcknowledgeAlarm",
+        1 => "ConfirmedCOVNotification",
+        2 => "ConfirmedEventNotification",
+        3 => "GetAlarmSummary",
+        4 => "GetEnrollmentSummary",
+        5 => "SubscribeCOV",
+        6 => "AtomicReadFile",
+        7 => "AtomicWriteFile",
+        8 => "AddListElement",
+        9 => "RemoveListElement",
+        10 => "CreateObject",
+        11 => "DeleteObject",
+        12 => "ReadProperty",
+        14 => "ReadPropertyMultiple",
+        15 => "WriteProperty",
+        16 => "WritePropertyMultiple",
+        17 => "DeviceCommunicationControl",
+        18 => "ConfirmedPrivateTransfer",
+        19 => "ConfirmedTextMessage",
+        20 => "ReinitializeDevice",
+        21 => "VT-Open",
+        22 => "VT-Close",
+        23 => "VT-Data",
+        26 => "ReadRange",
+        27 => "LifeSafetyOperation",
+        28 => "SubscribeCOVProperty",
+        29 => "GetEventInformation",
+        30 => "SubscribeCOVPropertyMultiple",
+        31 => "ConfirmedCOVNotificationMultiple",
+        32 => "ConfirmedAuditNotification",
+        33 => "AuditLogQuery",
+        _ => return None,
+    })
+}
+
+/// Name of a BACnetUnconfirmedServiceChoice (21), None for unknown choices
+pub fn unconfirmed_service_name(service_choice: u8) -> Option<&'static str> {
+    Some(match service_choice {
+        0 => "I-Am",
+        1 => "I-Have",
+        2 => "UnconfirmedCOVNotification",
+        3 => "UnconfirmedEventNotification",
+        4 => "UnconfirmedPrivateTransfer",
+        5 => "UnconfirmedTextMessage",
+        6 => "TimeSynchronization",
+        7 => "Who-Has",
+        8 => "Who-Is",
+        9 => "UTCTimeSynchronization",
+        10 => "WriteGroup",
+        11 => "UnconfirmedCOVNotificationMultiple",
+        12 => "UnconfirmedAuditNotification",
+        13 => "Who-Am-I",
+        14 => "You-Are",
+        _ => return None,
+    })
+}
+
+/// BACnetSegmentation (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Segmentation {
+    Both,     // = 0;
+    Transmit, // = 1;
+    Receive,  // = 2;
+    None,     // = 3;
+}
+
+impl TryFrom<u32> for Segmentation {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Both),
+            1 => Ok(Self::Transmit),
+            2 => Ok(Self::Receive),
+            3 => Ok(Self::None),
+            v => Err(Error::InvalidValue(format!(
+                "Segmentation not supported: {}",
+                v
+            ))),
         }
     }
 }
 
+impl From<Segmentation> for u32 {
+    fn from(value: Segmentation) -> Self {
+        match value {
+            Segmentation::Both => 0,
+            Segmentation::Transmit => 1,
+            Segmentation::Receive => 2,
+            Segmentation::None => 3,
+        }
+    }
+}
+
+/// I-Am-Request (16.10.3)
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct IAm {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IAm {
+    pub device_identifier: ObjectIdentifier,
+    pub max_apdu_length_accepted: u32,
+    pub segmentation_supported: Segmentation,
+    pub vendor_id: u16,
+}
+
+impl IAm {
+    /// Unconfirmed service choice (21)
+    pub const SERVICE_CHOICE: u8 = 0;
+
+    pub fn new(
+        device_identifier: ObjectIdentifier,
+        max_apdu_length_accepted: u32,
+        segmentation_supported: Segmentation,
+        vendor_id: u16,
+    ) -> Self {
+        Self {
+            device_identifier,
+            max_apdu_length_accepted,
+            segmentation_supported,
+            vendor_id,
+        }
+    }
+
+    fn values(&self) -> [ApplicationValue; 4] {
+        [
+            ApplicationValue::ObjectId(self.device_identifier),
+            ApplicationValue::Unsigned(self.max_apdu_length_accepted),
+            ApplicationValue::Enumerated(self.segmentation_supported.into()),
+            ApplicationValue::Unsigned(self.vendor_id as u32),
+        ]
+    }
+}
 
 impl Decode for IAm {
-    fn decode<T: std::io::Read + Sized>(_reader: &mut T) -> std::io::Result<Self> {
-        Ok(Self {})
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let (device_identifier, rest) = decode_application_value(&data)?;
+        let (max_apdu_length_accepted, rest) = decode_application_value(rest)?;
+        let (segmentation_supported, rest) = decode_application_value(rest)?;
+        let (vendor_id, _) = decode_application_value(rest)?;
+        match (
+            device_identifier,
+            max_apdu_length_accepted,
+            segmentation_supported,
+            vendor_id,
+        ) {
+            (
+                ApplicationValue::ObjectId(device_identifier),
+                ApplicationValue::Unsigned(max_apdu_length_accepted),
+                ApplicationValue::Enumerated(segmentation_supported),
+                ApplicationValue::Unsigned(vendor_id),
+            ) => Ok(Self {
+                device_identifier,
+                max_apdu_length_accepted,
+                segmentation_supported: Segmentation::try_from(segmentation_supported)?,
+                vendor_id: u16::try_from(vendor_id).map_err(|_| {
+                    Error::InvalidValue(format!("Vendor ID out of range: {}", vendor_id))
+                })?,
+            }),
+            v => Err(Error::InvalidValue(format!(
+                "Invalid I-Am parameters: {:?}",
+                v
+            ))),
+        }
     }
 }
 
 impl Encode for IAm {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
-        let data = vec![196, 2, 0, 2, 87, 34, 4, 0, 145, 0, 33, 15];
-        writer.write_all(&data)?;
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.values().iter().try_for_each(|v| v.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.values().iter().map(|v| v.len()).sum()
+    }
+}
+
+/// Who-Is-Request (16.10.1)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WhoIs {
+    /// Low and high limit of the device instances that should respond
+    pub range: Option<(u32, u32)>,
+}
+
+impl WhoIs {
+    /// Unconfirmed service choice (21)
+    pub const SERVICE_CHOICE: u8 = 8;
+
+    /// Who-Is addressed to every device
+    pub fn new() -> Self {
+        Self { range: None }
+    }
+
+    /// Who-Is addressed to the devices with an instance within `low..=high`
+    pub fn range(low: u32, high: u32) -> Self {
+        Self {
+            range: Some((low, high)),
+        }
+    }
+
+    /// Who-Is addressed to the single device with `instance`
+    pub fn for_instance(instance: u32) -> Self {
+        Self::range(instance, instance)
+    }
+
+    /// Whether a device with `instance` should respond
+    pub fn matches(&self, instance: u32) -> bool {
+        self.range
+            .is_none_or(|(low, high)| (low..=high).contains(&instance))
+    }
+}
+
+impl Encode for WhoIs {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        if let Some((low, high)) = self.range {
+            Tag::context(0, &encode_unsigned(low)).encode(writer)?;
+            Tag::context(1, &encode_unsigned(high)).encode(writer)?;
+        }
         Ok(())
     }
 
     fn len(&self) -> usize {
-        12
+        match self.range {
+            Some((low, high)) => 2 + encode_unsigned(low).len() + encode_unsigned(high).len(),
+            None => 0,
+        }
+    }
+}
+
+impl Decode for WhoIs {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if data.is_empty() {
+            return Ok(Self::new());
+        }
+
+        // Both limits are present or both are absent
+        let (low, rest) = decode_context(&data, 0)?;
+        let (high, _) = decode_context(rest, 1)?;
+        Ok(Self::range(decode_unsigned(low)?, decode_unsigned(high)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_i_am_round_trip() {
+        let i_am = IAm::new(
+            ObjectIdentifier::new(ObjectType::Device, 260001).unwrap(),
+            1476,
+            Segmentation::None,
+            999,
+        );
+
+        let data = i_am.encode_vec().expect("Write I-Am to buffer");
+        assert_eq!(data, hex::decode("c40203f7a12205c491032203e7").unwrap());
+        assert_eq!(data.len(), i_am.len());
+        assert_eq!(IAm::decode_slice(&data).unwrap(), i_am);
+    }
+
+    #[test]
+    fn test_decode_i_am_service() {
+        let data = hex::decode("00c4020002572204009100210f").unwrap();
+        let service = UnconfirmedService::decode_slice(&data).expect("Decode I-Am");
+
+        assert_eq!(
+            service,
+            UnconfirmedService::IAm(IAm::new(
+                ObjectIdentifier::new(ObjectType::Device, 599).unwrap(),
+                1024,
+                Segmentation::Both,
+                15
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_i_am_invalid() {
+        // Vendor ID as Enumerated instead of Unsigned
+        let data = hex::decode("c4020002572204009100910f").unwrap();
+        let err = IAm::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = IAm::decode_slice(&data[..8]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_i_am_json_round_trip() {
+        let data = hex::decode("c4020002572204009100210f").unwrap();
+        let i_am = IAm::decode_slice(&data).unwrap();
+
+        let json = serde_json::to_string(&i_am).unwrap();
+        assert_eq!(serde_json::from_str::<IAm>(&json).unwrap(), i_am);
+    }
+
+    #[test]
+    fn test_who_is_unbounded() {
+        let who_is = WhoIs::new();
+        assert!(who_is.encode_vec().unwrap().is_empty());
+        assert_eq!(who_is.len(), 0);
+        assert_eq!(WhoIs::decode_slice(&[]).unwrap(), who_is);
+        assert!(who_is.matches(4194302));
+
+        let service = UnconfirmedService::decode_slice(&[0x08]).unwrap();
+        assert_eq!(service, UnconfirmedService::WhoIs(who_is));
+    }
+
+    #[test]
+    fn test_who_is_range() {
+        let who_is = WhoIs::range(1000, 1999);
+
+        let data = who_is.encode_vec().expect("Write Who-Is to buffer");
+        assert_eq!(data, vec![0x0a, 0x03, 0xe8, 0x1a, 0x07, 0xcf]);
+        assert_eq!(data.len(), who_is.len());
+        assert_eq!(WhoIs::decode_slice(&data).unwrap(), who_is);
+        assert!(who_is.matches(1000));
+        assert!(who_is.matches(1999));
+        assert!(!who_is.matches(2000));
+    }
+
+    #[test]
+    fn test_who_is_for_instance() {
+        let who_is = WhoIs::for_instance(260001);
+
+        let data = who_is.encode_vec().expect("Write Who-Is to buffer");
+        assert_eq!(data, hex::decode("0b03f7a11b03f7a1").unwrap());
+        assert_eq!(data.len(), who_is.len());
+        assert_eq!(WhoIs::decode_slice(&data).unwrap(), who_is);
+        assert!(who_is.matches(260001));
+        assert!(!who_is.matches(260002));
+    }
+
+    #[test]
+    fn test_decode_who_is_single_limit() {
+        assert!(WhoIs::decode_slice(&[0x0a, 0x03, 0xe8]).is_err());
+        assert!(WhoIs::decode_slice(&[0x1a, 0x07, 0xcf]).is_err());
+    }
+
+    #[test]
+    fn test_complex_ack_read_property() {
+        // ReadProperty-ACK analog-input,1 present-value 72.5
+        let data = hex::decode("30010c0c0000000119553e44429100003f").unwrap();
+        let apdu = APDU::decode_slice(&data).unwrap();
+
+        let ComplexAck::ReadProperty(ack) = ComplexAck::try_from(&apdu).unwrap() else {
+            panic!("Not a ReadProperty-ACK");
+        };
+        assert_eq!(
+            ack.object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap()
+        );
+        assert_eq!(
+            ack.property_value.single(),
+            Some(&ApplicationValue::Real(72.5))
+        );
+    }
+
+    #[test]
+    fn test_complex_ack_read_property_multiple() {
+        // ReadPropertyMultiple-ACK analog-input,1 present-value 72.5
+        let data = hex::decode("30020e0c000000011e29554e44429100004f1f").unwrap();
+        let apdu = APDU::decode_slice(&data).unwrap();
+
+        let ComplexAck::ReadPropertyMultiple(ack) = ComplexAck::try_from(&apdu).unwrap() else {
+            panic!("Not a ReadPropertyMultiple-ACK");
+        };
+        assert_eq!(ack.read_access_results.len(), 1);
+        assert_eq!(
+            ack.read_access_results[0].results[0].result,
+            Ok(ApplicationValue::Real(72.5).into())
+        );
+        assert_eq!(
+            ComplexAck::decode_slice(&data[2..]).unwrap(),
+            ComplexAck::ReadPropertyMultiple(ack)
+        );
+    }
+
+    #[test]
+    fn test_complex_ack_invalid() {
+        // SimpleACK
+        let apdu = APDU::decode_slice(&[0x20, 0x01, 0x0f]).unwrap();
+        let err = ComplexAck::try_from(&apdu).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        // ComplexACK of ConfirmedPrivateTransfer
+        let apdu = APDU::decode_slice(&[0x30, 0x01, 0x12]).unwrap();
+        let err = ComplexAck::try_from(&apdu).unwrap_err();
+        assert!(matches!(err, Error::UnknownService(0x12)), "{:?}", err);
+        // First segment of a ReadProperty-ACK
+        let apdu = APDU::decode_slice(&hex::decode("3c0100040c0c00000001").unwrap()).unwrap();
+        let err = ComplexAck::try_from(&apdu).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_empty_unconfirmed_service() {
+        let err = UnconfirmedService::decode_slice(&[]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_encode_unsupported_unconfirmed_service() {
+        let err = UnconfirmedService::WriteGroup.encode_vec().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_unsupported_unconfirmed_service() {
+        let err = UnconfirmedService::decode_slice(&[0x05]).unwrap_err();
+        assert!(matches!(err, Error::UnknownService(0x05)), "{:?}", err);
     }
 }
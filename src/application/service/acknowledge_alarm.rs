@@ -0,0 +1,167 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    EventState, ObjectIdentifier, Tag, TimeStamp, decode_character_string, decode_context,
+    decode_enclosed, decode_enumerated, decode_unsigned, encode_character_string,
+    encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// AcknowledgeAlarm-Request (13.5.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcknowledgeAlarm {
+    pub acknowledging_process_identifier: u32,
+    pub event_object_identifier: ObjectIdentifier,
+    pub event_state_acknowledged: EventState,
+    /// Time stamp of the transition being acknowledged
+    pub time_stamp: TimeStamp,
+    /// Operator or process acknowledging the alarm
+    pub acknowledgment_source: String,
+    pub time_of_acknowledgment: TimeStamp,
+}
+
+impl AcknowledgeAlarm {
+    pub fn new(
+        acknowledging_process_identifier: u32,
+        event_object_identifier: ObjectIdentifier,
+        event_state_acknowledged: EventState,
+        time_stamp: TimeStamp,
+        acknowledgment_source: &str,
+        time_of_acknowledgment: TimeStamp,
+    ) -> Self {
+        Self {
+            acknowledging_process_identifier,
+            event_object_identifier,
+            event_state_acknowledged,
+            time_stamp,
+            acknowledgment_source: acknowledgment_source.to_string(),
+            time_of_acknowledgment,
+        }
+    }
+}
+
+impl ConfirmedRequestService for AcknowledgeAlarm {
+    const SERVICE_CHOICE: u8 = 0;
+}
+
+impl Encode for AcknowledgeAlarm {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_unsigned(self.acknowledging_process_identifier)).encode(writer)?;
+        Tag::context(1, &self.event_object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(2, &encode_enumerated(self.event_state_acknowledged.into())).encode(writer)?;
+        Tag::opening(3).encode(writer)?;
+        self.time_stamp.encode(writer)?;
+        Tag::closing(3).encode(writer)?;
+        Tag::context(4, &encode_character_string(&self.acknowledgment_source)).encode(writer)?;
+        Tag::opening(5).encode(writer)?;
+        self.time_of_acknowledgment.encode(writer)?;
+        Tag::closing(5).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + encode_unsigned(self.acknowledging_process_identifier).len();
+        l += 1 + 4; // Event Object Identifier
+        l += 1 + encode_enumerated(self.event_state_acknowledged.into()).len();
+        l += 1 + self.time_stamp.len() + 1;
+        l += Tag::context(4, &encode_character_string(&self.acknowledgment_source)).len();
+        l += 1 + self.time_of_acknowledgment.len() + 1;
+        l
+    }
+}
+
+impl Decode for AcknowledgeAlarm {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (acknowledging_process_identifier, rest) = decode_context(&data, 0)?;
+        let (event_object_identifier, rest) = decode_context(rest, 1)?;
+        let (event_state_acknowledged, rest) = decode_context(rest, 2)?;
+        let (time_stamp, rest) = decode_enclosed(rest, 3)?;
+        let (acknowledgment_source, rest) = decode_context(rest, 4)?;
+        let (time_of_acknowledgment, _) = decode_enclosed(rest, 5)?;
+        Ok(Self {
+            acknowledging_process_identifier: decode_unsigned(acknowledging_process_identifier)?,
+            event_object_identifier: ObjectIdentifier::decode_slice(event_object_identifier)?,
+            event_state_acknowledged: EventState::try_from(decode_enumerated(
+                event_state_acknowledged,
+            )?)?,
+            time_stamp: TimeStamp::decode_slice(time_stamp)?,
+            acknowledgment_source: decode_character_string(acknowledgment_source)?,
+            time_of_acknowledgment: TimeStamp::decode_slice(time_of_acknowledgment)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Date, ObjectType, Time};
+
+    #[test]
+    fn test_acknowledge_to_offnormal_transition() {
+        // Acknowledge the high-limit alarm of analog-input,2 raised at 15:35:00.20
+        let request = AcknowledgeAlarm::new(
+            1,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 2).unwrap(),
+            EventState::HighLimit,
+            TimeStamp::Time(Time::new(15, 35, 0, 20)),
+            "MDL",
+            TimeStamp::DateTime(Date::new(2024, 3, 15, 5).unwrap(), Time::new(15, 45, 0, 0)),
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(
+            data,
+            hex::decode(concat!(
+                "0901",
+                "1c00000002",
+                "2903",
+                "3e0c0f2300143f",
+                "4c004d444c",
+                "5e2ea47c030f05b40f2d00002f5f"
+            ))
+            .unwrap()
+        );
+        assert_eq!(data.len(), request.len());
+        assert_eq!(AcknowledgeAlarm::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(7).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x07, 0x00]);
+    }
+
+    #[test]
+    fn test_acknowledge_by_sequence_number() {
+        let request = AcknowledgeAlarm::new(
+            1,
+            ObjectIdentifier::new(ObjectType::BinaryInput, 5).unwrap(),
+            EventState::Offnormal,
+            TimeStamp::SequenceNumber(42),
+            "operator",
+            TimeStamp::SequenceNumber(43),
+        );
+
+        let data = request.encode_vec().unwrap();
+        assert_eq!(data.len(), request.len());
+        assert_eq!(AcknowledgeAlarm::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_decode_acknowledge_alarm_invalid() {
+        // Time of acknowledgment missing
+        let data = hex::decode("09011c0000000229033e0c0f2300143f4c004d444c").unwrap();
+        let err = AcknowledgeAlarm::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+        // Event state 9
+        let data = hex::decode("09011c0000000229093e0c0f2300143f4c004d444c5e19015f").unwrap();
+        let err = AcknowledgeAlarm::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ApplicationTag, ApplicationValue, ObjectIdentifier, Tag, decode_application_value,
+    decode_enclosed,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// Access method of an AtomicReadFile request (15.1.1.1.2)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FileAccess {
+    /// Read `requested_octet_count` octets starting at octet `file_start_position`
+    Stream {
+        file_start_position: i32,
+        requested_octet_count: u32,
+    },
+    /// Read `requested_record_count` records starting at record `file_start_record`
+    Record {
+        file_start_record: i32,
+        requested_record_count: u32,
+    },
+}
+
+/// File content of an AtomicReadFile-ACK or an AtomicWriteFile request, either a run of
+/// octets or a list of records (15.1.2.1.2, 15.2.1.1.2)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FileData {
+    Stream {
+        file_start_position: i32,
+        file_data: Vec<u8>,
+    },
+    Record {
+        file_start_record: i32,
+        file_record_data: Vec<Vec<u8>>,
+    },
+}
+
+impl Encode for FileData {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        match self {
+            Self::Stream {
+                file_start_position,
+                file_data,
+            } => {
+                Tag::opening(0).encode(writer)?;
+                ApplicationValue::Signed(*file_start_position).encode(writer)?;
+                Tag::application(ApplicationTag::OctetString, file_data).encode(writer)?;
+                Tag::closing(0).encode(writer)
+            }
+            Self::Record {
+                file_start_record,
+                file_record_data,
+            } => {
+                Tag::opening(1).encode(writer)?;
+                ApplicationValue::Signed(*file_start_record).encode(writer)?;
+                ApplicationValue::Unsigned(file_record_data.len() as u32).encode(writer)?;
+                for record in file_record_data {
+                    Tag::application(ApplicationTag::OctetString, record).encode(writer)?;
+                }
+                Tag::closing(1).encode(writer)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 2; // Opening and closing tags
+        match self {
+            Self::Stream {
+                file_start_position,
+                file_data,
+            } => {
+                l += ApplicationValue::Signed(*file_start_position).len();
+                l += Tag::application(ApplicationTag::OctetString, file_data).len();
+            }
+            Self::Record {
+                file_start_record,
+                file_record_data,
+            } => {
+                l += ApplicationValue::Signed(*file_start_record).len();
+                l += ApplicationValue::Unsigned(file_record_data.len() as u32).len();
+                for record in file_record_data {
+                    l += Tag::application(ApplicationTag::OctetString, record).len();
+                }
+            }
+        }
+        l
+    }
+}
+
+impl Decode for FileData {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (tag, _) = Tag::decode(&data)?;
+        if tag.is_opening(0) {
+            let (stream, _) = decode_enclosed(&data, 0)?;
+            let (file_start_position, rest) = decode_application_value(stream)?;
+            let (file_data, _) = decode_application_value(rest)?;
+            return match (file_start_position, file_data) {
+                (
+                    ApplicationValue::Signed(file_start_position),
+                    ApplicationValue::OctetString(file_data),
+                ) => Ok(Self::Stream {
+                    file_start_position,
+                    file_data,
+                }),
+                v => Err(Error::InvalidValue(format!(
+                    "Invalid stream access file data: {:?}",
+                    v
+                ))),
+            };
+        }
+
+        let (record, _) = decode_enclosed(&data, 1)?;
+        let (file_start_record, rest) = decode_application_value(record)?;
+        let (record_count, mut rest) = decode_application_value(rest)?;
+        let (file_start_record, record_count) = match (file_start_record, record_count) {
+            (ApplicationValue::Signed(start), ApplicationValue::Unsigned(count)) => (start, count),
+            v => {
+                return Err(Error::InvalidValue(format!(
+                    "Invalid record access file data: {:?}",
+                    v
+                )));
+            }
+        };
+        let mut file_record_data = Vec::new();
+        while !rest.is_empty() {
+            let (value, next) = decode_application_value(rest)?;
+            match value {
+                ApplicationValue::OctetString(record) => file_record_data.push(record),
+                v => {
+                    return Err(Error::InvalidValue(format!("Invalid file record: {:?}", v)));
+                }
+            }
+            rest = next;
+        }
+        if file_record_data.len() != record_count as usize {
+            return Err(Error::InvalidValue(format!(
+                "Record count {} does not match {} records",
+                record_count,
+                file_record_data.len()
+            )));
+        }
+        Ok(Self::Record {
+            file_start_record,
+            file_record_data,
+        })
+    }
+}
+
+/// AtomicReadFile-Request (15.1.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AtomicReadFile {
+    pub file_identifier: ObjectIdentifier,
+    pub access: FileAccess,
+}
+
+impl AtomicReadFile {
+    pub fn new(file_identifier: ObjectIdentifier, access: FileAccess) -> Self {
+        Self {
+            file_identifier,
+            access,
+        }
+    }
+
+    /// Read `requested_octet_count` octets of the file starting at `file_start_position`
+    pub fn stream(
+        file_identifier: ObjectIdentifier,
+        file_start_position: i32,
+        requested_octet_count: u32,
+    ) -> Self {
+        Self::new(
+            file_identifier,
+            FileAccess::Stream {
+                file_start_position,
+                requested_octet_count,
+            },
+        )
+    }
+
+    /// Read `requested_record_count` records of the file starting at `file_start_record`
+    pub fn record(
+        file_identifier: ObjectIdentifier,
+        file_start_record: i32,
+        requested_record_count: u32,
+    ) -> Self {
+        Self::new(
+            file_identifier,
+            FileAccess::Record {
+                file_start_record,
+                requested_record_count,
+            },
+        )
+    }
+
+    fn access_values(&self) -> (u8, ApplicationValue, ApplicationValue) {
+        match self.access {
+            FileAccess::Stream {
+                file_start_position,
+                requested_octet_count,
+            } => (
+                0,
+                ApplicationValue::Signed(file_start_position),
+                ApplicationValue::Unsigned(requested_octet_count),
+            ),
+            FileAccess::Record {
+                file_start_record,
+                requested_record_count,
+            } => (
+                1,
+                ApplicationValue::Signed(file_start_record),
+                ApplicationValue::Unsigned(requested_record_count),
+            ),
+        }
+    }
+}
+
+impl ConfirmedRequestService for AtomicReadFile {
+    const SERVICE_CHOICE: u8 = 6;
+}
+
+impl Encode for AtomicReadFile {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        ApplicationValue::ObjectId(self.file_identifier).encode(writer)?;
+        let (tag_number, start, count) = self.access_values();
+        Tag::opening(tag_number).encode(writer)?;
+        start.encode(writer)?;
+        count.encode(writer)?;
+        Tag::closing(tag_number).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        let (_, start, count) = self.access_values();
+        1 + 4 + 1 + start.len() + count.len() + 1
+    }
+}
+
+impl Decode for AtomicReadFile {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (file_identifier, rest) = decode_application_value(&data)?;
+        let ApplicationValue::ObjectId(file_identifier) = file_identifier else {
+            return Err(Error::InvalidValue(format!(
+                "Invalid file identifier: {:?}",
+                file_identifier
+            )));
+        };
+        let (tag, _) = Tag::decode(rest)?;
+        let tag_number = if tag.is_opening(0) { 0 } else { 1 };
+        let (access, _) = decode_enclosed(rest, tag_number)?;
+        let (start, rest) = decode_application_value(access)?;
+        let (count, _) = decode_application_value(rest)?;
+        let access = match (tag_number, start, count) {
+            (0, ApplicationValue::Signed(start), ApplicationValue::Unsigned(count)) => {
+                FileAccess::Stream {
+                    file_start_position: start,
+                    requested_octet_count: count,
+                }
+            }
+            (1, ApplicationValue::Signed(start), ApplicationValue::Unsigned(count)) => {
+                FileAccess::Record {
+                    file_start_record: start,
+                    requested_record_count: count,
+                }
+            }
+            v => {
+                return Err(Error::InvalidValue(format!(
+                    "Invalid file access method: {:?}",
+                    v
+                )));
+            }
+        };
+        Ok(Self::new(file_identifier, access))
+    }
+}
+
+/// AtomicReadFile-ACK (15.1.2)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AtomicReadFileAck {
+    /// Whether the returned data reaches the end of the file
+    pub end_of_file: bool,
+    pub data: FileData,
+}
+
+impl Encode for AtomicReadFileAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::boolean(self.end_of_file).encode(writer)?;
+        self.data.encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        1 + self.data.len()
+    }
+}
+
+impl Decode for AtomicReadFileAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (end_of_file, rest) = decode_application_value(&data)?;
+        let ApplicationValue::Boolean(end_of_file) = end_of_file else {
+            return Err(Error::InvalidValue(format!(
+                "Invalid end-of-file flag: {:?}",
+                end_of_file
+            )));
+        };
+        Ok(Self {
+            end_of_file,
+            data: FileData::decode_slice(rest)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_read_1024_octets_at_start() {
+        let request =
+            AtomicReadFile::stream(ObjectIdentifier::new(ObjectType::File, 1).unwrap(), 0, 1024);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("c4028000010e31002204000f").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(AtomicReadFile::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(0).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x00, 0x06]);
+    }
+
+    #[test]
+    fn test_decode_stream_ack() {
+        // AtomicReadFile-ACK end-of-file, 27 octets at position 0
+        let data =
+            hex::decode("110e3100651b4368696c6c65723031204f6e2d54696d653d342e3320486f7572730f")
+                .unwrap();
+        let ack = AtomicReadFileAck::decode_slice(&data).expect("Decode ACK");
+
+        assert!(ack.end_of_file);
+        assert_eq!(
+            ack.data,
+            FileData::Stream {
+                file_start_position: 0,
+                file_data: b"Chiller01 On-Time=4.3 Hours".to_vec(),
+            }
+        );
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+    }
+
+    #[test]
+    fn test_record_access_round_trip() {
+        let request =
+            AtomicReadFile::record(ObjectIdentifier::new(ObjectType::File, 2).unwrap(), 14, 3);
+        let data = request.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("c4028000021e310e21031f").unwrap());
+        assert_eq!(AtomicReadFile::decode_slice(&data).unwrap(), request);
+
+        let ack = AtomicReadFileAck {
+            end_of_file: false,
+            data: FileData::Record {
+                file_start_record: 14,
+                file_record_data: vec![b"12:00,45.6".to_vec(), b"12:15,44.8".to_vec()],
+            },
+        };
+        let data = ack.encode_vec().unwrap();
+        assert_eq!(data.len(), ack.len());
+        assert_eq!(AtomicReadFileAck::decode_slice(&data).unwrap(), ack);
+    }
+
+    #[test]
+    fn test_decode_atomic_read_file_invalid() {
+        // Requested octet count missing
+        let err =
+            AtomicReadFile::decode_slice(&hex::decode("c4028000010e31000f").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+        // Record count 2 with a single record
+        let err = AtomicReadFileAck::decode_slice(&hex::decode("101e3100210261001f").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
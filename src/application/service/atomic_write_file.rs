@@ -0,0 +1,187 @@
+use crate::application::{ConfirmedRequestService, FileData};
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, Tag, decode_application_value, decode_signed, encode_signed,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// AtomicWriteFile-Request (15.2.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AtomicWriteFile {
+    pub file_identifier: ObjectIdentifier,
+    pub data: FileData,
+}
+
+impl AtomicWriteFile {
+    pub fn new(file_identifier: ObjectIdentifier, data: FileData) -> Self {
+        Self {
+            file_identifier,
+            data,
+        }
+    }
+
+    /// Write `file_data` to the file starting at `file_start_position`, -1 appends to the file
+    pub fn stream(
+        file_identifier: ObjectIdentifier,
+        file_start_position: i32,
+        file_data: Vec<u8>,
+    ) -> Self {
+        Self::new(
+            file_identifier,
+            FileData::Stream {
+                file_start_position,
+                file_data,
+            },
+        )
+    }
+
+    /// Write `file_record_data` to the file starting at `file_start_record`, -1 appends to the
+    /// file
+    pub fn record(
+        file_identifier: ObjectIdentifier,
+        file_start_record: i32,
+        file_record_data: Vec<Vec<u8>>,
+    ) -> Self {
+        Self::new(
+            file_identifier,
+            FileData::Record {
+                file_start_record,
+                file_record_data,
+            },
+        )
+    }
+}
+
+impl ConfirmedRequestService for AtomicWriteFile {
+    const SERVICE_CHOICE: u8 = 7;
+}
+
+impl Encode for AtomicWriteFile {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        ApplicationValue::ObjectId(self.file_identifier).encode(writer)?;
+        self.data.encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        1 + 4 + self.data.len()
+    }
+}
+
+impl Decode for AtomicWriteFile {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (file_identifier, rest) = decode_application_value(&data)?;
+        let ApplicationValue::ObjectId(file_identifier) = file_identifier else {
+            return Err(Error::InvalidValue(format!(
+                "Invalid file identifier: {:?}",
+                file_identifier
+            )));
+        };
+        Ok(Self::new(file_identifier, FileData::decode_slice(rest)?))
+    }
+}
+
+/// AtomicWriteFile-ACK (15.2.2), the position or record the data was written at
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AtomicWriteFileAck {
+    FileStartPosition(i32),
+    FileStartRecord(i32),
+}
+
+impl Encode for AtomicWriteFileAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        match self {
+            Self::FileStartPosition(position) => {
+                Tag::context(0, &encode_signed(*position)).encode(writer)
+            }
+            Self::FileStartRecord(record) => {
+                Tag::context(1, &encode_signed(*record)).encode(writer)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::FileStartPosition(v) | Self::FileStartRecord(v) => 1 + encode_signed(*v).len(),
+        }
+    }
+}
+
+impl Decode for AtomicWriteFileAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (tag, _) = Tag::decode(&data)?;
+        if tag.is_context(0) {
+            Ok(Self::FileStartPosition(decode_signed(tag.data())?))
+        } else {
+            Ok(Self::FileStartRecord(decode_signed(tag.context_data(1)?)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_write_stream() {
+        // AtomicWriteFile file,1 at position 30
+        let request = AtomicWriteFile::stream(
+            ObjectIdentifier::new(ObjectType::File, 1).unwrap(),
+            30,
+            b"Chiller01 On-Time=4.3 Hours".to_vec(),
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(
+            data,
+            hex::decode(
+                "c4028000010e311e651b4368696c6c65723031204f6e2d54696d653d342e3320486f7572730f"
+            )
+            .unwrap()
+        );
+        assert_eq!(data.len(), request.len());
+        assert_eq!(AtomicWriteFile::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(1).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x01, 0x07]);
+    }
+
+    #[test]
+    fn test_write_records_appended() {
+        let request = AtomicWriteFile::record(
+            ObjectIdentifier::new(ObjectType::File, 2).unwrap(),
+            -1,
+            vec![b"12:00,45.6".to_vec(), b"12:15,44.8".to_vec()],
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data.len(), request.len());
+        assert_eq!(AtomicWriteFile::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_atomic_write_file_ack() {
+        let ack = AtomicWriteFileAck::FileStartPosition(30);
+        let data = ack.encode_vec().unwrap();
+        assert_eq!(data, vec![0x09, 0x1e]);
+        assert_eq!(data.len(), ack.len());
+        assert_eq!(AtomicWriteFileAck::decode_slice(&data).unwrap(), ack);
+
+        assert_eq!(
+            AtomicWriteFileAck::decode_slice(&[0x19, 0x0e]).unwrap(),
+            AtomicWriteFileAck::FileStartRecord(14)
+        );
+        let err = AtomicWriteFileAck::decode_slice(&[0x29, 0x0e]).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::application::PropertyReference;
+use crate::application::service::write_property_multiple::decode_property_write;
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, Tag, decode_context, decode_enclosed, decode_unsigned,
+    encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// ConfirmedCOVNotification-Request (13.15.1.1) and UnconfirmedCOVNotification-Request
+/// (13.16.1.1), both carry the same parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct CovNotification {
+    pub subscriber_process_identifier: u32,
+    pub initiating_device_identifier: ObjectIdentifier,
+    pub monitored_object_identifier: ObjectIdentifier,
+    /// Remaining lifetime of the subscription in seconds
+    pub time_remaining: u32,
+    /// Properties, or elements of array properties, and their new values
+    pub list_of_values: Vec<(PropertyReference, ApplicationValue)>,
+}
+
+impl CovNotification {
+    /// Confirmed service choice (21)
+    pub const CONFIRMED_SERVICE_CHOICE: u8 = 1;
+    /// Unconfirmed service choice (21)
+    pub const UNCONFIRMED_SERVICE_CHOICE: u8 = 2;
+
+    /// New value of `property_identifier`, if it is part of the notification as a whole
+    pub fn value(&self, property_identifier: impl Into<u32>) -> Option<&ApplicationValue> {
+        self.find(&PropertyReference::new(property_identifier))
+    }
+
+    /// New value of element `index` of the array `property_identifier`, if it is part of the
+    /// notification
+    pub fn element(
+        &self,
+        property_identifier: impl Into<u32>,
+        index: u32,
+    ) -> Option<&ApplicationValue> {
+        self.find(&PropertyReference::new(property_identifier).array_index(index))
+    }
+
+    fn find(&self, reference: &PropertyReference) -> Option<&ApplicationValue> {
+        self.list_of_values
+            .iter()
+            .find(|(r, _)| r == reference)
+            .map(|(_, v)| v)
+    }
+
+    fn values_len(&self) -> usize {
+        self.list_of_values
+            .iter()
+            .map(|(r, v)| r.len() + 1 + v.len() + 1)
+            .sum()
+    }
+}
+
+impl Encode for CovNotification {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_unsigned(self.subscriber_process_identifier)).encode(writer)?;
+        Tag::context(1, &self.initiating_device_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(2, &self.monitored_object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(3, &encode_unsigned(self.time_remaining)).encode(writer)?;
+        Tag::opening(4).encode(writer)?;
+        for (reference, value) in &self.list_of_values {
+            reference.encode(writer)?;
+            Tag::opening(2).encode(writer)?;
+            value.encode(writer)?;
+            Tag::closing(2).encode(writer)?;
+        }
+        Tag::closing(4).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + encode_unsigned(self.subscriber_process_identifier).len();
+        l += 1 + 4; // Initiating Device Identifier
+        l += 1 + 4; // Monitored Object Identifier
+        l += 1 + encode_unsigned(self.time_remaining).len();
+        l += 1 + self.values_len() + 1; // List of Values
+        l
+    }
+}
+
+impl Decode for CovNotification {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (subscriber_process_identifier, rest) = decode_context(&data, 0)?;
+        let (initiating_device_identifier, rest) = decode_context(rest, 1)?;
+        let (monitored_object_identifier, rest) = decode_context(rest, 2)?;
+        let (time_remaining, rest) = decode_context(rest, 3)?;
+        let (mut list, _) = decode_enclosed(rest, 4)?;
+
+        let mut list_of_values = Vec::new();
+        while !list.is_empty() {
+            let (property, rest) = decode_property_write(list)?;
+            let value = property.value.single().cloned().ok_or_else(|| {
+                Error::InvalidValue(format!("Not a single value: {:?}", property.value))
+            })?;
+            let reference = PropertyReference {
+                property_identifier: property.property_identifier,
+                property_array_index: property.property_array_index,
+            };
+            list_of_values.push((reference, value));
+            list = rest;
+        }
+
+        Ok(Self {
+            subscriber_process_identifier: decode_unsigned(subscriber_process_identifier)?,
+            initiating_device_identifier: ObjectIdentifier::decode_slice(
+                initiating_device_identifier,
+            )?,
+            monitored_object_identifier: ObjectIdentifier::decode_slice(
+                monitored_object_identifier,
+            )?,
+            time_remaining: decode_unsigned(time_remaining)?,
+            list_of_values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{BitString, ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_decode_cov_notification_analog_input() {
+        // analog-input,10 present-value 65.0, status-flags {0,0,0,0}
+        let data = hex::decode(concat!(
+            "0912",
+            "1c02000004",
+            "2c0000000a",
+            "3a0118",
+            "4e",
+            "09552e44428200002f",
+            "096f2e8204002f",
+            "4f"
+        ))
+        .unwrap();
+        let notification = CovNotification::decode_slice(&data).expect("Decode notification");
+
+        assert_eq!(notification.subscriber_process_identifier, 18);
+        assert_eq!(
+            notification.initiating_device_identifier,
+            ObjectIdentifier::new(ObjectType::Device, 4).unwrap()
+        );
+        assert_eq!(
+            notification.monitored_object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 10).unwrap()
+        );
+        assert_eq!(notification.time_remaining, 280);
+        assert_eq!(
+            notification.value(PropertyIdentifier::PresentValue),
+            Some(&ApplicationValue::Real(65.0))
+        );
+        assert_eq!(
+            notification.value(PropertyIdentifier::StatusFlags),
+            Some(&ApplicationValue::BitString(BitString::new(vec![false; 4])))
+        );
+
+        assert_eq!(notification.encode_vec().unwrap(), data);
+        assert_eq!(notification.len(), data.len());
+    }
+
+    #[test]
+    fn test_decode_cov_notification_array_element() {
+        // analog-value,1 priority-array[8] 50.0
+        let data = hex::decode(concat!(
+            "0912",
+            "1c02000004",
+            "2c00800001",
+            "3a0118",
+            "4e",
+            "095719082e44424800002f",
+            "4f"
+        ))
+        .unwrap();
+        let notification = CovNotification::decode_slice(&data).expect("Decode notification");
+
+        assert_eq!(
+            notification.list_of_values,
+            vec![(
+                PropertyReference::new(PropertyIdentifier::PriorityArray).array_index(8),
+                ApplicationValue::Real(50.0)
+            )]
+        );
+        assert_eq!(
+            notification.element(PropertyIdentifier::PriorityArray, 8),
+            Some(&ApplicationValue::Real(50.0))
+        );
+        assert_eq!(notification.value(PropertyIdentifier::PriorityArray), None);
+
+        assert_eq!(notification.encode_vec().unwrap(), data);
+        assert_eq!(notification.len(), data.len());
+    }
+
+    #[test]
+    fn test_decode_cov_notification_truncated() {
+        let data = hex::decode("09121c020000042c0000000a3a01184e0955").unwrap();
+        assert!(CovNotification::decode_slice(&data).is_err());
+    }
+}
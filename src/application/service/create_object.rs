@@ -0,0 +1,211 @@
+use crate::application::service::write_property_multiple::decode_property_write;
+use crate::application::{ConfirmedRequestService, PropertyWrite};
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, ObjectType, Tag, decode_application_value, decode_enclosed,
+    decode_enumerated, encode_enumerated,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// Object to create, the device picks the instance when only the type is given (15.3.1.1.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ObjectSpecifier {
+    Type(ObjectType),
+    Identifier(ObjectIdentifier),
+}
+
+/// CreateObject-Request (15.3.1)
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateObject {
+    pub object_specifier: ObjectSpecifier,
+    pub list_of_initial_values: Vec<PropertyWrite>,
+}
+
+impl CreateObject {
+    pub fn new(object_specifier: ObjectSpecifier) -> Self {
+        Self {
+            object_specifier,
+            list_of_initial_values: Vec::new(),
+        }
+    }
+
+    pub fn initial_value(mut self, value: PropertyWrite) -> Self {
+        self.list_of_initial_values.push(value);
+        self
+    }
+}
+
+impl ConfirmedRequestService for CreateObject {
+    const SERVICE_CHOICE: u8 = 10;
+}
+
+impl Encode for CreateObject {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::opening(0).encode(writer)?;
+        match self.object_specifier {
+            ObjectSpecifier::Type(object_type) => {
+                Tag::context(0, &encode_enumerated(u16::from(object_type) as u32)).encode(writer)?
+            }
+            ObjectSpecifier::Identifier(object_identifier) => {
+                Tag::context(1, &object_identifier.encode_vec()?).encode(writer)?
+            }
+        }
+        Tag::closing(0).encode(writer)?;
+        if !self.list_of_initial_values.is_empty() {
+            Tag::opening(1).encode(writer)?;
+            for value in &self.list_of_initial_values {
+                value.encode(writer)?;
+            }
+            Tag::closing(1).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 1;
+        l += match self.object_specifier {
+            ObjectSpecifier::Type(object_type) => {
+                1 + encode_enumerated(u16::from(object_type) as u32).len()
+            }
+            ObjectSpecifier::Identifier(_) => 1 + 4,
+        };
+        l += 1;
+        if !self.list_of_initial_values.is_empty() {
+            let values: usize = self.list_of_initial_values.iter().map(|v| v.len()).sum();
+            l += 1 + values + 1;
+        }
+        l
+    }
+}
+
+impl Decode for CreateObject {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (specifier, rest) = decode_enclosed(&data, 0)?;
+        let (tag, _) = Tag::decode(specifier)?;
+        let object_specifier = if tag.is_context(0) {
+            let object_type = decode_enumerated(tag.data())?;
+            let object_type = u16::try_from(object_type)
+                .map_err(|_| {
+                    Error::InvalidValue(format!("Object type out of range: {}", object_type))
+                })
+                .and_then(ObjectType::try_from)?;
+            ObjectSpecifier::Type(object_type)
+        } else {
+            ObjectSpecifier::Identifier(ObjectIdentifier::decode_slice(tag.context_data(1)?)?)
+        };
+
+        let mut list_of_initial_values = Vec::new();
+        if !rest.is_empty() {
+            let (mut list, _) = decode_enclosed(rest, 1)?;
+            while !list.is_empty() {
+                let (value, l) = decode_property_write(list)?;
+                list_of_initial_values.push(value);
+                list = l;
+            }
+        }
+        Ok(Self {
+            object_specifier,
+            list_of_initial_values,
+        })
+    }
+}
+
+/// CreateObject-ACK (15.3.1.3), the identifier of the created object
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CreateObjectAck {
+    pub object_identifier: ObjectIdentifier,
+}
+
+impl Encode for CreateObjectAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        ApplicationValue::ObjectId(self.object_identifier).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        1 + 4
+    }
+}
+
+impl Decode for CreateObjectAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        match decode_application_value(&data)? {
+            (ApplicationValue::ObjectId(object_identifier), _) => Ok(Self { object_identifier }),
+            (v, _) => Err(Error::InvalidValue(format!(
+                "Invalid object identifier: {:?}",
+                v
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::PropertyIdentifier;
+
+    #[test]
+    fn test_create_analog_value_with_name() {
+        let request = CreateObject::new(ObjectSpecifier::Type(ObjectType::AnalogValue))
+            .initial_value(PropertyWrite::new(
+                PropertyIdentifier::ObjectName,
+                ApplicationValue::CharacterString("Zone Temp SP".to_string()),
+            ));
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(
+            data,
+            hex::decode("0e09020f1e094d2e750d005a6f6e652054656d702053502f1f").unwrap()
+        );
+        assert_eq!(data.len(), request.len());
+        assert_eq!(CreateObject::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(4).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x04, 0x0a]);
+    }
+
+    #[test]
+    fn test_create_object_by_identifier() {
+        let request = CreateObject::new(ObjectSpecifier::Identifier(
+            ObjectIdentifier::new(ObjectType::AnalogValue, 7).unwrap(),
+        ));
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("0e1c008000070f").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(CreateObject::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_decode_create_object_ack() {
+        let ack = CreateObjectAck::decode_slice(&hex::decode("c400800005").unwrap()).unwrap();
+
+        assert_eq!(
+            ack.object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogValue, 5).unwrap()
+        );
+        assert_eq!(
+            ack.encode_vec().unwrap(),
+            hex::decode("c400800005").unwrap()
+        );
+
+        let err = CreateObjectAck::decode_slice(&[0x21, 0x05]).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_create_object_invalid() {
+        // Object specifier with context tag 2
+        let err = CreateObject::decode_slice(&hex::decode("0e29020f").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{ApplicationValue, ObjectIdentifier, decode_application_value};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// DeleteObject-Request (15.4.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DeleteObject {
+    pub object_identifier: ObjectIdentifier,
+}
+
+impl DeleteObject {
+    pub fn new(object_identifier: ObjectIdentifier) -> Self {
+        Self { object_identifier }
+    }
+}
+
+impl ConfirmedRequestService for DeleteObject {
+    const SERVICE_CHOICE: u8 = 11;
+}
+
+impl Encode for DeleteObject {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        ApplicationValue::ObjectId(self.object_identifier).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        1 + 4
+    }
+}
+
+impl Decode for DeleteObject {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        match decode_application_value(&data)? {
+            (ApplicationValue::ObjectId(object_identifier), _) => Ok(Self::new(object_identifier)),
+            (v, _) => Err(Error::InvalidValue(format!(
+                "Invalid object identifier: {:?}",
+                v
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_delete_object() {
+        let request = DeleteObject::new(ObjectIdentifier::new(ObjectType::AnalogValue, 5).unwrap());
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("c400800005").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(DeleteObject::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(6).unwrap().encode_vec().unwrap();
+        assert_eq!(apdu, hex::decode("0005060bc400800005").unwrap());
+    }
+}
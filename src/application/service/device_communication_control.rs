@@ -0,0 +1,184 @@
+use crate::application::{ConfirmedRequestService, MAX_PASSWORD_LENGTH};
+use crate::encoding::{
+    Tag, decode_character_string, decode_context, decode_enumerated, decode_optional_context,
+    decode_unsigned, encode_character_string, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Enable-disable of DeviceCommunicationControl (16.1.1.1.2)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EnableDisable {
+    Enable,            // = 0;
+    Disable,           // = 1;
+    DisableInitiation, // = 2;
+}
+
+impl TryFrom<u32> for EnableDisable {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Enable),
+            1 => Ok(Self::Disable),
+            2 => Ok(Self::DisableInitiation),
+            v => Err(Error::InvalidValue(format!(
+                "Enable-disable not supported: {}",
+                v
+            ))),
+        }
+    }
+}
+
+impl From<EnableDisable> for u32 {
+    fn from(value: EnableDisable) -> Self {
+        match value {
+            EnableDisable::Enable => 0,
+            EnableDisable::Disable => 1,
+            EnableDisable::DisableInitiation => 2,
+        }
+    }
+}
+
+/// DeviceCommunicationControl-Request (16.1.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviceCommunicationControl {
+    /// Minutes until communication is enabled again, None for indefinitely
+    pub time_duration: Option<u16>,
+    pub enable_disable: EnableDisable,
+    pub password: Option<String>,
+}
+
+impl DeviceCommunicationControl {
+    pub fn new(enable_disable: EnableDisable) -> Self {
+        Self {
+            time_duration: None,
+            enable_disable,
+            password: None,
+        }
+    }
+
+    pub fn time_duration(mut self, minutes: u16) -> Self {
+        self.time_duration = Some(minutes);
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+}
+
+impl ConfirmedRequestService for DeviceCommunicationControl {
+    const SERVICE_CHOICE: u8 = 17;
+}
+
+impl Encode for DeviceCommunicationControl {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        if let Some(minutes) = self.time_duration {
+            Tag::context(0, &encode_unsigned(minutes as u32)).encode(writer)?;
+        }
+        Tag::context(1, &encode_enumerated(self.enable_disable.into())).encode(writer)?;
+        if let Some(password) = &self.password {
+            if password.chars().count() > MAX_PASSWORD_LENGTH {
+                return Err(Error::InvalidValue(format!(
+                    "Password longer than {} characters",
+                    MAX_PASSWORD_LENGTH
+                )));
+            }
+            Tag::context(2, &encode_character_string(password)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        if let Some(minutes) = self.time_duration {
+            l += 1 + encode_unsigned(minutes as u32).len();
+        }
+        l += 1 + encode_enumerated(self.enable_disable.into()).len();
+        if let Some(password) = &self.password {
+            l += Tag::context(2, &encode_character_string(password)).len();
+        }
+        l
+    }
+}
+
+impl Decode for DeviceCommunicationControl {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (time_duration, rest) = decode_optional_context(&data, 0)?;
+        let (enable_disable, rest) = decode_context(rest, 1)?;
+        let (password, _) = decode_optional_context(rest, 2)?;
+        let time_duration = time_duration
+            .map(|d| {
+                let minutes = decode_unsigned(d)?;
+                u16::try_from(minutes).map_err(|_| {
+                    Error::InvalidValue(format!("Time duration out of range: {}", minutes))
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            time_duration,
+            enable_disable: EnableDisable::try_from(decode_enumerated(enable_disable)?)?,
+            password: password.map(decode_character_string).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disable_for_60_minutes_with_password() {
+        let request = DeviceCommunicationControl::new(EnableDisable::Disable)
+            .time_duration(60)
+            .password("#egbdf!");
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("093c19012d080023656762646621").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(
+            DeviceCommunicationControl::decode_slice(&data).unwrap(),
+            request
+        );
+
+        let apdu = request.confirmed_request(5).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x05, 0x11]);
+    }
+
+    #[test]
+    fn test_enable_without_password() {
+        let request = DeviceCommunicationControl::new(EnableDisable::Enable);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, vec![0x19, 0x00]);
+        assert_eq!(data.len(), request.len());
+        let decoded = DeviceCommunicationControl::decode_slice(&data).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.time_duration, None);
+    }
+
+    #[test]
+    fn test_device_communication_control_invalid() {
+        // Enable-disable missing
+        let err = DeviceCommunicationControl::decode_slice(&[0x09, 0x3c]).unwrap_err();
+        assert!(
+            matches!(err, Error::Truncated | Error::InvalidTag(_)),
+            "{:?}",
+            err
+        );
+        // Time duration beyond Unsigned16
+        let err = DeviceCommunicationControl::decode_slice(&hex::decode("0b0100001901").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::encoding::{
+    BitString, EventState, LengthValueType, NotifyType, ObjectIdentifier, Tag, TagNumber,
+    TimeStamp, decode_boolean, decode_character_string, decode_context, decode_enclosed,
+    decode_enumerated, decode_optional_context, decode_real, decode_unsigned, encode_boolean,
+    encode_character_string, encode_enumerated, encode_real, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{string::String, vec::Vec};
+
+/// BACnetEventType (21) of a change-of-state event
+pub const EVENT_TYPE_CHANGE_OF_STATE: u32 = 1;
+
+/// BACnetEventType (21) of an out-of-range event
+pub const EVENT_TYPE_OUT_OF_RANGE: u32 = 5;
+
+/// BACnetPropertyStates (21), a choice of enumerated, unsigned or boolean states identified by
+/// its context tag, e.g. 0 = boolean-value, 1 = binary-value, 8 = state
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PropertyState {
+    pub choice: u8,
+    pub value: u32,
+}
+
+impl PropertyState {
+    pub fn new(choice: u8, value: u32) -> Self {
+        Self { choice, value }
+    }
+}
+
+/// Tag number and LVT of the context tag at the start of `input`
+fn context_tag_number(input: &[u8]) -> crate::Result<(u8, LengthValueType)> {
+    let (tag, _) = Tag::decode(input)?;
+    match tag.tag_number() {
+        TagNumber::Context(t) => Ok((u8::from(t), tag.lvt())),
+        t => Err(Error::InvalidTag(format!(
+            "Expected context tag, found {:?}",
+            t
+        ))),
+    }
+}
+
+/// BACnetNotificationParameters (21), the values of the event type, tagged with its
+/// BACnetEventType
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventValues {
+    ChangeOfState {
+        new_state: PropertyState,
+        status_flags: BitString,
+    },
+    OutOfRange {
+        exceeding_value: f32,
+        status_flags: BitString,
+        deadband: f32,
+        exceeded_limit: f32,
+    },
+    /// Parameters of another event type, kept as their encoded octets
+    Other { event_type: u32, data: Vec<u8> },
+}
+
+impl EventValues {
+    /// BACnetEventType (21) of the parameters, the context tag enclosing them
+    pub fn event_type(&self) -> u32 {
+        match self {
+            Self::ChangeOfState { .. } => EVENT_TYPE_CHANGE_OF_STATE,
+            Self::OutOfRange { .. } => EVENT_TYPE_OUT_OF_RANGE,
+            Self::Other { event_type, .. } => *event_type,
+        }
+    }
+
+    fn tag_number(&self) -> crate::Result<u8> {
+        u8::try_from(self.event_type()).map_err(|_| {
+            Error::InvalidValue(format!("Event type out of range: {}", self.event_type()))
+        })
+    }
+}
+
+impl Encode for EventValues {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        let tag_number = self.tag_number()?;
+        Tag::opening(tag_number).encode(writer)?;
+        match self {
+            Self::ChangeOfState {
+                new_state,
+                status_flags,
+            } => {
+                Tag::opening(0).encode(writer)?;
+                Tag::context(new_state.choice, &encode_unsigned(new_state.value)).encode(writer)?;
+                Tag::closing(0).encode(writer)?;
+                Tag::context(1, &status_flags.encode_vec()?).encode(writer)?;
+            }
+            Self::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            } => {
+                Tag::context(0, &encode_real(*exceeding_value)).encode(writer)?;
+                Tag::context(1, &status_flags.encode_vec()?).encode(writer)?;
+                Tag::context(2, &encode_real(*deadband)).encode(writer)?;
+                Tag::context(3, &encode_real(*exceeded_limit)).encode(writer)?;
+            }
+            Self::Other { data, .. } => writer.write_all(data)?,
+        }
+        Tag::closing(tag_number).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        let l = match self {
+            Self::ChangeOfState {
+                new_state,
+                status_flags,
+            } => {
+                let state = Tag::context(new_state.choice, &encode_unsigned(new_state.value)).len();
+                1 + state + 1 + 1 + status_flags.len()
+            }
+            Self::OutOfRange { status_flags, .. } => 3 * (1 + 4) + 1 + status_flags.len(),
+            Self::Other { data, .. } => data.len(),
+        };
+        let tag_number = self.tag_number().unwrap_or(u8::MAX);
+        let tags = Tag::opening(tag_number).len() + Tag::closing(tag_number).len();
+        tags + l
+    }
+}
+
+impl Decode for EventValues {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (tag_number, _) = context_tag_number(&data)?;
+        let (values, _) = decode_enclosed(&data, tag_number)?;
+        match u32::from(tag_number) {
+            EVENT_TYPE_CHANGE_OF_STATE => {
+                let (new_state, rest) = decode_enclosed(values, 0)?;
+                let (status_flags, _) = decode_context(rest, 1)?;
+                let (choice, lvt) = context_tag_number(new_state)?;
+                if !matches!(lvt, LengthValueType::Length(_)) {
+                    return Err(Error::InvalidTag(format!(
+                        "Property state not supported: {:?}",
+                        lvt
+                    )));
+                }
+                let (value, _) = decode_context(new_state, choice)?;
+                Ok(Self::ChangeOfState {
+                    new_state: PropertyState::new(choice, decode_unsigned(value)?),
+                    status_flags: BitString::decode_slice(status_flags)?,
+                })
+            }
+            EVENT_TYPE_OUT_OF_RANGE => {
+                let (exceeding_value, rest) = decode_context(values, 0)?;
+                let (status_flags, rest) = decode_context(rest, 1)?;
+                let (deadband, rest) = decode_context(rest, 2)?;
+                let (exceeded_limit, _) = decode_context(rest, 3)?;
+                Ok(Self::OutOfRange {
+                    exceeding_value: decode_real(exceeding_value)?,
+                    status_flags: BitString::decode_slice(status_flags)?,
+                    deadband: decode_real(deadband)?,
+                    exceeded_limit: decode_real(exceeded_limit)?,
+                })
+            }
+            event_type => Ok(Self::Other {
+                event_type,
+                data: values.to_vec(),
+            }),
+        }
+    }
+}
+
+/// ConfirmedEventNotification-Request (13.8.1) and UnconfirmedEventNotification-Request
+/// (13.9.1), both carry the same parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventNotification {
+    pub process_identifier: u32,
+    pub initiating_device_identifier: ObjectIdentifier,
+    pub event_object_identifier: ObjectIdentifier,
+    pub time_stamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    /// BACnetEventType (21), e.g. `EVENT_TYPE_CHANGE_OF_STATE`. Ack-notifications only carry
+    /// this, otherwise it must be the type of `event_values`
+    pub event_type: u32,
+    pub message_text: Option<String>,
+    pub notify_type: NotifyType,
+    /// Absent for ack-notifications, as are `from_state` and `event_values`
+    pub ack_required: Option<bool>,
+    pub from_state: Option<EventState>,
+    pub to_state: EventState,
+    pub event_values: Option<EventValues>,
+}
+
+impl EventNotification {
+    /// Confirmed service choice (21)
+    pub const CONFIRMED_SERVICE_CHOICE: u8 = 2;
+    /// Unconfirmed service choice (21)
+    pub const UNCONFIRMED_SERVICE_CHOICE: u8 = 3;
+
+    /// Fail if the event values are of another type than `event_type`
+    fn check_event_type(&self) -> crate::Result<()> {
+        match &self.event_values {
+            Some(values) if values.event_type() != self.event_type => {
+                Err(Error::InvalidValue(format!(
+                    "Event values of type {} in a notification of type {}",
+                    values.event_type(),
+                    self.event_type
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Encode for EventNotification {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.check_event_type()?;
+        Tag::context(0, &encode_unsigned(self.process_identifier)).encode(writer)?;
+        Tag::context(1, &self.initiating_device_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(2, &self.event_object_identifier.encode_vec()?).encode(writer)?;
+        Tag::opening(3).encode(writer)?;
+        self.time_stamp.encode(writer)?;
+        Tag::closing(3).encode(writer)?;
+        Tag::context(4, &encode_unsigned(self.notification_class)).encode(writer)?;
+        Tag::context(5, &encode_unsigned(self.priority as u32)).encode(writer)?;
+        Tag::context(6, &encode_enumerated(self.event_type)).encode(writer)?;
+        if let Some(message_text) = &self.message_text {
+            Tag::context(7, &encode_character_string(message_text)).encode(writer)?;
+        }
+        Tag::context(8, &encode_enumerated(self.notify_type.into())).encode(writer)?;
+        if let Some(ack_required) = self.ack_required {
+            Tag::context(9, &encode_boolean(ack_required)).encode(writer)?;
+        }
+        if let Some(from_state) = self.from_state {
+            Tag::context(10, &encode_enumerated(from_state.into())).encode(writer)?;
+        }
+        Tag::context(11, &encode_enumerated(self.to_state.into())).encode(writer)?;
+        if let Some(event_values) = &self.event_values {
+            Tag::opening(12).encode(writer)?;
+            event_values.encode(writer)?;
+            Tag::closing(12).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + encode_unsigned(self.process_identifier).len();
+        l += 1 + 4; // Initiating Device Identifier
+        l += 1 + 4; // Event Object Identifier
+        l += 1 + self.time_stamp.len() + 1;
+        l += 1 + encode_unsigned(self.notification_class).len();
+        l += 1 + encode_unsigned(self.priority as u32).len();
+        l += 1 + encode_enumerated(self.event_type).len();
+        if let Some(message_text) = &self.message_text {
+            l += Tag::context(7, &encode_character_string(message_text)).len();
+        }
+        l += 1 + encode_enumerated(self.notify_type.into()).len();
+        if self.ack_required.is_some() {
+            l += 1 + 1;
+        }
+        if let Some(from_state) = self.from_state {
+            l += 1 + encode_enumerated(from_state.into()).len();
+        }
+        l += 1 + encode_enumerated(self.to_state.into()).len();
+        if let Some(event_values) = &self.event_values {
+            l += 1 + event_values.len() + 1;
+        }
+        l
+    }
+}
+
+impl Decode for EventNotification {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (process_identifier, rest) = decode_context(&data, 0)?;
+        let (initiating_device_identifier, rest) = decode_context(rest, 1)?;
+        let (event_object_identifier, rest) = decode_context(rest, 2)?;
+        let (time_stamp, rest) = decode_enclosed(rest, 3)?;
+        let (notification_class, rest) = decode_context(rest, 4)?;
+        let (priority, rest) = decode_context(rest, 5)?;
+        let (event_type, rest) = decode_context(rest, 6)?;
+        let (message_text, rest) = decode_optional_context(rest, 7)?;
+        let (notify_type, rest) = decode_context(rest, 8)?;
+        let (ack_required, rest) = decode_optional_context(rest, 9)?;
+        let (from_state, rest) = decode_optional_context(rest, 10)?;
+        let (to_state, rest) = decode_context(rest, 11)?;
+        let event_values = match rest {
+            [] => None,
+            rest => Some(EventValues::decode_slice(decode_enclosed(rest, 12)?.0)?),
+        };
+
+        let priority = decode_unsigned(priority)?;
+        let event_state = |data| EventState::try_from(decode_enumerated(data)?);
+        let notification = Self {
+            process_identifier: decode_unsigned(process_identifier)?,
+            initiating_device_identifier: ObjectIdentifier::decode_slice(
+                initiating_device_identifier,
+            )?,
+            event_object_identifier: ObjectIdentifier::decode_slice(event_object_identifier)?,
+            time_stamp: TimeStamp::decode_slice(time_stamp)?,
+            notification_class: decode_unsigned(notification_class)?,
+            priority: u8::try_from(priority)
+                .map_err(|_| Error::InvalidValue(format!("Priority out of range: {}", priority)))?,
+            event_type: decode_enumerated(event_type)?,
+            message_text: message_text.map(decode_character_string).transpose()?,
+            notify_type: NotifyType::try_from(decode_enumerated(notify_type)?)?,
+            ack_required: ack_required
+                .map(|a| decode_boolean(&Tag::context(9, a)))
+                .transpose()?,
+            from_state: from_state.map(event_state).transpose()?,
+            to_state: event_state(to_state)?,
+            event_values,
+        };
+        notification.check_event_type()?;
+        Ok(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::UnconfirmedService;
+    use crate::encoding::{Date, ObjectType, Time};
+
+    /// binary-input,5 from normal to offnormal, new state active, in alarm
+    const CHANGE_OF_STATE: &str = concat!(
+        "0901",
+        "1c02000004",
+        "2c00c00005",
+        "3e19103f",
+        "4904",
+        "5964",
+        "6901",
+        "7d0a00446f6f72206f70656e",
+        "8900",
+        "9901",
+        "a900",
+        "b902",
+        "ce1e0e19010f1a04801fcf"
+    );
+
+    #[test]
+    fn test_decode_change_of_state_notification() {
+        let data = hex::decode(CHANGE_OF_STATE).unwrap();
+        let notification = EventNotification::decode_slice(&data).expect("Decode notification");
+
+        assert_eq!(
+            notification,
+            EventNotification {
+                process_identifier: 1,
+                initiating_device_identifier: ObjectIdentifier::new(ObjectType::Device, 4).unwrap(),
+                event_object_identifier: ObjectIdentifier::new(ObjectType::BinaryInput, 5).unwrap(),
+                time_stamp: TimeStamp::SequenceNumber(16),
+                notification_class: 4,
+                priority: 100,
+                event_type: EVENT_TYPE_CHANGE_OF_STATE,
+                message_text: Some("Door open".to_string()),
+                notify_type: NotifyType::Alarm,
+                ack_required: Some(true),
+                from_state: Some(EventState::Normal),
+                to_state: EventState::Offnormal,
+                event_values: Some(EventValues::ChangeOfState {
+                    new_state: PropertyState::new(1, 1),
+                    status_flags: BitString::new(vec![true, false, false, false]),
+                }),
+            }
+        );
+        assert_eq!(notification.encode_vec().unwrap(), data);
+        assert_eq!(notification.len(), data.len());
+
+        let mut service = vec![EventNotification::UNCONFIRMED_SERVICE_CHOICE];
+        service.extend(&data);
+        assert_eq!(
+            UnconfirmedService::decode_slice(&service).unwrap(),
+            UnconfirmedService::UnconfirmedEventNotification(notification)
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_notification_round_trip() {
+        let notification = EventNotification {
+            process_identifier: 1,
+            initiating_device_identifier: ObjectIdentifier::new(ObjectType::Device, 4).unwrap(),
+            event_object_identifier: ObjectIdentifier::new(ObjectType::AnalogInput, 2).unwrap(),
+            time_stamp: TimeStamp::DateTime(
+                Date::new(2024, 3, 15, 5).unwrap(),
+                Time::new(15, 35, 0, 20),
+            ),
+            notification_class: 4,
+            priority: 100,
+            event_type: EVENT_TYPE_OUT_OF_RANGE,
+            message_text: None,
+            notify_type: NotifyType::Alarm,
+            ack_required: Some(true),
+            from_state: Some(EventState::Normal),
+            to_state: EventState::HighLimit,
+            event_values: Some(EventValues::OutOfRange {
+                exceeding_value: 80.1,
+                status_flags: BitString::new(vec![true, false, false, false]),
+                deadband: 1.0,
+                exceeded_limit: 80.0,
+            }),
+        };
+
+        let data = notification.encode_vec().unwrap();
+        assert_eq!(data.len(), notification.len());
+        assert_eq!(
+            EventNotification::decode_slice(&data).unwrap(),
+            notification
+        );
+    }
+
+    #[test]
+    fn test_ack_notification_and_other_event_type() {
+        // Ack-notification without ack-required, from-state and event values
+        let data = hex::decode("09011c020000042c00c000053e19113f4904596469018902b902").unwrap();
+        let notification = EventNotification::decode_slice(&data).unwrap();
+        assert_eq!(notification.notify_type, NotifyType::AckNotification);
+        assert_eq!(notification.ack_required, None);
+        assert_eq!(notification.event_values, None);
+        assert_eq!(notification.encode_vec().unwrap(), data);
+
+        // Change-of-value parameters are kept encoded
+        let values =
+            EventValues::decode_slice(&hex::decode("2e0e1c42a000000f1a04002f").unwrap()).unwrap();
+        assert_eq!(
+            values,
+            EventValues::Other {
+                event_type: 2,
+                data: hex::decode("0e1c42a000000f1a0400").unwrap()
+            }
+        );
+        assert_eq!(values.len(), 12);
+    }
+
+    #[test]
+    fn test_event_type_mismatch() {
+        // Event type out-of-range with change-of-state values
+        let data = hex::decode(CHANGE_OF_STATE.replacen("6901", "6905", 1)).unwrap();
+        let err = EventNotification::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+
+        let mut notification =
+            EventNotification::decode_slice(&hex::decode(CHANGE_OF_STATE).unwrap()).unwrap();
+        notification.event_type = EVENT_TYPE_OUT_OF_RANGE;
+        let err = notification.encode_vec().unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_proprietary_event_state() {
+        // To-state 64
+        let data = hex::decode(CHANGE_OF_STATE.replacen("b902", "b940", 1)).unwrap();
+        let notification = EventNotification::decode_slice(&data).unwrap();
+        assert_eq!(notification.to_state, EventState::Proprietary(64));
+        assert_eq!(notification.encode_vec().unwrap(), data);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ApplicationValue, BitString, EventState, EventTransitionBits, ObjectIdentifier,
+    decode_application_value,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// GetAlarmSummary-Request (13.10.1), the request has no parameters
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetAlarmSummary;
+
+impl ConfirmedRequestService for GetAlarmSummary {
+    const SERVICE_CHOICE: u8 = 3;
+}
+
+impl Encode for GetAlarmSummary {
+    fn encode<T: crate::io::Write + Sized>(&self, _writer: &mut T) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+/// Alarm of a GetAlarmSummary-ACK (13.10.2)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AlarmSummary {
+    pub object_identifier: ObjectIdentifier,
+    pub alarm_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+}
+
+impl AlarmSummary {
+    fn values(&self) -> [ApplicationValue; 3] {
+        [
+            ApplicationValue::ObjectId(self.object_identifier),
+            ApplicationValue::Enumerated(self.alarm_state.into()),
+            ApplicationValue::BitString(BitString::from(self.acknowledged_transitions)),
+        ]
+    }
+}
+
+impl Encode for AlarmSummary {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.values().iter().try_for_each(|v| v.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.values().iter().map(|v| v.len()).sum()
+    }
+}
+
+/// GetAlarmSummary-ACK (13.10.2)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetAlarmSummaryAck {
+    pub list_of_alarm_summaries: Vec<AlarmSummary>,
+}
+
+impl Encode for GetAlarmSummaryAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.list_of_alarm_summaries
+            .iter()
+            .try_for_each(|s| s.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.list_of_alarm_summaries.iter().map(|s| s.len()).sum()
+    }
+}
+
+impl Decode for GetAlarmSummaryAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut list_of_alarm_summaries = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (object_identifier, next) = decode_application_value(rest)?;
+            let (alarm_state, next) = decode_application_value(next)?;
+            let (acknowledged_transitions, next) = decode_application_value(next)?;
+            let summary = match (object_identifier, alarm_state, acknowledged_transitions) {
+                (
+                    ApplicationValue::ObjectId(object_identifier),
+                    ApplicationValue::Enumerated(alarm_state),
+                    ApplicationValue::BitString(acknowledged_transitions),
+                ) => AlarmSummary {
+                    object_identifier,
+                    alarm_state: EventState::try_from(alarm_state)?,
+                    acknowledged_transitions: EventTransitionBits::from(&acknowledged_transitions),
+                },
+                v => {
+                    return Err(Error::InvalidValue(format!(
+                        "Invalid alarm summary: {:?}",
+                        v
+                    )));
+                }
+            };
+            list_of_alarm_summaries.push(summary);
+            rest = next;
+        }
+        Ok(Self {
+            list_of_alarm_summaries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_get_alarm_summary_request() {
+        let apdu = GetAlarmSummary
+            .confirmed_request(1)
+            .unwrap()
+            .encode_vec()
+            .unwrap();
+        assert_eq!(apdu, vec![0x00, 0x05, 0x01, 0x03]);
+    }
+
+    #[test]
+    fn test_decode_two_alarm_summary_ack() {
+        // analog-input,2 high-limit and analog-input,3 low-limit, to-offnormal unacknowledged
+        let data = hex::decode("c4000000029103820560c4000000039104820560").unwrap();
+        let ack = GetAlarmSummaryAck::decode_slice(&data).expect("Decode ACK");
+
+        assert_eq!(
+            ack.list_of_alarm_summaries,
+            vec![
+                AlarmSummary {
+                    object_identifier: ObjectIdentifier::new(ObjectType::AnalogInput, 2).unwrap(),
+                    alarm_state: EventState::HighLimit,
+                    acknowledged_transitions: EventTransitionBits::new(false, true, true),
+                },
+                AlarmSummary {
+                    object_identifier: ObjectIdentifier::new(ObjectType::AnalogInput, 3).unwrap(),
+                    alarm_state: EventState::LowLimit,
+                    acknowledged_transitions: EventTransitionBits::new(false, true, true),
+                },
+            ]
+        );
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+
+        assert_eq!(
+            GetAlarmSummaryAck::decode_slice(&[]).unwrap(),
+            GetAlarmSummaryAck::default()
+        );
+    }
+
+    #[test]
+    fn test_decode_alarm_summary_ack_invalid() {
+        // Alarm state as Unsigned
+        let err = GetAlarmSummaryAck::decode_slice(&hex::decode("c4000000022103820560").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        // Acknowledged transitions missing
+        let err =
+            GetAlarmSummaryAck::decode_slice(&hex::decode("c4000000029103").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ApplicationValue, BitString, EventState, EventTransitionBits, NotifyType, ObjectIdentifier,
+    Tag, TimeStamp, decode_application_value, decode_boolean, decode_context, decode_enclosed,
+    decode_enumerated, decode_optional_context, decode_time_stamp, encode_boolean,
+    encode_enumerated,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// GetEventInformation-Request (13.12.1)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetEventInformation {
+    /// Last object of the previous page, the list restarts after it
+    pub last_received_object_identifier: Option<ObjectIdentifier>,
+}
+
+impl GetEventInformation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn last_received(mut self, object_identifier: ObjectIdentifier) -> Self {
+        self.last_received_object_identifier = Some(object_identifier);
+        self
+    }
+}
+
+impl ConfirmedRequestService for GetEventInformation {
+    const SERVICE_CHOICE: u8 = 29;
+}
+
+impl Encode for GetEventInformation {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        if let Some(object_identifier) = self.last_received_object_identifier {
+            Tag::context(0, &object_identifier.encode_vec()?).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        match self.last_received_object_identifier {
+            Some(_) => 1 + 4,
+            None => 0,
+        }
+    }
+}
+
+impl Decode for GetEventInformation {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (last_received_object_identifier, _) = decode_optional_context(&data, 0)?;
+        Ok(Self {
+            last_received_object_identifier: last_received_object_identifier
+                .map(ObjectIdentifier::decode_slice)
+                .transpose()?,
+        })
+    }
+}
+
+/// Event summary of an object with an active event or an unacknowledged transition (13.12.2)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventSummary {
+    pub object_identifier: ObjectIdentifier,
+    pub event_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+    /// Time stamps of the last to-offnormal, to-fault and to-normal transitions
+    pub event_time_stamps: [TimeStamp; 3],
+    pub notify_type: NotifyType,
+    pub event_enable: EventTransitionBits,
+    /// Priorities of to-offnormal, to-fault and to-normal notifications
+    pub event_priorities: [u8; 3],
+}
+
+impl Encode for EventSummary {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.event_state.into())).encode(writer)?;
+        Tag::context(
+            2,
+            &BitString::from(self.acknowledged_transitions).encode_vec()?,
+        )
+        .encode(writer)?;
+        Tag::opening(3).encode(writer)?;
+        for time_stamp in &self.event_time_stamps {
+            time_stamp.encode(writer)?;
+        }
+        Tag::closing(3).encode(writer)?;
+        Tag::context(4, &encode_enumerated(self.notify_type.into())).encode(writer)?;
+        Tag::context(5, &BitString::from(self.event_enable).encode_vec()?).encode(writer)?;
+        Tag::opening(6).encode(writer)?;
+        for priority in self.event_priorities {
+            ApplicationValue::Unsigned(priority as u32).encode(writer)?;
+        }
+        Tag::closing(6).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let time_stamps: usize = self.event_time_stamps.iter().map(|t| t.len()).sum();
+        let priorities: usize = self
+            .event_priorities
+            .iter()
+            .map(|p| ApplicationValue::Unsigned(*p as u32).len())
+            .sum();
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.event_state.into()).len(); // Event State
+        l += 1 + BitString::from(self.acknowledged_transitions).len(); // Acknowledged Transitions
+        l += 1 + time_stamps + 1; // Event Time Stamps
+        l += 1 + encode_enumerated(self.notify_type.into()).len(); // Notify Type
+        l += 1 + BitString::from(self.event_enable).len(); // Event Enable
+        l += 1 + priorities + 1; // Event Priorities
+        l
+    }
+}
+
+/// Decode the event summary at the start of `input`, returning it together with the remaining
+/// input
+fn decode_event_summary(input: &[u8]) -> crate::Result<(EventSummary, &[u8])> {
+    let (object_identifier, rest) = decode_context(input, 0)?;
+    let (event_state, rest) = decode_context(rest, 1)?;
+    let (acknowledged_transitions, rest) = decode_context(rest, 2)?;
+    let (time_stamps, rest) = decode_enclosed(rest, 3)?;
+    let (notify_type, rest) = decode_context(rest, 4)?;
+    let (event_enable, rest) = decode_context(rest, 5)?;
+    let (priorities, rest) = decode_enclosed(rest, 6)?;
+
+    let (to_offnormal, time_stamps) = decode_time_stamp(time_stamps)?;
+    let (to_fault, time_stamps) = decode_time_stamp(time_stamps)?;
+    let (to_normal, _) = decode_time_stamp(time_stamps)?;
+    let mut event_priorities = [0; 3];
+    let mut priorities = priorities;
+    for event_priority in &mut event_priorities {
+        let (priority, next) = decode_application_value(priorities)?;
+        *event_priority = match priority {
+            ApplicationValue::Unsigned(p @ 0..=255) => p as u8,
+            v => {
+                return Err(Error::InvalidValue(format!(
+                    "Invalid event priority: {:?}",
+                    v
+                )));
+            }
+        };
+        priorities = next;
+    }
+
+    let summary = EventSummary {
+        object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+        event_state: EventState::try_from(decode_enumerated(event_state)?)?,
+        acknowledged_transitions: EventTransitionBits::from(&BitString::decode_slice(
+            acknowledged_transitions,
+        )?),
+        event_time_stamps: [to_offnormal, to_fault, to_normal],
+        notify_type: NotifyType::try_from(decode_enumerated(notify_type)?)?,
+        event_enable: EventTransitionBits::from(&BitString::decode_slice(event_enable)?),
+        event_priorities,
+    };
+    Ok((summary, rest))
+}
+
+/// GetEventInformation-ACK (13.12.2)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetEventInformationAck {
+    pub list_of_event_summaries: Vec<EventSummary>,
+    /// More summaries follow the last one of the list, to be read with another request
+    pub more_events: bool,
+}
+
+impl GetEventInformationAck {
+    /// Request for the next page of summaries, None when the list is complete or has no object
+    /// to continue after
+    pub fn next_request(&self) -> Option<GetEventInformation> {
+        if !self.more_events {
+            return None;
+        }
+        self.list_of_event_summaries
+            .last()
+            .map(|s| GetEventInformation::new().last_received(s.object_identifier))
+    }
+}
+
+impl Encode for GetEventInformationAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::opening(0).encode(writer)?;
+        for summary in &self.list_of_event_summaries {
+            summary.encode(writer)?;
+        }
+        Tag::closing(0).encode(writer)?;
+        Tag::context(1, &encode_boolean(self.more_events)).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        let summaries: usize = self.list_of_event_summaries.iter().map(|s| s.len()).sum();
+        1 + summaries + 1 + 2
+    }
+}
+
+impl Decode for GetEventInformationAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (mut list, rest) = decode_enclosed(&data, 0)?;
+        let mut list_of_event_summaries = Vec::new();
+        while !list.is_empty() {
+            let (summary, next) = decode_event_summary(list)?;
+            list_of_event_summaries.push(summary);
+            list = next;
+        }
+        let (more_events, _) = Tag::decode(rest)?;
+        if !more_events.is_context(1) {
+            return Err(Error::InvalidTag(format!(
+                "Expected context tag 1, found {:?}",
+                more_events
+            )));
+        }
+        Ok(Self {
+            list_of_event_summaries,
+            more_events: decode_boolean(&more_events)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Date, ObjectType, Time};
+
+    #[test]
+    fn test_get_event_information_request() {
+        let request = GetEventInformation::new();
+        assert!(request.encode_vec().unwrap().is_empty());
+        assert_eq!(GetEventInformation::decode_slice(&[]).unwrap(), request);
+
+        let request =
+            request.last_received(ObjectIdentifier::new(ObjectType::AnalogInput, 3).unwrap());
+        let data = request.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("0c00000003").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(GetEventInformation::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(2).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x02, 0x1d]);
+    }
+
+    /// Event summary of analog-input,2 in high-limit, to-offnormal unacknowledged
+    const SUMMARY_AI_2: &str = concat!(
+        "0c00000002",
+        "1903",
+        "2a0560",
+        "3e0c0f230014190019003f",
+        "4900",
+        "5a05e0",
+        "6e210f210f21146f"
+    );
+
+    /// Event summary of analog-input,3 back to normal, to-normal unacknowledged
+    const SUMMARY_AI_3: &str = concat!(
+        "0c00000003",
+        "1900",
+        "2a05c0",
+        "3e0c0f2800002ea47c030f05b40f2d00002f19013f",
+        "4900",
+        "5a05e0",
+        "6e210f210f21146f"
+    );
+
+    #[test]
+    fn test_decode_ack_with_two_summaries() {
+        let data = hex::decode(format!("0e{}{}0f1900", SUMMARY_AI_2, SUMMARY_AI_3)).unwrap();
+        let ack = GetEventInformationAck::decode_slice(&data).expect("Decode ACK");
+
+        assert!(!ack.more_events);
+        assert_eq!(ack.next_request(), None);
+        let [first, second] = &ack.list_of_event_summaries[..] else {
+            panic!("Expected two summaries: {:?}", ack);
+        };
+        assert_eq!(
+            *first,
+            EventSummary {
+                object_identifier: ObjectIdentifier::new(ObjectType::AnalogInput, 2).unwrap(),
+                event_state: EventState::HighLimit,
+                acknowledged_transitions: EventTransitionBits::new(false, true, true),
+                event_time_stamps: [
+                    TimeStamp::Time(Time::new(15, 35, 0, 20)),
+                    TimeStamp::SequenceNumber(0),
+                    TimeStamp::SequenceNumber(0),
+                ],
+                notify_type: NotifyType::Alarm,
+                event_enable: EventTransitionBits::new(true, true, true),
+                event_priorities: [15, 15, 20],
+            }
+        );
+        assert_eq!(second.event_state, EventState::Normal);
+        assert_eq!(
+            second.event_time_stamps[1],
+            TimeStamp::DateTime(Date::new(2024, 3, 15, 5).unwrap(), Time::new(15, 45, 0, 0))
+        );
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+    }
+
+    #[test]
+    fn test_paged_ack() {
+        // Empty list
+        let ack = GetEventInformationAck::decode_slice(&hex::decode("0e0f1900").unwrap()).unwrap();
+        assert_eq!(ack, GetEventInformationAck::default());
+        assert_eq!(ack.next_request(), None);
+
+        // More events after analog-input,2
+        let data = hex::decode(format!("0e{}0f1901", SUMMARY_AI_2)).unwrap();
+        let ack = GetEventInformationAck::decode_slice(&data).unwrap();
+        assert!(ack.more_events);
+        assert_eq!(
+            ack.next_request(),
+            Some(
+                GetEventInformation::new()
+                    .last_received(ObjectIdentifier::new(ObjectType::AnalogInput, 2).unwrap())
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_ack_invalid() {
+        // More-events missing
+        let err = GetEventInformationAck::decode_slice(&hex::decode("0e0f").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+        // Priority beyond Unsigned8
+        let data = hex::decode(format!(
+            "0e{}0f1900",
+            SUMMARY_AI_2.replace("21146f", "2201006f")
+        ))
+        .unwrap();
+        let err = GetEventInformationAck::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
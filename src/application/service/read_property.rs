@@ -0,0 +1,203 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ObjectIdentifier, PropertyValue, Tag, decode_context, decode_enclosed, decode_enumerated,
+    decode_optional_context, decode_unsigned, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// ReadProperty-Request (15.5.1.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadProperty {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+}
+
+impl ReadProperty {
+    pub fn new(object_identifier: ObjectIdentifier, property_identifier: impl Into<u32>) -> Self {
+        Self {
+            object_identifier,
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+        }
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+}
+
+impl ConfirmedRequestService for ReadProperty {
+    const SERVICE_CHOICE: u8 = 12;
+}
+
+impl Encode for ReadProperty {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len(); // Property Identifier
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len(); // Property Array Index
+        }
+        l
+    }
+}
+
+impl Decode for ReadProperty {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (object_identifier, rest) = decode_context(&data, 0)?;
+        let (property_identifier, rest) = decode_context(rest, 1)?;
+        let (property_array_index, _) = decode_optional_context(rest, 2)?;
+        Ok(Self {
+            object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+            property_identifier: decode_enumerated(property_identifier)?,
+            property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+        })
+    }
+}
+
+/// ReadProperty-ACK (15.5.1.3)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadPropertyAck {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub property_value: PropertyValue,
+}
+
+impl Encode for ReadPropertyAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        Tag::opening(3).encode(writer)?;
+        self.property_value.encode(writer)?;
+        Tag::closing(3).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len(); // Property Identifier
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len(); // Property Array Index
+        }
+        l += 1 + self.property_value.len() + 1; // Property Value
+        l
+    }
+}
+
+impl Decode for ReadPropertyAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (object_identifier, rest) = decode_context(&data, 0)?;
+        let (property_identifier, rest) = decode_context(rest, 1)?;
+        let (property_array_index, rest) = decode_optional_context(rest, 2)?;
+        let (property_value, _) = decode_enclosed(rest, 3)?;
+        Ok(Self {
+            object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+            property_identifier: decode_enumerated(property_identifier)?,
+            property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+            property_value: PropertyValue::decode_slice(property_value)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_read_property_present_value() {
+        // ReadProperty analog-input,1 present-value
+        let request = ReadProperty::new(
+            ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap(),
+            PropertyIdentifier::PresentValue,
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("0c000000011955").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReadProperty::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(1).unwrap().encode_vec().unwrap();
+        assert_eq!(apdu, hex::decode("0005010c0c000000011955").unwrap());
+    }
+
+    #[test]
+    fn test_read_property_array_index() {
+        // ReadProperty device,260001 object-list[0]
+        let request = ReadProperty::new(
+            ObjectIdentifier::new(ObjectType::Device, 260001).unwrap(),
+            PropertyIdentifier::ObjectList,
+        )
+        .array_index(0);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("0c0203f7a1194c2900").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReadProperty::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_decode_read_property_ack_present_value() {
+        // ReadProperty-ACK analog-input,1 present-value 72.5
+        let data = hex::decode("0c0000000119553e44429100003f").unwrap();
+        let ack = ReadPropertyAck::decode_slice(&data).expect("Decode ACK");
+
+        assert_eq!(
+            ack.object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap()
+        );
+        assert_eq!(ack.property_identifier, 85);
+        assert_eq!(ack.property_array_index, None);
+        assert_eq!(
+            ack.property_value.single(),
+            Some(&crate::encoding::ApplicationValue::Real(72.5))
+        );
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+    }
+
+    #[test]
+    fn test_decode_read_property_ack_constructed_value() {
+        // ReadProperty-ACK with a value containing context tags [0] { [1] OID }
+        let data = hex::decode("0c00000001194e3e0e1c000000011f3f").unwrap();
+        let ack = ReadPropertyAck::decode_slice(&data).expect("Decode ACK");
+
+        assert_eq!(
+            ack.property_value,
+            PropertyValue::Constructed(hex::decode("0e1c000000011f").unwrap())
+        );
+        assert_eq!(ack.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_read_property_missing_property() {
+        assert!(ReadProperty::decode_slice(&hex::decode("0c00000001").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use crate::application::{BACnetError, ConfirmedRequestService};
+use crate::encoding::{
+    ObjectIdentifier, PropertyValue, Tag, decode_context, decode_enclosed, decode_enumerated,
+    decode_optional_context, decode_unsigned, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// BACnetPropertyReference (21)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyReference {
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+}
+
+impl PropertyReference {
+    pub fn new(property_identifier: impl Into<u32>) -> Self {
+        Self {
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+        }
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+}
+
+impl Encode for PropertyReference {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(1, &encode_unsigned(index)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 1 + encode_enumerated(self.property_identifier).len();
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len();
+        }
+        l
+    }
+}
+
+/// ReadAccessSpecification (21)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadAccessSpecification {
+    pub object_identifier: ObjectIdentifier,
+    pub property_references: Vec<PropertyReference>,
+}
+
+impl ReadAccessSpecification {
+    pub fn new<P: Copy + Into<u32>>(object_identifier: ObjectIdentifier, properties: &[P]) -> Self {
+        Self {
+            object_identifier,
+            property_references: properties
+                .iter()
+                .map(|p| PropertyReference::new(*p))
+                .collect(),
+        }
+    }
+}
+
+impl Encode for ReadAccessSpecification {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::opening(1).encode(writer)?;
+        for reference in &self.property_references {
+            reference.encode(writer)?;
+        }
+        Tag::closing(1).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let references: usize = self.property_references.iter().map(|r| r.len()).sum();
+        1 + 4 + 1 + references + 1
+    }
+}
+
+/// ReadPropertyMultiple-Request (15.7.1.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadPropertyMultiple {
+    pub read_access_specifications: Vec<ReadAccessSpecification>,
+}
+
+impl ReadPropertyMultiple {
+    pub fn new(read_access_specifications: Vec<ReadAccessSpecification>) -> Self {
+        Self {
+            read_access_specifications,
+        }
+    }
+}
+
+impl ConfirmedRequestService for ReadPropertyMultiple {
+    const SERVICE_CHOICE: u8 = 14;
+}
+
+impl Encode for ReadPropertyMultiple {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.read_access_specifications
+            .iter()
+            .try_for_each(|s| s.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.read_access_specifications
+            .iter()
+            .map(|s| s.len())
+            .sum()
+    }
+}
+
+impl Decode for ReadPropertyMultiple {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut read_access_specifications = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (object_identifier, next) = decode_context(rest, 0)?;
+            let (references, next) = decode_enclosed(next, 1)?;
+            let mut property_references = Vec::new();
+            let mut references = references;
+            while !references.is_empty() {
+                let (property_identifier, next) = decode_context(references, 0)?;
+                let (property_array_index, next) = decode_optional_context(next, 1)?;
+                property_references.push(PropertyReference {
+                    property_identifier: decode_enumerated(property_identifier)?,
+                    property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+                });
+                references = next;
+            }
+            read_access_specifications.push(ReadAccessSpecification {
+                object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+                property_references,
+            });
+            rest = next;
+        }
+        Ok(Self::new(read_access_specifications))
+    }
+}
+
+/// Result of reading a single property, a failed property does not fail the whole request
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadResult {
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub result: Result<PropertyValue, BACnetError>,
+}
+
+impl Encode for ReadResult {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(2, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(3, &encode_unsigned(index)).encode(writer)?;
+        }
+        let (tag, content) = match &self.result {
+            Ok(value) => (4, value.encode_vec()?),
+            Err(error) => (5, error.encode_vec()?),
+        };
+        Tag::opening(tag).encode(writer)?;
+        writer.write_all(&content)?;
+        Tag::closing(tag).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 1 + encode_enumerated(self.property_identifier).len();
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len();
+        }
+        l += match &self.result {
+            Ok(value) => value.len(),
+            Err(error) => error.len(),
+        } + 2;
+        l
+    }
+}
+
+/// ReadAccessResult (21)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadAccessResult {
+    pub object_identifier: ObjectIdentifier,
+    pub results: Vec<ReadResult>,
+}
+
+impl Encode for ReadAccessResult {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::opening(1).encode(writer)?;
+        for result in &self.results {
+            result.encode(writer)?;
+        }
+        Tag::closing(1).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let results: usize = self.results.iter().map(|r| r.len()).sum();
+        1 + 4 + 1 + results + 1
+    }
+}
+
+/// ReadPropertyMultiple-ACK (15.7.1.3)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadPropertyMultipleAck {
+    pub read_access_results: Vec<ReadAccessResult>,
+}
+
+impl Encode for ReadPropertyMultipleAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.read_access_results
+            .iter()
+            .try_for_each(|r| r.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.read_access_results.iter().map(|r| r.len()).sum()
+    }
+}
+
+fn decode_read_result(input: &[u8]) -> crate::Result<(ReadResult, &[u8])> {
+    let (property_identifier, rest) = decode_context(input, 2)?;
+    let (property_array_index, rest) = decode_optional_context(rest, 3)?;
+    let (result, rest) = match Tag::decode(rest)? {
+        (tag, _) if tag.is_opening(4) => {
+            let (value, rest) = decode_enclosed(rest, 4)?;
+            (Ok(PropertyValue::decode_slice(value)?), rest)
+        }
+        _ => {
+            let (error, rest) = decode_enclosed(rest, 5)?;
+            let error = BACnetError::try_from(error)?;
+            (Err(error), rest)
+        }
+    };
+    let result = ReadResult {
+        property_identifier: decode_enumerated(property_identifier)?,
+        property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+        result,
+    };
+    Ok((result, rest))
+}
+
+impl Decode for ReadPropertyMultipleAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut read_access_results = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (object_identifier, next) = decode_context(rest, 0)?;
+            let (mut list, next) = decode_enclosed(next, 1)?;
+            let mut results = Vec::new();
+            while !list.is_empty() {
+                let (result, l) = decode_read_result(list)?;
+                results.push(result);
+                list = l;
+            }
+            read_access_results.push(ReadAccessResult {
+                object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+                results,
+            });
+            rest = next;
+        }
+        Ok(Self {
+            read_access_results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{ErrorClass, ErrorCode};
+    use crate::encoding::{ApplicationValue, ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_read_property_multiple_present_value_and_units() {
+        // analog-input,1 and analog-value,2: present-value, units
+        let request = ReadPropertyMultiple::new(vec![
+            ReadAccessSpecification::new(
+                ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap(),
+                &[PropertyIdentifier::PresentValue, PropertyIdentifier::Units],
+            ),
+            ReadAccessSpecification::new(
+                ObjectIdentifier::new(ObjectType::AnalogValue, 2).unwrap(),
+                &[PropertyIdentifier::PresentValue, PropertyIdentifier::Units],
+            ),
+        ]);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(
+            data,
+            hex::decode("0c000000011e095509751f0c008000021e095509751f").unwrap()
+        );
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReadPropertyMultiple::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(9).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x09, 0x0e]);
+    }
+
+    #[test]
+    fn test_read_property_multiple_ack_with_error() {
+        let data = hex::decode(concat!(
+            "0c000000011e",
+            "29554e44429100004f", // present-value 72.5
+            "29754e913e4f",       // units degrees-celsius
+            "1f0c008000021e",
+            "29554e44412000004f", // present-value 10.0
+            "29755e910291205f",   // units: property, unknown-property
+            "1f"
+        ))
+        .unwrap();
+        let ack = ReadPropertyMultipleAck::decode_slice(&data).expect("Decode ACK");
+
+        assert_eq!(ack.read_access_results.len(), 2);
+        let first = &ack.read_access_results[0];
+        assert_eq!(
+            first.object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap()
+        );
+        assert_eq!(first.results[0].property_identifier, 85);
+        assert_eq!(
+            first.results[0].result,
+            Ok(ApplicationValue::Real(72.5).into())
+        );
+        assert_eq!(
+            first.results[1].result,
+            Ok(ApplicationValue::Enumerated(62).into())
+        );
+
+        let second = &ack.read_access_results[1];
+        assert_eq!(
+            second.object_identifier,
+            ObjectIdentifier::new(ObjectType::AnalogValue, 2).unwrap()
+        );
+        assert_eq!(
+            second.results[0].result,
+            Ok(ApplicationValue::Real(10.0).into())
+        );
+        assert_eq!(
+            second.results[1].result,
+            Err(BACnetError::new(
+                ErrorClass::Property,
+                ErrorCode::UnknownProperty
+            ))
+        );
+
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+    }
+
+    #[test]
+    fn test_decode_read_property_multiple_ack_truncated() {
+        let data = hex::decode("0c000000011e29554e4442910000").unwrap();
+        assert!(ReadPropertyMultipleAck::decode_slice(&data).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ApplicationValue, BitString, Date, ObjectIdentifier, PropertyValue, Tag, Time,
+    decode_application_value, decode_context, decode_enclosed, decode_enumerated,
+    decode_optional_context, decode_unsigned, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// Range of the items to read (15.8.1.1.4), a negative count reads the items before the
+/// reference
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Range {
+    /// Items from the item at `reference_index`, the first item having index 1
+    ByPosition { reference_index: u32, count: i32 },
+    /// Items from the item with sequence number `reference_sequence_number`
+    BySequenceNumber {
+        reference_sequence_number: u32,
+        count: i32,
+    },
+    /// Items from the first item timestamped after `reference_date` and `reference_time`
+    ByTime {
+        reference_date: Date,
+        reference_time: Time,
+        count: i32,
+    },
+}
+
+impl Range {
+    fn tag_number(&self) -> u8 {
+        match self {
+            Self::ByPosition { .. } => 3,
+            Self::BySequenceNumber { .. } => 6,
+            Self::ByTime { .. } => 7,
+        }
+    }
+
+    /// Reference and count, application tagged
+    fn values(&self) -> Vec<ApplicationValue> {
+        match *self {
+            Self::ByPosition {
+                reference_index,
+                count,
+            } => vec![
+                ApplicationValue::Unsigned(reference_index),
+                ApplicationValue::Signed(count),
+            ],
+            Self::BySequenceNumber {
+                reference_sequence_number,
+                count,
+            } => vec![
+                ApplicationValue::Unsigned(reference_sequence_number),
+                ApplicationValue::Signed(count),
+            ],
+            Self::ByTime {
+                reference_date,
+                reference_time,
+                count,
+            } => vec![
+                ApplicationValue::Date(reference_date),
+                ApplicationValue::Time(reference_time),
+                ApplicationValue::Signed(count),
+            ],
+        }
+    }
+}
+
+impl Encode for Range {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::opening(self.tag_number()).encode(writer)?;
+        for value in self.values() {
+            value.encode(writer)?;
+        }
+        Tag::closing(self.tag_number()).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        1 + self.values().iter().map(|v| v.len()).sum::<usize>() + 1
+    }
+}
+
+/// Decode the range at the start of `input`, returning it together with the remaining input
+fn decode_range(input: &[u8]) -> crate::Result<(Range, &[u8])> {
+    let (tag, _) = Tag::decode(input)?;
+    let tag_number = [3, 6, 7]
+        .into_iter()
+        .find(|t| tag.is_opening(*t))
+        .ok_or_else(|| Error::InvalidTag(format!("Expected range, found {:?}", tag)))?;
+    let (content, rest) = decode_enclosed(input, tag_number)?;
+
+    let mut values = Vec::new();
+    let mut content = content;
+    while !content.is_empty() {
+        let (value, next) = decode_application_value(content)?;
+        values.push(value);
+        content = next;
+    }
+    let range = match (tag_number, &values[..]) {
+        (
+            3,
+            [
+                ApplicationValue::Unsigned(reference_index),
+                ApplicationValue::Signed(count),
+            ],
+        ) => Range::ByPosition {
+            reference_index: *reference_index,
+            count: *count,
+        },
+        (
+            6,
+            [
+                ApplicationValue::Unsigned(reference_sequence_number),
+                ApplicationValue::Signed(count),
+            ],
+        ) => Range::BySequenceNumber {
+            reference_sequence_number: *reference_sequence_number,
+            count: *count,
+        },
+        (
+            7,
+            [
+                ApplicationValue::Date(reference_date),
+                ApplicationValue::Time(reference_time),
+                ApplicationValue::Signed(count),
+            ],
+        ) => Range::ByTime {
+            reference_date: *reference_date,
+            reference_time: *reference_time,
+            count: *count,
+        },
+        (t, v) => {
+            return Err(Error::InvalidValue(format!(
+                "Invalid range [{}]: {:?}",
+                t, v
+            )));
+        }
+    };
+    Ok((range, rest))
+}
+
+/// ReadRange-Request (15.8.1.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadRange {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    /// Items to read, all items when absent
+    pub range: Option<Range>,
+}
+
+impl ReadRange {
+    pub fn new(object_identifier: ObjectIdentifier, property_identifier: impl Into<u32>) -> Self {
+        Self {
+            object_identifier,
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+            range: None,
+        }
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+
+    pub fn range(mut self, range: Range) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+
+impl ConfirmedRequestService for ReadRange {
+    const SERVICE_CHOICE: u8 = 26;
+}
+
+impl Encode for ReadRange {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        if let Some(range) = &self.range {
+            range.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len(); // Property Identifier
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len(); // Property Array Index
+        }
+        if let Some(range) = &self.range {
+            l += range.len();
+        }
+        l
+    }
+}
+
+impl Decode for ReadRange {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (object_identifier, rest) = decode_context(&data, 0)?;
+        let (property_identifier, rest) = decode_context(rest, 1)?;
+        let (property_array_index, rest) = decode_optional_context(rest, 2)?;
+        let range = match rest {
+            [] => None,
+            rest => Some(decode_range(rest)?.0),
+        };
+        Ok(Self {
+            object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+            property_identifier: decode_enumerated(property_identifier)?,
+            property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+            range,
+        })
+    }
+}
+
+/// BACnetResultFlags (21) of a ReadRange-ACK
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResultFlags {
+    /// The first returned item is the first item of the list
+    pub first_item: bool,
+    /// The last returned item is the last item of the list
+    pub last_item: bool,
+    /// More items matched the range than could be returned
+    pub more_items: bool,
+}
+
+impl From<&BitString> for ResultFlags {
+    fn from(bits: &BitString) -> Self {
+        Self {
+            first_item: bits.get(0),
+            last_item: bits.get(1),
+            more_items: bits.get(2),
+        }
+    }
+}
+
+impl From<ResultFlags> for BitString {
+    fn from(flags: ResultFlags) -> Self {
+        BitString::new(vec![flags.first_item, flags.last_item, flags.more_items])
+    }
+}
+
+/// ReadRange-ACK (15.8.1.3)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadRangeAck {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub result_flags: ResultFlags,
+    pub item_count: u32,
+    /// The returned items, constructed items such as log records are kept as encoded octets
+    pub item_data: PropertyValue,
+    /// Sequence number of the first returned item, for reads by sequence number or time
+    pub first_sequence_number: Option<u32>,
+}
+
+impl Encode for ReadRangeAck {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        Tag::context(3, &BitString::from(self.result_flags).encode_vec()?).encode(writer)?;
+        Tag::context(4, &encode_unsigned(self.item_count)).encode(writer)?;
+        Tag::opening(5).encode(writer)?;
+        self.item_data.encode(writer)?;
+        Tag::closing(5).encode(writer)?;
+        if let Some(sequence_number) = self.first_sequence_number {
+            Tag::context(6, &encode_unsigned(sequence_number)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len(); // Property Identifier
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len(); // Property Array Index
+        }
+        l += 1 + BitString::from(self.result_flags).len(); // Result Flags
+        l += 1 + encode_unsigned(self.item_count).len(); // Item Count
+        l += 1 + self.item_data.len() + 1; // Item Data
+        if let Some(sequence_number) = self.first_sequence_number {
+            l += 1 + encode_unsigned(sequence_number).len(); // First Sequence Number
+        }
+        l
+    }
+}
+
+impl Decode for ReadRangeAck {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (object_identifier, rest) = decode_context(&data, 0)?;
+        let (property_identifier, rest) = decode_context(rest, 1)?;
+        let (property_array_index, rest) = decode_optional_context(rest, 2)?;
+        let (result_flags, rest) = decode_context(rest, 3)?;
+        let (item_count, rest) = decode_context(rest, 4)?;
+        let (item_data, rest) = decode_enclosed(rest, 5)?;
+        let (first_sequence_number, _) = decode_optional_context(rest, 6)?;
+        Ok(Self {
+            object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+            property_identifier: decode_enumerated(property_identifier)?,
+            property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+            result_flags: ResultFlags::from(&BitString::decode_slice(result_flags)?),
+            item_count: decode_unsigned(item_count)?,
+            item_data: PropertyValue::decode_slice(item_data)?,
+            first_sequence_number: first_sequence_number.map(decode_unsigned).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_read_range_by_position() {
+        // ReadRange trend-log,1 log-buffer, 10 items from index 1
+        let request = ReadRange::new(
+            ObjectIdentifier::new(ObjectType::TrendLog, 1).unwrap(),
+            PropertyIdentifier::LogBuffer,
+        )
+        .range(Range::ByPosition {
+            reference_index: 1,
+            count: 10,
+        });
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("0c0500000119833e2101310a3f").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReadRange::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(9).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x09, 0x1a]);
+    }
+
+    #[test]
+    fn test_read_range_by_sequence_number_and_time() {
+        let by_sequence_number = ReadRange::new(
+            ObjectIdentifier::new(ObjectType::TrendLog, 1).unwrap(),
+            PropertyIdentifier::LogBuffer,
+        )
+        .range(Range::BySequenceNumber {
+            reference_sequence_number: 1000,
+            count: -5,
+        });
+        let data = by_sequence_number.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("0c0500000119836e2203e831fb6f").unwrap());
+        assert_eq!(data.len(), by_sequence_number.len());
+        assert_eq!(ReadRange::decode_slice(&data).unwrap(), by_sequence_number);
+
+        let by_time = ReadRange::new(
+            ObjectIdentifier::new(ObjectType::TrendLog, 1).unwrap(),
+            PropertyIdentifier::LogBuffer,
+        )
+        .range(Range::ByTime {
+            reference_date: Date::new(2024, 3, 15, 5).unwrap(),
+            reference_time: Time::new(14, 0, 0, 0),
+            count: 20,
+        });
+        let data = by_time.encode_vec().unwrap();
+        assert_eq!(
+            data,
+            hex::decode("0c0500000119837ea47c030f05b40e00000031147f").unwrap()
+        );
+        assert_eq!(data.len(), by_time.len());
+        assert_eq!(ReadRange::decode_slice(&data).unwrap(), by_time);
+    }
+
+    #[test]
+    fn test_decode_read_range_ack_more_items() {
+        // ReadRange-ACK first-item and more-items, 2 items, first sequence number 79201
+        let data = hex::decode("0c0500000119833a05a049025e444291000044429200005f6b013561").unwrap();
+        let ack = ReadRangeAck::decode_slice(&data).expect("Decode ACK");
+
+        assert_eq!(
+            ack.result_flags,
+            ResultFlags {
+                first_item: true,
+                last_item: false,
+                more_items: true,
+            }
+        );
+        assert_eq!(ack.item_count, 2);
+        assert_eq!(
+            ack.item_data,
+            PropertyValue::Values(vec![
+                ApplicationValue::Real(72.5),
+                ApplicationValue::Real(73.0)
+            ])
+        );
+        assert_eq!(ack.first_sequence_number, Some(79201));
+        assert_eq!(ack.encode_vec().unwrap(), data);
+        assert_eq!(ack.len(), data.len());
+    }
+
+    #[test]
+    fn test_decode_read_range_invalid() {
+        // Range with context tag 4
+        let err = ReadRange::decode_slice(&hex::decode("0c0500000119834e2101310a4f").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+        // By position with an unsigned count
+        let err = ReadRange::decode_slice(&hex::decode("0c0500000119833e2101210a3f").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
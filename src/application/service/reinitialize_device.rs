@@ -0,0 +1,170 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    Tag, decode_character_string, decode_context, decode_enumerated, decode_optional_context,
+    encode_character_string, encode_enumerated,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Longest password of a ReinitializeDevice or DeviceCommunicationControl request in
+/// characters (16.4.1.1.2, 16.1.1.1.3)
+pub const MAX_PASSWORD_LENGTH: usize = 20;
+
+/// Reinitialized state of device (16.4.1.1.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ReinitializedState {
+    Coldstart,       // = 0;
+    Warmstart,       // = 1;
+    StartBackup,     // = 2;
+    EndBackup,       // = 3;
+    StartRestore,    // = 4;
+    EndRestore,      // = 5;
+    AbortRestore,    // = 6;
+    ActivateChanges, // = 7;
+}
+
+impl TryFrom<u32> for ReinitializedState {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Coldstart),
+            1 => Ok(Self::Warmstart),
+            2 => Ok(Self::StartBackup),
+            3 => Ok(Self::EndBackup),
+            4 => Ok(Self::StartRestore),
+            5 => Ok(Self::EndRestore),
+            6 => Ok(Self::AbortRestore),
+            7 => Ok(Self::ActivateChanges),
+            v => Err(Error::InvalidValue(format!(
+                "Reinitialized state not supported: {}",
+                v
+            ))),
+        }
+    }
+}
+
+impl From<ReinitializedState> for u32 {
+    fn from(value: ReinitializedState) -> Self {
+        match value {
+            ReinitializedState::Coldstart => 0,
+            ReinitializedState::Warmstart => 1,
+            ReinitializedState::StartBackup => 2,
+            ReinitializedState::EndBackup => 3,
+            ReinitializedState::StartRestore => 4,
+            ReinitializedState::EndRestore => 5,
+            ReinitializedState::AbortRestore => 6,
+            ReinitializedState::ActivateChanges => 7,
+        }
+    }
+}
+
+/// ReinitializeDevice-Request (16.4.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReinitializeDevice {
+    pub reinitialized_state: ReinitializedState,
+    pub password: Option<String>,
+}
+
+impl ReinitializeDevice {
+    pub fn new(reinitialized_state: ReinitializedState) -> Self {
+        Self {
+            reinitialized_state,
+            password: None,
+        }
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+}
+
+impl ConfirmedRequestService for ReinitializeDevice {
+    const SERVICE_CHOICE: u8 = 20;
+}
+
+impl Encode for ReinitializeDevice {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_enumerated(self.reinitialized_state.into())).encode(writer)?;
+        if let Some(password) = &self.password {
+            if password.chars().count() > MAX_PASSWORD_LENGTH {
+                return Err(Error::InvalidValue(format!(
+                    "Password longer than {} characters",
+                    MAX_PASSWORD_LENGTH
+                )));
+            }
+            Tag::context(1, &encode_character_string(password)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + encode_enumerated(self.reinitialized_state.into()).len();
+        if let Some(password) = &self.password {
+            l += Tag::context(1, &encode_character_string(password)).len();
+        }
+        l
+    }
+}
+
+impl Decode for ReinitializeDevice {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (reinitialized_state, rest) = decode_context(&data, 0)?;
+        let (password, _) = decode_optional_context(rest, 1)?;
+        Ok(Self {
+            reinitialized_state: ReinitializedState::try_from(decode_enumerated(
+                reinitialized_state,
+            )?)?,
+            password: password.map(decode_character_string).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmstart_with_password() {
+        let request = ReinitializeDevice::new(ReinitializedState::Warmstart).password("AbCdEfGh");
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("09011d09004162436445664768").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReinitializeDevice::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(3).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x03, 0x14]);
+    }
+
+    #[test]
+    fn test_coldstart_without_password() {
+        let request = ReinitializeDevice::new(ReinitializedState::Coldstart);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, vec![0x09, 0x00]);
+        assert_eq!(data.len(), request.len());
+        assert_eq!(ReinitializeDevice::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_reinitialize_device_invalid() {
+        let err = ReinitializeDevice::decode_slice(&[0x09, 0x08]).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+
+        let request = ReinitializeDevice::new(ReinitializedState::Warmstart)
+            .password("a password that is too long");
+        let err = request.encode_vec().unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
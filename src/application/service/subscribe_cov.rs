@@ -0,0 +1,147 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ObjectIdentifier, Tag, decode_boolean, decode_context, decode_optional_context,
+    decode_unsigned, encode_boolean, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// SubscribeCOV-Request (13.14.1.1), a request without confirmed notifications flag and
+/// lifetime cancels the subscription
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscribeCov {
+    pub subscriber_process_identifier: u32,
+    pub monitored_object_identifier: ObjectIdentifier,
+    pub issue_confirmed_notifications: Option<bool>,
+    /// Lifetime in seconds, 0 subscribes indefinitely
+    pub lifetime: Option<u32>,
+}
+
+impl SubscribeCov {
+    pub fn new(
+        subscriber_process_identifier: u32,
+        monitored_object_identifier: ObjectIdentifier,
+        issue_confirmed_notifications: bool,
+        lifetime: u32,
+    ) -> Self {
+        Self {
+            subscriber_process_identifier,
+            monitored_object_identifier,
+            issue_confirmed_notifications: Some(issue_confirmed_notifications),
+            lifetime: Some(lifetime),
+        }
+    }
+
+    /// Cancel the subscription of `subscriber_process_identifier` to the object
+    pub fn cancel(
+        subscriber_process_identifier: u32,
+        monitored_object_identifier: ObjectIdentifier,
+    ) -> Self {
+        Self {
+            subscriber_process_identifier,
+            monitored_object_identifier,
+            issue_confirmed_notifications: None,
+            lifetime: None,
+        }
+    }
+
+    pub fn is_cancellation(&self) -> bool {
+        self.issue_confirmed_notifications.is_none() && self.lifetime.is_none()
+    }
+}
+
+impl ConfirmedRequestService for SubscribeCov {
+    const SERVICE_CHOICE: u8 = 5;
+}
+
+impl Encode for SubscribeCov {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_unsigned(self.subscriber_process_identifier)).encode(writer)?;
+        Tag::context(1, &self.monitored_object_identifier.encode_vec()?).encode(writer)?;
+        if let Some(confirmed) = self.issue_confirmed_notifications {
+            Tag::context(2, &encode_boolean(confirmed)).encode(writer)?;
+        }
+        if let Some(lifetime) = self.lifetime {
+            Tag::context(3, &encode_unsigned(lifetime)).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + encode_unsigned(self.subscriber_process_identifier).len();
+        l += 1 + 4; // Monitored Object Identifier
+        if self.issue_confirmed_notifications.is_some() {
+            l += 2;
+        }
+        if let Some(lifetime) = self.lifetime {
+            l += 1 + encode_unsigned(lifetime).len();
+        }
+        l
+    }
+}
+
+impl Decode for SubscribeCov {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (subscriber_process_identifier, rest) = decode_context(&data, 0)?;
+        let (monitored_object_identifier, rest) = decode_context(rest, 1)?;
+        let (issue_confirmed_notifications, rest) = match Tag::decode(rest) {
+            Ok((tag, rest)) if tag.is_context(2) => (Some(decode_boolean(&tag)?), rest),
+            _ => (None, rest),
+        };
+        let (lifetime, _) = decode_optional_context(rest, 3)?;
+        Ok(Self {
+            subscriber_process_identifier: decode_unsigned(subscriber_process_identifier)?,
+            monitored_object_identifier: ObjectIdentifier::decode_slice(
+                monitored_object_identifier,
+            )?,
+            issue_confirmed_notifications,
+            lifetime: lifetime.map(decode_unsigned).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_subscribe_cov_confirmed_300_seconds() {
+        let request = SubscribeCov::new(
+            18,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 10).unwrap(),
+            true,
+            300,
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("09121c0000000a29013a012c").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(SubscribeCov::decode_slice(&data).unwrap(), request);
+        assert!(!request.is_cancellation());
+
+        let apdu = request.confirmed_request(15).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x0f, 0x05]);
+    }
+
+    #[test]
+    fn test_subscribe_cov_cancel() {
+        let request = SubscribeCov::cancel(
+            18,
+            ObjectIdentifier::new(ObjectType::AnalogInput, 10).unwrap(),
+        );
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("09121c0000000a").unwrap());
+        assert_eq!(data.len(), request.len());
+        let decoded = SubscribeCov::decode_slice(&data).unwrap();
+        assert_eq!(decoded, request);
+        assert!(decoded.is_cancellation());
+    }
+}
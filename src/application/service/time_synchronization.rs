@@ -0,0 +1,97 @@
+use crate::encoding::{ApplicationValue, Date, Time, decode_application_value};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// TimeSynchronization-Request (16.7), also the parameters of UTCTimeSynchronization-Request
+/// (16.8) whose date and time are in UTC
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TimeSynchronization {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl TimeSynchronization {
+    /// Unconfirmed service choice (21)
+    pub const SERVICE_CHOICE: u8 = 6;
+
+    /// Unconfirmed service choice of UTCTimeSynchronization (21)
+    pub const UTC_SERVICE_CHOICE: u8 = 9;
+
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+}
+
+impl Encode for TimeSynchronization {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        ApplicationValue::Date(self.date).encode(writer)?;
+        ApplicationValue::Time(self.time).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        ApplicationValue::Date(self.date).len() + ApplicationValue::Time(self.time).len()
+    }
+}
+
+impl Decode for TimeSynchronization {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (date, rest) = decode_application_value(&data)?;
+        let (time, _) = decode_application_value(rest)?;
+        match (date, time) {
+            (ApplicationValue::Date(date), ApplicationValue::Time(time)) => {
+                Ok(Self::new(date, time))
+            }
+            v => Err(Error::InvalidValue(format!(
+                "Invalid TimeSynchronization parameters: {:?}",
+                v
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::UnconfirmedService;
+
+    #[test]
+    fn test_time_synchronization_round_trip() {
+        let request =
+            TimeSynchronization::new(Date::new(2024, 3, 15, 5).unwrap(), Time::new(14, 30, 5, 25));
+
+        let data = request
+            .encode_vec()
+            .expect("Write TimeSynchronization to buffer");
+        assert_eq!(data, hex::decode("a47c030f05b40e1e0519").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(TimeSynchronization::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_decode_utc_time_synchronization_service() {
+        let data = hex::decode("09a47c030f05b40e1e0519").unwrap();
+        let service = UnconfirmedService::decode_slice(&data).unwrap();
+
+        let request =
+            TimeSynchronization::new(Date::new(2024, 3, 15, 5).unwrap(), Time::new(14, 30, 5, 25));
+        assert_eq!(service, UnconfirmedService::UtcTimeSynchronization(request));
+        let mut encoded = vec![TimeSynchronization::UTC_SERVICE_CHOICE];
+        encoded.extend(service.encode_vec().unwrap());
+        assert_eq!(encoded, data);
+    }
+
+    #[test]
+    fn test_decode_time_synchronization_invalid() {
+        // Time before Date
+        let data = hex::decode("b40e1e0519a47c030f05").unwrap();
+        let err = TimeSynchronization::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = TimeSynchronization::decode_slice(&data[..7]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+}
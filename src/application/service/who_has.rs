@@ -0,0 +1,229 @@
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, Tag, decode_application_value, decode_character_string,
+    decode_context, decode_optional_context, decode_unsigned, encode_character_string,
+    encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Object searched for by Who-Has
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WhoHasObject {
+    Identifier(ObjectIdentifier),
+    Name(String),
+}
+
+/// Who-Has-Request (16.9.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WhoHas {
+    /// Low and high limit of the device instances that should respond
+    pub range: Option<(u32, u32)>,
+    pub object: WhoHasObject,
+}
+
+impl WhoHas {
+    /// Unconfirmed service choice (21)
+    pub const SERVICE_CHOICE: u8 = 7;
+
+    pub fn identifier(object_identifier: ObjectIdentifier) -> Self {
+        Self {
+            range: None,
+            object: WhoHasObject::Identifier(object_identifier),
+        }
+    }
+
+    pub fn name(object_name: &str) -> Self {
+        Self {
+            range: None,
+            object: WhoHasObject::Name(object_name.to_string()),
+        }
+    }
+
+    pub fn range(mut self, low: u32, high: u32) -> Self {
+        self.range = Some((low, high));
+        self
+    }
+
+    fn object_data(&self) -> crate::Result<(u8, Vec<u8>)> {
+        match &self.object {
+            WhoHasObject::Identifier(o) => Ok((2, o.encode_vec()?)),
+            WhoHasObject::Name(n) => Ok((3, encode_character_string(n))),
+        }
+    }
+}
+
+impl Encode for WhoHas {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        if let Some((low, high)) = self.range {
+            Tag::context(0, &encode_unsigned(low)).encode(writer)?;
+            Tag::context(1, &encode_unsigned(high)).encode(writer)?;
+        }
+        let (tag, data) = self.object_data()?;
+        Tag::context(tag, &data).encode(writer)
+    }
+
+    fn len(&self) -> usize {
+        let mut l = match self.range {
+            Some((low, high)) => 2 + encode_unsigned(low).len() + encode_unsigned(high).len(),
+            None => 0,
+        };
+        l += match &self.object {
+            WhoHasObject::Identifier(_) => 1 + 4,
+            WhoHasObject::Name(n) => Tag::context(3, &encode_character_string(n)).len(),
+        };
+        l
+    }
+}
+
+impl Decode for WhoHas {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (low, rest) = decode_optional_context(&data, 0)?;
+        let (range, rest) = match low {
+            Some(low) => {
+                let (high, rest) = decode_context(rest, 1)?;
+                (Some((decode_unsigned(low)?, decode_unsigned(high)?)), rest)
+            }
+            None => (None, rest),
+        };
+        let object = match decode_optional_context(rest, 2)? {
+            (Some(o), _) => WhoHasObject::Identifier(ObjectIdentifier::decode_slice(o)?),
+            (None, rest) => {
+                let (name, _) = decode_context(rest, 3)?;
+                WhoHasObject::Name(decode_character_string(name)?)
+            }
+        };
+        Ok(Self { range, object })
+    }
+}
+
+/// I-Have-Request (16.9.2)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IHave {
+    pub device_identifier: ObjectIdentifier,
+    pub object_identifier: ObjectIdentifier,
+    pub object_name: String,
+}
+
+impl IHave {
+    /// Unconfirmed service choice (21)
+    pub const SERVICE_CHOICE: u8 = 1;
+
+    pub fn new(
+        device_identifier: ObjectIdentifier,
+        object_identifier: ObjectIdentifier,
+        object_name: &str,
+    ) -> Self {
+        Self {
+            device_identifier,
+            object_identifier,
+            object_name: object_name.to_string(),
+        }
+    }
+
+    fn values(&self) -> [ApplicationValue; 3] {
+        [
+            ApplicationValue::ObjectId(self.device_identifier),
+            ApplicationValue::ObjectId(self.object_identifier),
+            ApplicationValue::CharacterString(self.object_name.clone()),
+        ]
+    }
+}
+
+impl Encode for IHave {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.values().iter().try_for_each(|v| v.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.values().iter().map(|v| v.len()).sum()
+    }
+}
+
+impl Decode for IHave {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (device_identifier, rest) = decode_application_value(&data)?;
+        let (object_identifier, rest) = decode_application_value(rest)?;
+        let (object_name, _) = decode_application_value(rest)?;
+        match (device_identifier, object_identifier, object_name) {
+            (
+                ApplicationValue::ObjectId(device_identifier),
+                ApplicationValue::ObjectId(object_identifier),
+                ApplicationValue::CharacterString(object_name),
+            ) => Ok(Self {
+                device_identifier,
+                object_identifier,
+                object_name,
+            }),
+            v => Err(Error::InvalidValue(format!(
+                "Invalid I-Have parameters: {:?}",
+                v
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::UnconfirmedService;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_who_has_name() {
+        let who_has = WhoHas::name("AHU-1");
+
+        let data = who_has.encode_vec().expect("Write Who-Has to buffer");
+        assert_eq!(data, hex::decode("3d06004148552d31").unwrap());
+        assert_eq!(data.len(), who_has.len());
+        assert_eq!(WhoHas::decode_slice(&data).unwrap(), who_has);
+    }
+
+    #[test]
+    fn test_who_has_identifier_in_range() {
+        let who_has =
+            WhoHas::identifier(ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap())
+                .range(1000, 1999);
+
+        let data = who_has.encode_vec().expect("Write Who-Has to buffer");
+        assert_eq!(data, hex::decode("0a03e81a07cf2c00000001").unwrap());
+        assert_eq!(data.len(), who_has.len());
+        assert_eq!(WhoHas::decode_slice(&data).unwrap(), who_has);
+    }
+
+    #[test]
+    fn test_i_have() {
+        let i_have = IHave::new(
+            ObjectIdentifier::new(ObjectType::Device, 260001).unwrap(),
+            ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+            "AHU-1",
+        );
+
+        let data = i_have.encode_vec().expect("Write I-Have to buffer");
+        assert_eq!(
+            data,
+            hex::decode("c40203f7a1c4008000017506004148552d31").unwrap()
+        );
+        assert_eq!(data.len(), i_have.len());
+
+        let service = UnconfirmedService::decode_slice(&[&[0x01], &data[..]].concat()).unwrap();
+        assert_eq!(service, UnconfirmedService::IHave(i_have));
+    }
+
+    #[test]
+    fn test_decode_who_has_without_object() {
+        assert!(WhoHas::decode_slice(&hex::decode("0a03e81a07cf").unwrap()).is_err());
+    }
+}
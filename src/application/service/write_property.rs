@@ -0,0 +1,294 @@
+use crate::application::ConfirmedRequestService;
+use crate::encoding::{
+    ApplicationValue, ObjectIdentifier, PropertyIdentifier, PropertyValue, Tag, decode_context,
+    decode_enclosed, decode_enumerated, decode_optional_context, decode_unsigned,
+    encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// WriteProperty-Request (15.9.1.1)
+#[derive(Clone, Debug, PartialEq)]
+pub struct WriteProperty {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub property_value: PropertyValue,
+    /// Priority 1..=16, writing a Null relinquishes the value at this priority
+    pub priority: Option<u8>,
+}
+
+impl WriteProperty {
+    pub fn new(
+        object_identifier: ObjectIdentifier,
+        property_identifier: impl Into<u32>,
+        value: impl Into<PropertyValue>,
+    ) -> Self {
+        Self {
+            object_identifier,
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+            property_value: value.into(),
+            priority: None,
+        }
+    }
+
+    /// Relinquish the value written at `priority`, which must be within 1..=16
+    pub fn relinquish(
+        object_identifier: ObjectIdentifier,
+        property_identifier: impl Into<u32>,
+        priority: u8,
+    ) -> crate::Result<Self> {
+        Self::new(
+            object_identifier,
+            property_identifier,
+            ApplicationValue::Null,
+        )
+        .priority(priority)
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+
+    /// Write at `priority`, which must be within 1..=16
+    pub fn priority(mut self, priority: u8) -> crate::Result<Self> {
+        self.priority = Some(check_priority(priority)?);
+        Ok(self)
+    }
+    /// Check a write of the present-value of a multi-state object is a state within
+    /// 1..=`number_of_states` (12.18.4), any write passes if the number of states is not known
+    pub fn check_state(&self, number_of_states: Option<u32>) -> crate::Result<()> {
+        let Some(number_of_states) = number_of_states else {
+            return Ok(());
+        };
+        if self.property_identifier != u32::from(PropertyIdentifier::PresentValue) {
+            return Ok(());
+        }
+        match self.property_value.single() {
+            Some(ApplicationValue::Unsigned(state)) if !(1..=number_of_states).contains(state) => {
+                Err(Error::InvalidValue(format!(
+                    "State {} outside 1..={}",
+                    state, number_of_states
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Check a write priority is within 1..=16
+pub(crate) fn check_priority(priority: u8) -> crate::Result<u8> {
+    match priority {
+        1..=16 => Ok(priority),
+        p => Err(Error::InvalidPriority(p)),
+    }
+}
+
+/// Decode a write priority, rejecting values outside 1..=16, priorities above 255 are reported
+/// as 255
+pub(crate) fn decode_priority(data: &[u8]) -> crate::Result<u8> {
+    let priority = decode_unsigned(data)?;
+    u8::try_from(priority)
+        .map_err(|_| Error::InvalidPriority(u8::MAX))
+        .and_then(check_priority)
+}
+
+/// Encode an enclosed property value and its optional write priority, shared by WriteProperty
+/// and WritePropertyMultiple
+pub(crate) fn encode_value_and_priority<T: crate::io::Write + Sized>(
+    writer: &mut T,
+    value_tag: u8,
+    value: &PropertyValue,
+    priority_tag: u8,
+    priority: Option<u8>,
+) -> crate::Result<()> {
+    Tag::opening(value_tag).encode(writer)?;
+    value.encode(writer)?;
+    Tag::closing(value_tag).encode(writer)?;
+    if let Some(priority) = priority {
+        Tag::context(priority_tag, &[check_priority(priority)?]).encode(writer)?;
+    }
+    Ok(())
+}
+
+/// Length of `encode_value_and_priority`
+pub(crate) fn value_and_priority_len(value: &PropertyValue, priority: Option<u8>) -> usize {
+    1 + value.len() + 1 + priority.map_or(0, |_| 2)
+}
+
+impl ConfirmedRequestService for WriteProperty {
+    const SERVICE_CHOICE: u8 = 15;
+}
+
+impl Encode for WriteProperty {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        encode_value_and_priority(writer, 3, &self.property_value, 4, self.priority)
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len(); // Property Identifier
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len(); // Property Array Index
+        }
+        l += value_and_priority_len(&self.property_value, self.priority); // Value, Priority
+        l
+    }
+}
+
+impl Decode for WriteProperty {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let (object_identifier, rest) = decode_context(&data, 0)?;
+        let (property_identifier, rest) = decode_context(rest, 1)?;
+        let (property_array_index, rest) = decode_optional_context(rest, 2)?;
+        let (property_value, rest) = decode_enclosed(rest, 3)?;
+        let (priority, _) = decode_optional_context(rest, 4)?;
+        Ok(Self {
+            object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+            property_identifier: decode_enumerated(property_identifier)?,
+            property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+            property_value: PropertyValue::decode_slice(property_value)?,
+            priority: priority.map(decode_priority).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_write_property_real_priority_8() {
+        // WriteProperty analog-value,1 present-value 72.5 priority 8
+        let request = WriteProperty::new(
+            ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+            PropertyIdentifier::PresentValue,
+            ApplicationValue::Real(72.5),
+        )
+        .priority(8)
+        .unwrap();
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(
+            data,
+            hex::decode("0c0080000119553e44429100003f4908").unwrap()
+        );
+        assert_eq!(data.len(), request.len());
+        assert_eq!(WriteProperty::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(3).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x03, 0x0f]);
+    }
+
+    #[test]
+    fn test_write_property_relinquish() {
+        let request = WriteProperty::relinquish(
+            ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+            PropertyIdentifier::PresentValue,
+            8,
+        )
+        .unwrap();
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data, hex::decode("0c0080000119553e003f4908").unwrap());
+        assert_eq!(data.len(), request.len());
+        assert_eq!(WriteProperty::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_write_property_array_index_without_priority() {
+        let request = WriteProperty::new(
+            ObjectIdentifier::new(ObjectType::Device, 260001).unwrap(),
+            PropertyIdentifier::ObjectName,
+            ApplicationValue::CharacterString("AHU-1".to_string()),
+        )
+        .array_index(0);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        assert_eq!(data.len(), request.len());
+        assert_eq!(WriteProperty::decode_slice(&data).unwrap(), request);
+    }
+
+    #[test]
+    fn test_write_property_invalid_priority() {
+        let object = ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap();
+        let write = |priority| {
+            WriteProperty::new(
+                object,
+                PropertyIdentifier::PresentValue,
+                ApplicationValue::Real(72.5),
+            )
+            .priority(priority)
+        };
+        let err = write(0).unwrap_err();
+        assert!(matches!(err, Error::InvalidPriority(0)), "{:?}", err);
+        assert_eq!(write(16).unwrap().priority, Some(16));
+        let err = write(17).unwrap_err();
+        assert!(matches!(err, Error::InvalidPriority(17)), "{:?}", err);
+        let err =
+            WriteProperty::relinquish(object, PropertyIdentifier::PresentValue, 0).unwrap_err();
+        assert!(matches!(err, Error::InvalidPriority(_)), "{:?}", err);
+
+        // Priorities set directly on the field are still checked when encoding
+        let mut request = write(16).unwrap();
+        request.priority = Some(17);
+        let err = request.encode_vec().unwrap_err();
+        assert!(matches!(err, Error::InvalidPriority(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_write_property_invalid_priority() {
+        // Priority 0, 17 and 256
+        for priority in ["4900", "4911", "4a0100"] {
+            let data = hex::decode(format!("0c0080000119553e44429100003f{}", priority)).unwrap();
+            let err = WriteProperty::decode_slice(&data).unwrap_err();
+            assert!(matches!(err, Error::InvalidPriority(_)), "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_write_multistate_state_checked() {
+        // number-of-states 3 and feedback-value 2 of a multi-state output
+        let number_of_states = PropertyValue::decode_slice(&[0x21, 0x03]).unwrap();
+        let feedback_value = PropertyValue::decode_slice(&[0x21, 0x02]).unwrap();
+        let number_of_states = u32::try_from(number_of_states.into_single().unwrap()).unwrap();
+        let feedback_value = u32::try_from(feedback_value.into_single().unwrap()).unwrap();
+        assert_eq!((number_of_states, feedback_value), (3, 2));
+
+        let object_identifier = ObjectIdentifier::new(ObjectType::MultiStateOutput, 1).unwrap();
+        let write = |property: PropertyIdentifier, state: u32| {
+            WriteProperty::new(
+                object_identifier,
+                property,
+                ApplicationValue::Unsigned(state),
+            )
+        };
+        let present_value = PropertyIdentifier::PresentValue;
+        let err = write(present_value, 4)
+            .check_state(Some(number_of_states))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        assert!(write(present_value, 0).check_state(Some(3)).is_err());
+        assert!(write(present_value, 3).check_state(Some(3)).is_ok());
+        assert!(write(present_value, 4).check_state(None).is_ok());
+        assert!(
+            write(PropertyIdentifier::Priority, 4)
+                .check_state(Some(3))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::application::ConfirmedRequestService;
+use crate::application::service::write_property::{
+    check_priority, decode_priority, encode_value_and_priority, value_and_priority_len,
+};
+use crate::encoding::{
+    ObjectIdentifier, PropertyValue, Tag, decode_context, decode_enclosed, decode_enumerated,
+    decode_optional_context, decode_unsigned, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// BACnetPropertyValue (21), a property to write with its value and optional priority
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyWrite {
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub value: PropertyValue,
+    pub priority: Option<u8>,
+}
+
+impl PropertyWrite {
+    pub fn new(property_identifier: impl Into<u32>, value: impl Into<PropertyValue>) -> Self {
+        Self {
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+            value: value.into(),
+            priority: None,
+        }
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+
+    /// Write at `priority`, which must be within 1..=16
+    pub fn priority(mut self, priority: u8) -> crate::Result<Self> {
+        self.priority = Some(check_priority(priority)?);
+        Ok(self)
+    }
+}
+
+impl Encode for PropertyWrite {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(1, &encode_unsigned(index)).encode(writer)?;
+        }
+        encode_value_and_priority(writer, 2, &self.value, 3, self.priority)
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 1 + encode_enumerated(self.property_identifier).len();
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len();
+        }
+        l += value_and_priority_len(&self.value, self.priority);
+        l
+    }
+}
+
+/// Decode the BACnetPropertyValue at the start of `input`, returning it together with the
+/// remaining input
+pub(crate) fn decode_property_write(input: &[u8]) -> crate::Result<(PropertyWrite, &[u8])> {
+    let (property_identifier, rest) = decode_context(input, 0)?;
+    let (property_array_index, rest) = decode_optional_context(rest, 1)?;
+    let (value, rest) = decode_enclosed(rest, 2)?;
+    let (priority, rest) = decode_optional_context(rest, 3)?;
+    let property = PropertyWrite {
+        property_identifier: decode_enumerated(property_identifier)?,
+        property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+        value: PropertyValue::decode_slice(value)?,
+        priority: priority.map(decode_priority).transpose()?,
+    };
+    Ok((property, rest))
+}
+
+/// WriteAccessSpecification (21)
+#[derive(Clone, Debug, PartialEq)]
+pub struct WriteAccessSpecification {
+    pub object_identifier: ObjectIdentifier,
+    pub properties: Vec<PropertyWrite>,
+}
+
+impl WriteAccessSpecification {
+    pub fn new(object_identifier: ObjectIdentifier, properties: Vec<PropertyWrite>) -> Self {
+        Self {
+            object_identifier,
+            properties,
+        }
+    }
+}
+
+impl Encode for WriteAccessSpecification {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::opening(1).encode(writer)?;
+        for property in &self.properties {
+            property.encode(writer)?;
+        }
+        Tag::closing(1).encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let properties: usize = self.properties.iter().map(|p| p.len()).sum();
+        1 + 4 + 1 + properties + 1
+    }
+}
+
+/// WritePropertyMultiple-Request (15.10.1.1)
+#[derive(Clone, Debug, PartialEq)]
+pub struct WritePropertyMultiple {
+    pub write_access_specifications: Vec<WriteAccessSpecification>,
+}
+
+impl WritePropertyMultiple {
+    pub fn new(write_access_specifications: Vec<WriteAccessSpecification>) -> Self {
+        Self {
+            write_access_specifications,
+        }
+    }
+}
+
+impl ConfirmedRequestService for WritePropertyMultiple {
+    const SERVICE_CHOICE: u8 = 16;
+}
+
+impl Encode for WritePropertyMultiple {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        self.write_access_specifications
+            .iter()
+            .try_for_each(|s| s.encode(writer))
+    }
+
+    fn len(&self) -> usize {
+        self.write_access_specifications
+            .iter()
+            .map(|s| s.len())
+            .sum()
+    }
+}
+
+impl Decode for WritePropertyMultiple {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut write_access_specifications = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (object_identifier, next) = decode_context(rest, 0)?;
+            let (mut list, next) = decode_enclosed(next, 1)?;
+            let mut properties = Vec::new();
+            while !list.is_empty() {
+                let (property, l) = decode_property_write(list)?;
+                properties.push(property);
+                list = l;
+            }
+            write_access_specifications.push(WriteAccessSpecification {
+                object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+                properties,
+            });
+            rest = next;
+        }
+        Ok(Self::new(write_access_specifications))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationValue, ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_write_property_multiple() {
+        let request = WritePropertyMultiple::new(vec![
+            WriteAccessSpecification::new(
+                ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+                vec![
+                    PropertyWrite::new(
+                        PropertyIdentifier::PresentValue,
+                        ApplicationValue::Real(72.5),
+                    )
+                    .priority(8)
+                    .unwrap(),
+                    PropertyWrite::new(
+                        PropertyIdentifier::ObjectName,
+                        ApplicationValue::CharacterString("AHU-1".to_string()),
+                    ),
+                ],
+            ),
+            WriteAccessSpecification::new(
+                ObjectIdentifier::new(ObjectType::BinaryValue, 3).unwrap(),
+                vec![
+                    PropertyWrite::new(
+                        PropertyIdentifier::PresentValue,
+                        ApplicationValue::Enumerated(1),
+                    )
+                    .priority(8)
+                    .unwrap(),
+                ],
+            ),
+        ]);
+
+        let data = request.encode_vec().expect("Write request to buffer");
+        let capture = hex::decode(concat!(
+            "0c008000011e",
+            "09552e44429100002f3908",
+            "094d2e7506004148552d312f",
+            "1f0c014000031e",
+            "09552e91012f3908",
+            "1f"
+        ))
+        .unwrap();
+        assert_eq!(data, capture);
+        assert_eq!(data.len(), request.len());
+        assert_eq!(WritePropertyMultiple::decode_slice(&data).unwrap(), request);
+
+        let apdu = request.confirmed_request(4).unwrap().encode_vec().unwrap();
+        assert_eq!(&apdu[..4], &[0x00, 0x05, 0x04, 0x10]);
+    }
+}
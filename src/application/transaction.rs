@@ -0,0 +1,490 @@
+use crate::application::{APDU, BACnetPDU, MORE_FOLLOWS, SEGMENTED_MESSAGE, SegmentAck};
+
+use std::collections::HashMap;
+use std::net::SocketAddrV4;
+use std::time::{Duration, Instant};
+
+/// Default APDU_Timeout of the Device object (12.11.27)
+pub const DEFAULT_APDU_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Default Number_Of_APDU_Retries of the Device object (12.11.28)
+pub const DEFAULT_APDU_RETRIES: u8 = 3;
+
+/// Outcome of an expired transaction
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Expiry {
+    /// The request should be sent again, the timer has been restarted
+    Retry,
+    /// All retries are used up, the transaction has been removed
+    TimedOut,
+}
+
+#[derive(Clone, Debug)]
+struct Transaction {
+    address: SocketAddrV4,
+    deadline: Instant,
+    retries: u8,
+    /// Sequence number of the next segment of a segmented ComplexACK
+    next_sequence: u8,
+    /// Window size of the segmented ComplexACK, proposed by the server in its first segment
+    window_size: u8,
+    /// Sequence number of the segment acknowledged last
+    acknowledged: u8,
+    /// Service data of the segments received so far
+    segments: Vec<u8>,
+    /// Segments of the request, sent a window at a time
+    request: Vec<APDU>,
+    /// Index of the first request segment not acknowledged by the server
+    unacknowledged: usize,
+    /// Window size of the segmented request, set by the server's Segment-ACKs
+    request_window_size: u8,
+}
+
+impl Transaction {
+    /// Segments of the request from the first unacknowledged one, at most `window_size`
+    fn window(&self, window_size: u8) -> &[APDU] {
+        let end = (self.unacknowledged + window_size as usize).min(self.request.len());
+        &self.request[self.unacknowledged..end]
+    }
+
+    /// Segment-ACK of the ComplexACK segments received up to `sequence_number`
+    fn segment_ack(&self, invoke_id: u8, negative: bool, sequence_number: u8) -> APDU {
+        APDU::from(SegmentAck {
+            negative,
+            server: false,
+            invoke_id,
+            sequence_number,
+            actual_window_size: self.window_size,
+        })
+    }
+}
+
+/// Tracks the confirmed requests awaiting a reply (5.4.4), keyed by invoke ID
+#[derive(Clone, Debug)]
+pub struct TransactionManager {
+    timeout: Duration,
+    retries: u8,
+    next_invoke_id: u8,
+    pending: HashMap<u8, Transaction>,
+    /// APDUs to send, such as Segment-ACKs of received segments
+    outgoing: Vec<(SocketAddrV4, APDU)>,
+}
+
+impl Default for TransactionManager {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_APDU_TIMEOUT,
+            retries: DEFAULT_APDU_RETRIES,
+            next_invoke_id: 0,
+            pending: HashMap::new(),
+            outgoing: Vec::new(),
+        }
+    }
+}
+
+impl TransactionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time to wait for a reply before retrying, defaults to DEFAULT_APDU_TIMEOUT
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of times a request is sent again before giving up, defaults to DEFAULT_APDU_RETRIES
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Start a transaction with the device at `address`, returning its invoke ID
+    pub fn begin(&mut self, address: SocketAddrV4, now: Instant) -> std::io::Result<u8> {
+        let invoke_id = (0..=u8::MAX)
+            .map(|i| self.next_invoke_id.wrapping_add(i))
+            .find(|id| !self.pending.contains_key(id))
+            .ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::WouldBlock, "All invoke IDs are in use")
+            })?;
+        self.next_invoke_id = invoke_id.wrapping_add(1);
+        self.pending.insert(
+            invoke_id,
+            Transaction {
+                address,
+                deadline: now + self.timeout,
+                retries: self.retries,
+                next_sequence: 0,
+                window_size: 1,
+                acknowledged: 0,
+                segments: Vec::new(),
+                request: Vec::new(),
+                unacknowledged: 0,
+                request_window_size: 1,
+            },
+        );
+        Ok(invoke_id)
+    }
+
+    /// Send the request of the transaction with `invoke_id`, split into `segments`. The segments
+    /// are queued for `take_outgoing` a window at a time (5.4.4.1): the first segment alone,
+    /// each following window once the server acknowledged the previous one
+    pub fn send(&mut self, invoke_id: u8, segments: Vec<APDU>) {
+        let Some(transaction) = self.pending.get_mut(&invoke_id) else {
+            return;
+        };
+        transaction.request = segments;
+        transaction.unacknowledged = 0;
+        for segment in transaction.window(1) {
+            self.outgoing.push((transaction.address, segment.clone()));
+        }
+    }
+
+    /// Whether a transaction with `invoke_id` awaits a reply
+    pub fn is_pending(&self, invoke_id: u8) -> bool {
+        self.pending.contains_key(&invoke_id)
+    }
+
+    /// Time by which a reply to `invoke_id` is expected
+    pub fn deadline(&self, invoke_id: u8) -> Option<Instant> {
+        self.pending.get(&invoke_id).map(|t| t.deadline)
+    }
+
+    /// Pass an APDU received from `source`, a reply completing a transaction is returned.
+    /// A Segment-ACK restarts the timer of its transaction and queues the next window of the
+    /// request, from the segment after the acknowledged one. APDUs that match no transaction
+    /// are discarded. The segments of a segmented ComplexACK each restart the timer, the last
+    /// one completes the transaction with a ComplexACK carrying the reassembled service data.
+    /// The first segment, every full window and the last segment are acknowledged, a segment
+    /// out of order is discarded and answered with a negative Segment-ACK (5.4.4.2), both
+    /// queued for `take_outgoing`.
+    pub fn receive(&mut self, source: SocketAddrV4, apdu: APDU, now: Instant) -> Option<APDU> {
+        let invoke_id = match apdu.pdu_type()? {
+            BACnetPDU::ConfirmedRequest | BACnetPDU::UnconfirmedRequest => return None,
+            _ => apdu.invoke_id()?,
+        };
+        let transaction = self.pending.get_mut(&invoke_id)?;
+        if transaction.address != source {
+            return None;
+        }
+        if apdu.pdu_type() == Some(BACnetPDU::SegmentACK) {
+            transaction.deadline = now + self.timeout;
+            let ack = match SegmentAck::try_from(&apdu) {
+                Ok(ack) if ack.server => ack,
+                _ => return None,
+            };
+            let next = ack.sequence_number as usize + 1;
+            if next < transaction.unacknowledged || next > transaction.request.len() {
+                return None;
+            }
+            transaction.unacknowledged = next;
+            transaction.request_window_size = ack.actual_window_size.clamp(1, 127);
+            for segment in transaction.window(transaction.request_window_size) {
+                self.outgoing.push((source, segment.clone()));
+            }
+            return None;
+        }
+        if apdu.pdu_type() == Some(BACnetPDU::ComplexACK) && apdu.flags & SEGMENTED_MESSAGE != 0 {
+            let (sequence, proposed_window_size) = match apdu.pci[..] {
+                [_, sequence, window_size] => (sequence, window_size),
+                _ => return None,
+            };
+            if sequence != transaction.next_sequence {
+                if transaction.next_sequence != 0 {
+                    let last = transaction.next_sequence.wrapping_sub(1);
+                    let nak = transaction.segment_ack(invoke_id, true, last);
+                    self.outgoing.push((source, nak));
+                }
+                return None;
+            }
+            if sequence == 0 {
+                transaction.window_size = proposed_window_size.clamp(1, 127);
+            }
+            transaction.next_sequence = transaction.next_sequence.wrapping_add(1);
+            transaction.segments.extend_from_slice(&apdu.user_data);
+            transaction.deadline = now + self.timeout;
+            let more_follows = apdu.flags & MORE_FOLLOWS != 0;
+            if sequence == 0
+                || !more_follows
+                || sequence.wrapping_sub(transaction.acknowledged) >= transaction.window_size
+            {
+                transaction.acknowledged = sequence;
+                let ack = transaction.segment_ack(invoke_id, false, sequence);
+                self.outgoing.push((source, ack));
+            }
+            if more_follows {
+                return None;
+            }
+            let transaction = self.pending.remove(&invoke_id)?;
+            return Some(APDU {
+                flags: 0,
+                pci: vec![invoke_id],
+                user_data: transaction.segments,
+                ..apdu
+            });
+        }
+        self.pending.remove(&invoke_id);
+        Some(apdu)
+    }
+
+    /// Handle the deadline of `invoke_id` passing, None if it did not pass yet. On a retry the
+    /// unacknowledged window of a request given to `send` is queued again, or its first
+    /// segment once all were acknowledged
+    pub fn expire(&mut self, invoke_id: u8, now: Instant) -> Option<Expiry> {
+        let transaction = self.pending.get_mut(&invoke_id)?;
+        if now < transaction.deadline {
+            return None;
+        }
+        if transaction.retries == 0 {
+            self.pending.remove(&invoke_id);
+            return Some(Expiry::TimedOut);
+        }
+        transaction.retries -= 1;
+        transaction.deadline = now + self.timeout;
+        let window_size = if transaction.unacknowledged == 0 {
+            1
+        } else if transaction.unacknowledged == transaction.request.len() {
+            transaction.unacknowledged = 0;
+            1
+        } else {
+            transaction.request_window_size
+        };
+        for segment in transaction.window(window_size) {
+            self.outgoing.push((transaction.address, segment.clone()));
+        }
+        Some(Expiry::Retry)
+    }
+
+    /// APDUs queued to be sent, with their destination
+    pub fn take_outgoing(&mut self) -> Vec<(SocketAddrV4, APDU)> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    /// Abandon the transaction with `invoke_id`, a late reply is discarded
+    pub fn cancel(&mut self, invoke_id: u8) {
+        self.pending.remove(&invoke_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+    use crate::application::{ConfirmedRequest, MaxSegmentsAccepted, segment_request};
+
+    const DEVICE: SocketAddrV4 = SocketAddrV4::new(std::net::Ipv4Addr::LOCALHOST, 47808);
+
+    fn simple_ack(invoke_id: u8) -> APDU {
+        APDU::decode_slice(&[0x20, invoke_id, 0x0f]).unwrap()
+    }
+
+    #[test]
+    fn test_invoke_ids_wrap_and_skip_pending() {
+        let now = Instant::now();
+        let mut manager = TransactionManager::new();
+        for i in 0..=u8::MAX {
+            assert_eq!(manager.begin(DEVICE, now).unwrap(), i);
+        }
+        assert_eq!(
+            manager.begin(DEVICE, now).unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+
+        manager.cancel(7);
+        manager.cancel(3);
+        assert_eq!(manager.begin(DEVICE, now).unwrap(), 3);
+        assert_eq!(manager.begin(DEVICE, now).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_reply_completes_transaction() {
+        let now = Instant::now();
+        let mut manager = TransactionManager::new();
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        let other = SocketAddrV4::new(std::net::Ipv4Addr::new(10, 0, 0, 1), 47808);
+        assert_eq!(manager.receive(other, simple_ack(invoke_id), now), None);
+        assert_eq!(
+            manager.receive(DEVICE, simple_ack(invoke_id + 1), now),
+            None
+        );
+        assert_eq!(
+            manager.receive(DEVICE, simple_ack(invoke_id), now),
+            Some(simple_ack(invoke_id))
+        );
+        assert!(!manager.is_pending(invoke_id));
+        assert_eq!(manager.receive(DEVICE, simple_ack(invoke_id), now), None);
+    }
+
+    #[test]
+    fn test_dropped_reply_retried() {
+        let now = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let mut manager = TransactionManager::new().timeout(timeout).retries(1);
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        // The first reply is lost
+        assert_eq!(manager.expire(invoke_id, now), None);
+        assert_eq!(
+            manager.expire(invoke_id, now + timeout),
+            Some(Expiry::Retry)
+        );
+        assert_eq!(manager.deadline(invoke_id), Some(now + 2 * timeout));
+
+        // The retry is answered
+        assert_eq!(
+            manager.receive(DEVICE, simple_ack(invoke_id), now + timeout),
+            Some(simple_ack(invoke_id))
+        );
+        assert_eq!(manager.expire(invoke_id, now + 2 * timeout), None);
+    }
+
+    #[test]
+    fn test_retries_exhausted() {
+        let now = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let mut manager = TransactionManager::new().timeout(timeout).retries(1);
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        assert_eq!(
+            manager.expire(invoke_id, now + timeout),
+            Some(Expiry::Retry)
+        );
+        assert_eq!(
+            manager.expire(invoke_id, now + 2 * timeout),
+            Some(Expiry::TimedOut)
+        );
+        assert!(!manager.is_pending(invoke_id));
+    }
+
+    #[test]
+    fn test_segment_ack_restarts_timer() {
+        let now = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let mut manager = TransactionManager::new().timeout(timeout).retries(0);
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        let segment_ack = APDU::decode_slice(&[0x40, invoke_id, 0x00, 0x02]).unwrap();
+        let later = now + timeout / 2;
+        assert_eq!(manager.receive(DEVICE, segment_ack, later), None);
+        assert_eq!(manager.expire(invoke_id, now + timeout), None);
+        assert_eq!(manager.deadline(invoke_id), Some(later + timeout));
+    }
+
+    #[test]
+    fn test_segmented_complex_ack_reassembled() {
+        let now = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let mut manager = TransactionManager::new().timeout(timeout).retries(0);
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        // ReadProperty ComplexACK in three segments, window size 2
+        let segment = |flags: u8, sequence: u8, data: &[u8]| {
+            let mut pdu = vec![0x30 | flags, invoke_id, sequence, 0x02, 0x0c];
+            pdu.extend_from_slice(data);
+            APDU::decode_slice(&pdu).unwrap()
+        };
+        let first = segment(0b1100, 0, &[0x0c, 0x00, 0x00]);
+        let second = segment(0b1100, 1, &[0x00, 0x01, 0x19]);
+        let last = segment(0b1000, 2, &[0x55, 0x3e, 0x91, 0x00, 0x3f]);
+
+        let later = now + timeout / 2;
+        assert_eq!(manager.receive(DEVICE, first.clone(), later), None);
+        assert!(manager.is_pending(invoke_id));
+        assert_eq!(manager.deadline(invoke_id), Some(later + timeout));
+
+        // The timer restarted, the original deadline passed without expiring
+        assert_eq!(manager.expire(invoke_id, now + timeout), None);
+
+        // A repeated or skipped segment is discarded
+        assert_eq!(manager.receive(DEVICE, first, later), None);
+        assert_eq!(manager.receive(DEVICE, last.clone(), later), None);
+        assert!(manager.is_pending(invoke_id));
+
+        let even_later = later + timeout / 2;
+        assert_eq!(manager.receive(DEVICE, second, even_later), None);
+        assert_eq!(manager.deadline(invoke_id), Some(even_later + timeout));
+
+        let reply = manager.receive(DEVICE, last, even_later).unwrap();
+        assert!(!manager.is_pending(invoke_id));
+
+        // The first segment and the last are acknowledged, the repeated and the skipped
+        // segment are answered with a negative ACK of the first
+        let ack = |negative: bool, sequence_number: u8| {
+            let ack = SegmentAck {
+                negative,
+                server: false,
+                invoke_id,
+                sequence_number,
+                actual_window_size: 2,
+            };
+            (DEVICE, APDU::from(ack))
+        };
+        assert_eq!(
+            manager.take_outgoing(),
+            vec![ack(false, 0), ack(true, 0), ack(true, 0), ack(false, 2)]
+        );
+        assert!(manager.take_outgoing().is_empty());
+        assert_eq!(reply.pdu_type(), Some(BACnetPDU::ComplexACK));
+        assert_eq!(reply.invoke_id(), Some(invoke_id));
+        assert_eq!(reply.service_choice, 0x0c);
+        assert_eq!(
+            reply.user_data(),
+            hex::decode("0c0000000119553e91003f").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_segmented_request_sent_a_window_at_a_time() {
+        let now = Instant::now();
+        let mut manager = TransactionManager::new().retries(1);
+        let invoke_id = manager.begin(DEVICE, now).unwrap();
+
+        // Four segments of a request, the server acknowledges with window size 2
+        let request = ConfirmedRequest::new(invoke_id, 0x0f, vec![0x55; 40]);
+        let segments: Vec<APDU> =
+            segment_request(&request, 16, MaxSegmentsAccepted::Unspecified, 2)
+                .unwrap()
+                .into_iter()
+                .map(APDU::from)
+                .collect();
+        assert_eq!(segments.len(), 4);
+        let ack = |negative: bool, sequence_number: u8| {
+            let ack = SegmentAck {
+                negative,
+                server: true,
+                invoke_id,
+                sequence_number,
+                actual_window_size: 2,
+            };
+            APDU::from(ack)
+        };
+        let sent = |manager: &mut TransactionManager| -> Vec<APDU> {
+            manager
+                .take_outgoing()
+                .into_iter()
+                .map(|(address, apdu)| {
+                    assert_eq!(address, DEVICE);
+                    apdu
+                })
+                .collect()
+        };
+
+        manager.send(invoke_id, segments.clone());
+        assert_eq!(sent(&mut manager), segments[..1]);
+
+        assert_eq!(manager.receive(DEVICE, ack(false, 0), now), None);
+        assert_eq!(sent(&mut manager), segments[1..3]);
+
+        // Segment 2 went missing, the server asks for it again
+        assert_eq!(manager.receive(DEVICE, ack(true, 1), now), None);
+        assert_eq!(sent(&mut manager), segments[2..4]);
+
+        // A retry after the last window was acknowledged starts over with the first segment
+        assert_eq!(manager.receive(DEVICE, ack(false, 3), now), None);
+        assert!(sent(&mut manager).is_empty());
+        let later = manager.deadline(invoke_id).unwrap();
+        assert_eq!(manager.expire(invoke_id, later), Some(Expiry::Retry));
+        assert_eq!(sent(&mut manager), segments[..1]);
+    }
+}
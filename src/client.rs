@@ -0,0 +1,1123 @@
+/// High-level BACnet/IP client (Annex J) on top of the encoding, network and application layers
+use crate::application::*;
+use crate::encoding::{ApplicationValue, ObjectIdentifier, PropertyIdentifier, PropertyValue};
+use crate::network::*;
+use crate::transport::bacnetip::*;
+use crate::{Decode, Encode, Error};
+
+use async_std::future;
+use async_std::net::UdpSocket;
+use async_std::task;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::{Duration, Instant};
+
+use tracing::{info, trace};
+
+/// UDP port assigned to BACnet/IP (J.1)
+pub const BACNET_IP_PORT: u16 = 0xBAC0;
+
+/// Largest BVLC datagram, a 1476 octet APDU with NPCI and BVLL header
+const MAX_DATAGRAM: usize = 1497;
+
+/// Token bucket pacing broadcasts, holding up to `burst` broadcasts and refilled with one every
+/// `interval`
+#[derive(Clone, Debug)]
+struct BroadcastLimiter {
+    interval: Duration,
+    burst: u32,
+    /// Time the bucket is full again, as in the generic cell rate algorithm
+    full_at: Option<Instant>,
+}
+
+impl BroadcastLimiter {
+    /// Take a token for a broadcast at `now`, returning how long to wait before sending it
+    fn acquire(&mut self, now: Instant) -> Duration {
+        let full_at = self.full_at.map_or(now, |t| t.max(now));
+        let allowance = self.interval * self.burst.saturating_sub(1);
+        let send_at = full_at.checked_sub(allowance).map_or(now, |t| t.max(now));
+        self.full_at = Some(full_at + self.interval);
+        send_at - now
+    }
+}
+
+/// A BACnet/IP client sending requests and awaiting their replies
+pub struct Client {
+    socket: UdpSocket,
+    /// Destination of broadcasts such as Who-Is
+    target: SocketAddrV4,
+    /// Time to collect I-Am replies
+    timeout: Duration,
+    /// Hop count of remote network destinations
+    hop_count: u8,
+    /// Segments accepted in a segmented response
+    max_segments: MaxSegmentsAccepted,
+    /// Largest APDU accepted by the devices requests are sent to, longer requests are segmented
+    max_apdu: MaxApduLengthAccepted,
+    /// Window size proposed for segmented requests
+    window_size: u8,
+    broadcasts: BroadcastLimiter,
+    transactions: TransactionManager,
+}
+
+impl Client {
+    /// Bind a client to `port` on all interfaces, port 0 picks an ephemeral port
+    pub async fn bind(port: u16) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)).await?;
+        socket.set_broadcast(true)?;
+        Ok(Self {
+            socket,
+            target: SocketAddrV4::new(Ipv4Addr::BROADCAST, BACNET_IP_PORT),
+            timeout: DEFAULT_APDU_TIMEOUT,
+            hop_count: DEFAULT_HOP_COUNT,
+            max_segments: MaxSegmentsAccepted::Up64,
+            max_apdu: MaxApduLengthAccepted::Up1476,
+            window_size: 16,
+            broadcasts: BroadcastLimiter {
+                interval: Duration::ZERO,
+                burst: 1,
+                full_at: None,
+            },
+            transactions: TransactionManager::new(),
+        })
+    }
+
+    /// Address broadcasts are sent to, defaults to the local broadcast on port 47808
+    pub fn target(mut self, target: SocketAddrV4) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Time to wait for replies, defaults to DEFAULT_APDU_TIMEOUT
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.transactions = self.transactions.timeout(timeout);
+        self
+    }
+
+    /// Number of times a confirmed request is sent again without a reply, defaults to
+    /// DEFAULT_APDU_RETRIES
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.transactions = self.transactions.retries(retries);
+        self
+    }
+
+    /// Hop count of remote network destinations such as the global broadcast of Who-Is,
+    /// defaults to DEFAULT_HOP_COUNT
+    pub fn hop_count(mut self, hops: u8) -> Self {
+        self.hop_count = hops;
+        self
+    }
+
+    /// Number of segments accepted in a segmented response, defaults to 64
+    pub fn max_segments(mut self, max_segments: MaxSegmentsAccepted) -> Self {
+        self.max_segments = max_segments;
+        self
+    }
+
+    /// Minimum interval between broadcasts such as Who-Is, after a burst of `burst` broadcasts
+    /// sent at once. Broadcasts are not paced by default
+    pub fn broadcast_interval(mut self, interval: Duration, burst: u32) -> Self {
+        self.broadcasts.interval = interval;
+        self.broadcasts.burst = burst.max(1);
+        self
+    }
+
+    /// Largest APDU the devices accept, longer requests are sent in segments, defaults to 1476
+    pub fn max_apdu(mut self, max_apdu: MaxApduLengthAccepted) -> Self {
+        self.max_apdu = max_apdu;
+        self
+    }
+
+    /// Window size proposed for segmented requests, 1 to 127, defaults to 16
+    pub fn window_size(mut self, window_size: u8) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Broadcast a Who-Is to the target and collect the I-Am replies received within the timeout
+    pub async fn who_is(&mut self, request: WhoIs) -> std::io::Result<Vec<(SocketAddrV4, IAm)>> {
+        self.send_who_is(&request).await?;
+
+        let mut devices = Vec::new();
+        let deadline = Instant::now() + self.timeout;
+        while let Some(device) = self.receive_i_am(deadline).await? {
+            devices.push(device);
+        }
+        Ok(devices)
+    }
+
+    /// Broadcast a Who-Is for the device with `instance` and return its I-Am, None when no
+    /// device answered within `wait`
+    pub async fn discover_one(
+        &mut self,
+        instance: u32,
+        wait: Duration,
+    ) -> std::io::Result<Option<(SocketAddrV4, IAm)>> {
+        self.send_who_is(&WhoIs::for_instance(instance)).await?;
+
+        let deadline = Instant::now() + wait;
+        while let Some((source, i_am)) = self.receive_i_am(deadline).await? {
+            if i_am.device_identifier.instance() == instance {
+                return Ok(Some((source, i_am)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Broadcast a Who-Is once the broadcast interval allows it
+    async fn send_who_is(&mut self, request: &WhoIs) -> std::io::Result<()> {
+        let delay = self.broadcasts.acquire(Instant::now());
+        if !delay.is_zero() {
+            trace!("Broadcast delayed by {:?}", delay);
+            task::sleep(delay).await;
+        }
+        let apdu = APDU::new(
+            BACnetPDU::UnconfirmedRequest.as_u8(),
+            WhoIs::SERVICE_CHOICE,
+            request.encode_vec()?,
+        );
+        let npdu = NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff).with_hops(self.hop_count)),
+            None,
+            NPDUPriority::Normal,
+        );
+        self.send(BVLCFunction::OriginalBroadcastNPDU(npdu), self.target)
+            .await
+    }
+
+    /// Receive the next I-Am, None once the deadline passed, other APDUs are skipped
+    async fn receive_i_am(
+        &self,
+        deadline: Instant,
+    ) -> std::io::Result<Option<(SocketAddrV4, IAm)>> {
+        while let Some((source, apdu)) = self.receive_until(deadline).await? {
+            if apdu.pdu_type() != Some(BACnetPDU::UnconfirmedRequest)
+                || apdu.service_choice != IAm::SERVICE_CHOICE
+            {
+                continue;
+            }
+            match IAm::decode_slice(apdu.user_data()) {
+                Ok(i_am) => return Ok(Some((source, i_am))),
+                Err(e) => trace!("Invalid I-Am from {}: {}", source, e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read a property of an object in the device at `address`, sent with network `priority`,
+    /// Normal if None
+    pub async fn read_property(
+        &mut self,
+        address: SocketAddrV4,
+        object_identifier: ObjectIdentifier,
+        property_identifier: impl Into<u32>,
+        priority: Option<NPDUPriority>,
+    ) -> crate::Result<PropertyValue> {
+        let request = ReadProperty::new(object_identifier, property_identifier);
+        let result = self
+            .confirmed_request(address, priority, |invoke_id| {
+                request.confirmed_request(invoke_id)
+            })
+            .await
+            .and_then(|ack| ReadPropertyAck::decode_slice(ack.user_data()))
+            .map(|ack| ack.property_value);
+        let property = property_name(request.property_identifier);
+        match &result {
+            Ok(value) => info!(
+                "ReadProperty {} {} -> {}",
+                object_identifier, property, value
+            ),
+            Err(e) => info!(
+                "ReadProperty {} {} failed: {}",
+                object_identifier, property, e
+            ),
+        }
+        result
+    }
+
+    /// Read a property holding a single value and convert it to `T`, such as f32 for a REAL.
+    /// A value of another type fails with `Error::TypeMismatch`
+    pub async fn read_typed<T: TryFrom<ApplicationValue, Error = Error>>(
+        &mut self,
+        address: SocketAddrV4,
+        object_identifier: ObjectIdentifier,
+        property_identifier: impl Into<u32>,
+    ) -> crate::Result<T> {
+        let value = self
+            .read_property(address, object_identifier, property_identifier, None)
+            .await?;
+        match value.into_single() {
+            Some(value) => T::try_from(value),
+            None => Err(Error::InvalidValue(
+                "Property value is not a single value".to_string(),
+            )),
+        }
+    }
+
+    /// Write a property of an object in the device at `address`, sent with network `priority`,
+    /// Normal if None
+    pub async fn write_property(
+        &mut self,
+        address: SocketAddrV4,
+        request: WriteProperty,
+        priority: Option<NPDUPriority>,
+    ) -> crate::Result<()> {
+        let result = self
+            .confirmed_request(address, priority, |invoke_id| {
+                request.confirmed_request(invoke_id)
+            })
+            .await
+            .map(|_| ());
+        let object_identifier = request.object_identifier;
+        let property = property_name(request.property_identifier);
+        let value = &request.property_value;
+        match &result {
+            Ok(()) => info!(
+                "WriteProperty {} {} = {} -> ok",
+                object_identifier, property, value
+            ),
+            Err(e) => info!(
+                "WriteProperty {} {} = {} failed: {}",
+                object_identifier, property, value, e
+            ),
+        }
+        result
+    }
+
+    /// Send a confirmed request built for the allocated invoke ID and await the SimpleACK or
+    /// ComplexACK, retrying as configured. An Error, Reject or Abort reply is returned as
+    /// `Error::Service`, `Error::Rejected` or `Error::Aborted`
+    async fn confirmed_request(
+        &mut self,
+        address: SocketAddrV4,
+        priority: Option<NPDUPriority>,
+        build: impl FnOnce(u8) -> crate::Result<ConfirmedRequest>,
+    ) -> crate::Result<APDU> {
+        let invoke_id = self.transactions.begin(address, Instant::now())?;
+        let priority = priority.unwrap_or_default();
+        let result = self.transact(address, invoke_id, priority, build).await;
+        self.transactions.cancel(invoke_id);
+        result
+    }
+
+    async fn transact(
+        &mut self,
+        address: SocketAddrV4,
+        invoke_id: u8,
+        priority: NPDUPriority,
+        build: impl FnOnce(u8) -> crate::Result<ConfirmedRequest>,
+    ) -> crate::Result<APDU> {
+        let mut request = build(invoke_id)?;
+        request.segmented_response_accepted = true;
+        request.max_segments = self.max_segments;
+        let segments = segment_request(
+            &request,
+            self.max_apdu.max_bytes(),
+            MaxSegmentsAccepted::Unspecified,
+            self.window_size,
+        )?;
+        let segments = segments.into_iter().map(APDU::from).collect();
+        self.transactions.send(invoke_id, segments);
+        self.send_outgoing(priority).await?;
+
+        while let Some(deadline) = self.transactions.deadline(invoke_id) {
+            match self.receive_until(deadline).await? {
+                Some((source, apdu)) => {
+                    let reply = self.transactions.receive(source, apdu, Instant::now());
+                    self.send_outgoing(priority).await?;
+                    if let Some(reply) = reply {
+                        return match reply.pdu_type() {
+                            Some(BACnetPDU::SimpleACK) | Some(BACnetPDU::ComplexACK) => Ok(reply),
+                            _ => Err(reply_error(&reply)),
+                        };
+                    }
+                }
+                None => {
+                    if self.transactions.expire(invoke_id, Instant::now()) == Some(Expiry::Retry) {
+                        trace!("Retry invoke ID {} to {}", invoke_id, address);
+                        self.send_outgoing(priority).await?;
+                    }
+                }
+            }
+        }
+        Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            format!("No reply from {} to invoke ID {}", address, invoke_id),
+        )))
+    }
+
+    /// Send the APDUs queued by the transactions with network `priority`, each in its own
+    /// datagram
+    async fn send_outgoing(&mut self, priority: NPDUPriority) -> std::io::Result<()> {
+        for (address, apdu) in self.transactions.take_outgoing() {
+            let npdu = NpduBuilder::new()
+                .hop_count(self.hop_count)
+                .priority(priority)
+                .confirmed_request(apdu);
+            self.send(BVLCFunction::OriginalUnicastNPDU(npdu), address)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Send `data` to `address` as is, without encoding, such as a captured frame to replay
+    pub async fn send_raw(&self, data: &[u8], address: SocketAddrV4) -> std::io::Result<()> {
+        trace!("Send raw to {}: {:02x?}", address, data);
+        self.socket.send_to(data, address).await?;
+        Ok(())
+    }
+
+    async fn send(&self, function: BVLCFunction, address: SocketAddrV4) -> std::io::Result<()> {
+        let data = BVLC::new(function).encode_vec()?;
+        trace!("Send to {}: {:02x?}", address, data);
+        self.socket.send_to(&data, address).await?;
+        Ok(())
+    }
+
+    /// Receive the next APDU, None once the deadline passed, datagrams that do not decode or
+    /// carry no APDU are skipped
+    async fn receive_until(
+        &self,
+        deadline: Instant,
+    ) -> std::io::Result<Option<(SocketAddrV4, APDU)>> {
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let (n, peer) = match future::timeout(remaining, self.socket.recv_from(&mut buf)).await
+            {
+                Ok(received) => received?,
+                Err(_) => return Ok(None),
+            };
+            let SocketAddr::V4(peer) = peer else {
+                continue;
+            };
+            let bvlc = match BVLC::decode_slice(&buf[..n]) {
+                Ok(bvlc) => bvlc,
+                Err(e) => {
+                    trace!("Invalid BVLC from {}: {}", peer, e);
+                    continue;
+                }
+            };
+            let source = bvlc.originating_address(peer);
+            if let Some(NPDUContent::APDU(apdu)) = bvlc.function.npdu().map(|n| &n.content) {
+                return Ok(Some((source, apdu.clone())));
+            }
+        }
+    }
+}
+
+/// Name of a standard property for logs, the number of any other
+fn property_name(property_identifier: u32) -> String {
+    match PropertyIdentifier::try_from(property_identifier) {
+        Ok(property) => property.to_string(),
+        Err(_) => property_identifier.to_string(),
+    }
+}
+
+/// Error for an Error, Reject or Abort PDU received in reply to a confirmed request
+fn reply_error(apdu: &APDU) -> Error {
+    if let Some(reason) = apdu.reject_reason() {
+        return Error::Rejected(reason);
+    }
+    if let Some(reason) = apdu.abort_reason() {
+        return Error::Aborted(reason);
+    }
+    match apdu.pdu_type() {
+        Some(BACnetPDU::Error) => match BACnetError::try_from(apdu) {
+            Ok(e) => Error::Service(e),
+            Err(e) => e,
+        },
+        t => Error::Unsupported(format!("Unexpected reply: {:?}", t)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationTag, ObjectType};
+
+    /// Loopback socket standing in for a device
+    async fn responder() -> (UdpSocket, SocketAddrV4) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let SocketAddr::V4(address) = socket.local_addr().unwrap() else {
+            unreachable!()
+        };
+        (socket, address)
+    }
+
+    async fn client(target: SocketAddrV4) -> Client {
+        Client::bind(0)
+            .await
+            .unwrap()
+            .target(target)
+            .timeout(Duration::from_millis(500))
+    }
+
+    /// Receive the next APDU sent by the client
+    async fn receive(socket: &UdpSocket) -> (SocketAddr, APDU) {
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        let (n, peer) = socket.recv_from(&mut buf).await.unwrap();
+        let bvlc = BVLC::decode_slice(&buf[..n]).unwrap();
+        let Some(NPDUContent::APDU(apdu)) = bvlc.function.npdu().map(|n| n.content.clone()) else {
+            panic!("Request without APDU: {:?}", bvlc);
+        };
+        (peer, apdu)
+    }
+
+    /// Receive a request and reply with the frame `reply` builds from the request APDU
+    async fn respond(socket: &UdpSocket, reply: impl Fn(&APDU) -> Vec<u8>) -> APDU {
+        let (peer, apdu) = receive(socket).await;
+        socket.send_to(&reply(&apdu), peer).await.unwrap();
+        apdu
+    }
+
+    #[test]
+    fn test_who_is_collects_i_am() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |_| {
+                    // I-Am device,260001 max-apdu 1476, no segmentation, vendor 15
+                    hex::decode("810b00180120ffff00ff1000c4020000012205c49103210f").unwrap()
+                })
+                .await
+            });
+            let devices = client.who_is(WhoIs::new()).await.unwrap();
+            let request = responder.await;
+
+            assert_eq!(request.service_choice, WhoIs::SERVICE_CHOICE);
+            assert_eq!(devices.len(), 1);
+            assert_eq!(devices[0].0, address);
+            assert_eq!(
+                devices[0].1.device_identifier,
+                ObjectIdentifier::new(ObjectType::Device, 1).unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn test_who_is_hop_count() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address)
+                .await
+                .timeout(Duration::from_millis(50))
+                .hop_count(16);
+
+            let responder = task::spawn(async move {
+                let mut buf = vec![0u8; MAX_DATAGRAM];
+                let (n, _) = device.recv_from(&mut buf).await.unwrap();
+                BVLC::decode_slice(&buf[..n]).unwrap()
+            });
+            client.who_is(WhoIs::new()).await.unwrap();
+            let bvlc = responder.await;
+
+            let destination = bvlc.function.npdu().unwrap().destination.clone().unwrap();
+            assert_eq!(destination.hops(), 16);
+        });
+    }
+
+    #[test]
+    fn test_broadcasts_spaced_by_interval() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let interval = Duration::from_millis(200);
+            let mut client = client(address).await.broadcast_interval(interval, 1);
+
+            let start = Instant::now();
+            assert!(
+                client
+                    .discover_one(1, Duration::ZERO)
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+            assert!(
+                client
+                    .discover_one(2, Duration::ZERO)
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+            assert!(start.elapsed() >= interval);
+
+            let (_, first) = receive(&device).await;
+            let (_, second) = receive(&device).await;
+            assert_eq!(
+                WhoIs::decode_slice(first.user_data()).unwrap(),
+                WhoIs::for_instance(1)
+            );
+            assert_eq!(
+                WhoIs::decode_slice(second.user_data()).unwrap(),
+                WhoIs::for_instance(2)
+            );
+        });
+    }
+
+    #[test]
+    fn test_broadcast_limiter_burst() {
+        let interval = Duration::from_millis(100);
+        let mut limiter = BroadcastLimiter {
+            interval,
+            burst: 2,
+            full_at: None,
+        };
+        let now = Instant::now();
+        assert_eq!(limiter.acquire(now), Duration::ZERO);
+        assert_eq!(limiter.acquire(now), Duration::ZERO);
+        assert_eq!(limiter.acquire(now), interval);
+        assert_eq!(limiter.acquire(now + interval), interval);
+
+        // An idle bucket fills up again
+        let later = now + interval * 10;
+        assert_eq!(limiter.acquire(later), Duration::ZERO);
+        assert_eq!(limiter.acquire(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_send_raw() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let client = client(address).await;
+
+            // Global broadcast Who-Is, unicast to the device
+            let frame = hex::decode("810b000c0120ffff00ff1008").unwrap();
+            client.send_raw(&frame, address).await.unwrap();
+
+            let mut buf = vec![0u8; MAX_DATAGRAM];
+            let (n, peer) = device.recv_from(&mut buf).await.unwrap();
+            assert_eq!(buf[..n], frame);
+            assert_eq!(peer.port(), client.local_addr().unwrap().port());
+        });
+    }
+
+    #[test]
+    fn test_discover_one() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |_| {
+                    hex::decode("810b00180120ffff00ff1000c4020000012205c49103210f").unwrap()
+                })
+                .await
+            });
+            let (source, i_am) = client
+                .discover_one(1, Duration::from_millis(500))
+                .await
+                .unwrap()
+                .expect("I-Am of device 1");
+            let request = responder.await;
+
+            assert_eq!(request.service_choice, WhoIs::SERVICE_CHOICE);
+            assert_eq!(
+                WhoIs::decode_slice(request.user_data()).unwrap(),
+                WhoIs::for_instance(1)
+            );
+            assert_eq!(source, address);
+            assert_eq!(i_am.device_identifier.instance(), 1);
+
+            let none = client
+                .discover_one(2, Duration::from_millis(50))
+                .await
+                .unwrap();
+            assert_eq!(none, None);
+        });
+    }
+
+    #[test]
+    fn test_read_property() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |request| {
+                    // ComplexACK ReadProperty analog-input,1 present-value 72.5
+                    let mut frame = hex::decode("810a00170100").unwrap();
+                    frame.extend([0x30, request.invoke_id().unwrap(), 0x0c]);
+                    frame.extend(hex::decode("0c0000000119553e44429100003f").unwrap());
+                    frame
+                })
+                .await
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let value = client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    None,
+                )
+                .await
+                .unwrap();
+            let request = responder.await;
+
+            assert_eq!(request.service_choice, ReadProperty::SERVICE_CHOICE);
+            assert_eq!(value.single(), Some(&ApplicationValue::Real(72.5)));
+        });
+    }
+
+    #[test]
+    fn test_read_property_priority() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                let mut buf = vec![0u8; MAX_DATAGRAM];
+                let (n, peer) = device.recv_from(&mut buf).await.unwrap();
+                let request = buf[..n].to_vec();
+
+                let mut frame = hex::decode("810a00170100").unwrap();
+                frame.extend([0x30, request[8], 0x0c]);
+                frame.extend(hex::decode("0c0000000119553e44429100003f").unwrap());
+                device.send_to(&frame, peer).await.unwrap();
+                request
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let priority = Some(NPDUPriority::CriticalEquipment);
+            client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    priority,
+                )
+                .await
+                .unwrap();
+            let request = responder.await;
+
+            // BVLL header, then the NPCI version and control octet: expecting reply,
+            // critical equipment priority
+            assert_eq!(request[4..6], [0x01, 0b0000_0110]);
+            assert_eq!(request[5] & 0b11, 0b10);
+        });
+    }
+
+    #[test]
+    fn test_read_typed() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |request| {
+                    let mut frame = hex::decode("810a00170100").unwrap();
+                    frame.extend([0x30, request.invoke_id().unwrap(), 0x0c]);
+                    frame.extend(hex::decode("0c0000000119553e44429100003f").unwrap());
+                    frame
+                })
+                .await;
+                respond(&device, |request| {
+                    // ComplexACK ReadProperty device,1 object-name "AHU-1"
+                    let mut frame = hex::decode("810a001a0100").unwrap();
+                    frame.extend([0x30, request.invoke_id().unwrap(), 0x0c]);
+                    frame.extend(hex::decode("0c02000001194d3e7506004148552d313f").unwrap());
+                    frame
+                })
+                .await;
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let value: f32 = client
+                .read_typed(address, object_identifier, PropertyIdentifier::PresentValue)
+                .await
+                .unwrap();
+            assert_eq!(value, 72.5);
+
+            let object_identifier = ObjectIdentifier::new(ObjectType::Device, 1).unwrap();
+            let err = client
+                .read_typed::<f32>(address, object_identifier, PropertyIdentifier::ObjectName)
+                .await
+                .unwrap_err();
+            responder.await;
+            assert!(
+                matches!(
+                    err,
+                    Error::TypeMismatch {
+                        expected: ApplicationTag::Real,
+                        found: ApplicationTag::CharacterString
+                    }
+                ),
+                "{:?}",
+                err
+            );
+        });
+    }
+
+    /// Log output of the tests, shared with the subscriber writing it
+    #[derive(Clone, Default)]
+    struct Capture(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_read_property_info_summary() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .without_time()
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            task::block_on(async {
+                let (device, address) = responder().await;
+                let mut client = client(address).await;
+
+                let responder = task::spawn(async move {
+                    for _ in 0..2 {
+                        respond(&device, |request| {
+                            let mut frame = hex::decode("810a00170100").unwrap();
+                            frame.extend([0x30, request.invoke_id().unwrap(), 0x0c]);
+                            frame.extend(hex::decode("0c0000000119553e44429100003f").unwrap());
+                            frame
+                        })
+                        .await;
+                    }
+                });
+                let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+                for _ in 0..2 {
+                    client
+                        .read_property(
+                            address,
+                            object_identifier,
+                            PropertyIdentifier::PresentValue,
+                            None,
+                        )
+                        .await
+                        .unwrap();
+                }
+                responder.await;
+            })
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let info: Vec<&str> = output.lines().filter(|l| l.contains(" INFO ")).collect();
+        assert_eq!(info.len(), 2, "{}", output);
+        for line in info {
+            assert!(
+                line.ends_with("ReadProperty analog-input,1 present-value -> 72.5"),
+                "{}",
+                line
+            );
+        }
+        // Raw frames are only traced
+        assert!(
+            output
+                .lines()
+                .any(|l| l.contains("TRACE") && l.contains("Send to"))
+        );
+    }
+
+    #[test]
+    fn test_write_property_error() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |request| {
+                    // Error property / write-access-denied
+                    let mut frame = hex::decode("810a000d0100").unwrap();
+                    frame.extend([0x50, request.invoke_id().unwrap(), 0x0f]);
+                    frame.extend(hex::decode("91029128").unwrap());
+                    frame
+                })
+                .await
+            });
+            let request = WriteProperty::new(
+                ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+                PropertyIdentifier::PresentValue,
+                ApplicationValue::Real(21.0),
+            );
+            let err = client
+                .write_property(address, request, None)
+                .await
+                .unwrap_err();
+            responder.await;
+
+            let Error::Service(error) = err else {
+                panic!("Not an Error PDU: {:?}", err);
+            };
+            assert_eq!(error.class, ErrorClass::Property);
+            assert_eq!(error.code, ErrorCode::WriteAccessDenied);
+        });
+    }
+
+    #[test]
+    fn test_write_property_invalid_priority() {
+        task::block_on(async {
+            let (_device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let mut request = WriteProperty::new(
+                ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+                PropertyIdentifier::PresentValue,
+                ApplicationValue::Real(21.0),
+            );
+            request.priority = Some(17);
+            let err = client
+                .write_property(address, request, None)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::InvalidPriority(17)), "{:?}", err);
+        });
+    }
+
+    #[test]
+    fn test_read_property_rejected() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                respond(&device, |request| {
+                    // Reject unrecognized-service
+                    let mut frame = hex::decode("810a00090100").unwrap();
+                    frame.extend([0x60, request.invoke_id().unwrap(), 0x09]);
+                    frame
+                })
+                .await
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let err = client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    None,
+                )
+                .await
+                .unwrap_err();
+            responder.await;
+
+            assert!(
+                matches!(err, Error::Rejected(RejectReason::UnrecognizedService)),
+                "{:?}",
+                err
+            );
+        });
+    }
+
+    #[test]
+    fn test_read_property_retried_after_dropped_reply() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address)
+                .await
+                .timeout(Duration::from_millis(100))
+                .retries(1);
+
+            let responder = task::spawn(async move {
+                // The first request goes unanswered
+                let mut buf = vec![0u8; MAX_DATAGRAM];
+                device.recv_from(&mut buf).await.unwrap();
+                respond(&device, |request| {
+                    let mut frame = hex::decode("810a00170100").unwrap();
+                    frame.extend([0x30, request.invoke_id().unwrap(), 0x0c]);
+                    frame.extend(hex::decode("0c0000000119553e44429100003f").unwrap());
+                    frame
+                })
+                .await
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let value = client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    None,
+                )
+                .await
+                .unwrap();
+            responder.await;
+
+            assert_eq!(value.single(), Some(&ApplicationValue::Real(72.5)));
+        });
+    }
+
+    #[test]
+    fn test_read_property_timeout() {
+        task::block_on(async {
+            let (_device, address) = responder().await;
+            let mut client = client(address)
+                .await
+                .timeout(Duration::from_millis(50))
+                .retries(1);
+
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let err = client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    None,
+                )
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, Error::Io(e) if e.kind() == std::io::ErrorKind::TimedOut),
+                "{:?}",
+                err
+            );
+        });
+    }
+
+    #[test]
+    fn test_read_property_segmented_response() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address).await;
+
+            let responder = task::spawn(async move {
+                let (peer, request) = receive(&device).await;
+                let invoke_id = request.invoke_id().unwrap();
+
+                // ReadProperty ComplexACK analog-input,1 present-value 72.5 in three segments,
+                // window size 2
+                let segment = |flags: u8, sequence: u8, data: &str| {
+                    let mut frame = vec![0x81, 0x0a, 0x00, 0x00, 0x01, 0x00];
+                    frame.extend([0x30 | flags, invoke_id, sequence, 0x02, 0x0c]);
+                    frame.extend(hex::decode(data).unwrap());
+                    frame[3] = frame.len() as u8;
+                    frame
+                };
+                let mut acks = Vec::new();
+                device
+                    .send_to(&segment(0b1100, 0, "0c00000001"), peer)
+                    .await
+                    .unwrap();
+                acks.push(receive(&device).await.1);
+                device
+                    .send_to(&segment(0b1100, 1, "19553e"), peer)
+                    .await
+                    .unwrap();
+                device
+                    .send_to(&segment(0b1000, 2, "44429100003f"), peer)
+                    .await
+                    .unwrap();
+                acks.push(receive(&device).await.1);
+                (request, acks)
+            });
+            let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+            let value = client
+                .read_property(
+                    address,
+                    object_identifier,
+                    PropertyIdentifier::PresentValue,
+                    None,
+                )
+                .await
+                .unwrap();
+            let (request, acks) = responder.await;
+
+            let request = ConfirmedRequest::try_from(request).unwrap();
+            assert!(request.segmented_response_accepted);
+            assert_eq!(request.max_segments, MaxSegmentsAccepted::Up64);
+            assert_eq!(value.single(), Some(&ApplicationValue::Real(72.5)));
+
+            // The first segment and the last one are acknowledged
+            let acks: Vec<_> = acks
+                .iter()
+                .map(|ack| SegmentAck::try_from(ack).unwrap())
+                .collect();
+            assert_eq!(
+                acks.iter()
+                    .map(|ack| (ack.negative, ack.server, ack.sequence_number))
+                    .collect::<Vec<_>>(),
+                vec![(false, false, 0), (false, false, 2)]
+            );
+            assert!(acks.iter().all(|ack| ack.actual_window_size == 2));
+        });
+    }
+
+    #[test]
+    fn test_write_property_segmented_request() {
+        task::block_on(async {
+            let (device, address) = responder().await;
+            let mut client = client(address)
+                .await
+                .max_apdu(MaxApduLengthAccepted::Up50)
+                .window_size(4);
+
+            let responder = task::spawn(async move {
+                let segment_ack = |invoke_id: u8, sequence_number: u8| {
+                    let mut frame = hex::decode("810a000a0100").unwrap();
+                    frame.extend([0x41, invoke_id, sequence_number, 0x02]);
+                    frame
+                };
+                let (peer, first) = receive(&device).await;
+                let invoke_id = first.invoke_id().unwrap();
+
+                // The rest waits for the acknowledgement of the first segment
+                let mut buf = vec![0u8; MAX_DATAGRAM];
+                let early = future::timeout(Duration::from_millis(100), device.recv_from(&mut buf));
+                assert!(early.await.is_err());
+                device
+                    .send_to(&segment_ack(invoke_id, 0), peer)
+                    .await
+                    .unwrap();
+                let (_, second) = receive(&device).await;
+                let (_, last) = receive(&device).await;
+                device
+                    .send_to(&segment_ack(invoke_id, 2), peer)
+                    .await
+                    .unwrap();
+
+                let mut frame = hex::decode("810a00090100").unwrap();
+                frame.extend([0x20, invoke_id, 0x0f]);
+                device.send_to(&frame, peer).await.unwrap();
+                [first, second, last]
+            });
+            let request = WriteProperty::new(
+                ObjectIdentifier::new(ObjectType::AnalogValue, 1).unwrap(),
+                PropertyIdentifier::Description,
+                ApplicationValue::CharacterString("x".repeat(100)),
+            );
+            client
+                .write_property(address, request.clone(), None)
+                .await
+                .unwrap();
+            let segments = responder.await;
+
+            let segments = segments.map(|segment| ConfirmedRequest::try_from(segment).unwrap());
+            assert_eq!(
+                segments
+                    .iter()
+                    .map(|s| (s.sequence_number, s.more_follows, s.proposed_window_size))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (Some(0), true, Some(4)),
+                    (Some(1), true, Some(4)),
+                    (Some(2), false, Some(4))
+                ]
+            );
+            assert!(segments.iter().all(|s| s.segmented_message));
+            let service_request: Vec<u8> = segments
+                .iter()
+                .flat_map(|s| s.service_request.clone())
+                .collect();
+            assert_eq!(service_request, request.encode_vec().unwrap());
+        });
+    }
+}
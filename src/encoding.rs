@@ -1,15 +1,277 @@
+pub mod binary;
+pub mod bit_string;
+pub mod date_time;
+pub mod event;
+pub mod object_identifier;
+pub mod object_status;
+pub mod object_type;
 mod parse;
+pub mod primitive;
+pub mod property_identifier;
+pub mod property_reference;
+pub mod value;
+pub use binary::*;
+pub use bit_string::*;
+pub use date_time::*;
+pub use event::*;
+pub use object_identifier::*;
+pub use object_status::*;
+pub use object_type::*;
+pub use primitive::*;
+pub use property_identifier::*;
+pub use property_reference::*;
+pub use value::*;
 
-#[allow(dead_code)]
+use crate::{Encode, Error};
+
+/// A BACnet tag (20.2.1) borrowing its content octets from the encoded buffer
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Tag<'a> {
     tag_number: TagNumber,
     lvt: LengthValueType,
     data: &'a [u8],
 }
+
+impl<'a> Tag<'a> {
+    pub fn application(tag: ApplicationTag, data: &'a [u8]) -> Self {
+        Self {
+            tag_number: TagNumber::Application(tag),
+            lvt: LengthValueType::Length(data.len() as u32),
+            data,
+        }
+    }
+
+    pub fn context(tag_number: u8, data: &'a [u8]) -> Self {
+        Self {
+            tag_number: TagNumber::Context(ContextTag::from(tag_number)),
+            lvt: LengthValueType::Length(data.len() as u32),
+            data,
+        }
+    }
+
+    /// Application tagged Null (20.2.2)
+    pub fn null() -> Self {
+        Self::application(ApplicationTag::Null, &[])
+    }
+
+    /// Application tagged Boolean, the value is carried in the LVT (20.2.3)
+    pub fn boolean(value: bool) -> Self {
+        Self {
+            tag_number: TagNumber::Application(ApplicationTag::Boolean),
+            lvt: LengthValueType::Value(value as u8),
+            data: &[],
+        }
+    }
+
+    pub fn opening(tag_number: u8) -> Self {
+        Self {
+            tag_number: TagNumber::Context(ContextTag::from(tag_number)),
+            lvt: LengthValueType::Opening,
+            data: &[],
+        }
+    }
+
+    pub fn closing(tag_number: u8) -> Self {
+        Self {
+            tag_number: TagNumber::Context(ContextTag::from(tag_number)),
+            lvt: LengthValueType::Closing,
+            data: &[],
+        }
+    }
+
+    /// Decode the tag at the start of `input`, returning it together with the remaining input
+    pub fn decode(input: &'a [u8]) -> crate::Result<(Self, &'a [u8])> {
+        match parse::parse_bacnet_tag(input) {
+            Ok((rest, tag)) => Ok((tag, rest)),
+            Err(_) => Err(Error::Truncated),
+        }
+    }
+
+    /// Content octets of an application tag, or an error if the tag is of another type
+    pub fn application_data(&self, tag: ApplicationTag) -> crate::Result<&'a [u8]> {
+        match self.tag_number {
+            TagNumber::Application(t) if t == tag => Ok(self.data),
+            t => Err(Error::InvalidTag(format!(
+                "Expected application tag {:?}, found {:?}",
+                tag, t
+            ))),
+        }
+    }
+
+    /// Content octets of a context tag, or an error if the tag has another tag number or class
+    pub fn context_data(&self, tag_number: u8) -> crate::Result<&'a [u8]> {
+        match (self.tag_number, self.lvt) {
+            (TagNumber::Context(t), LengthValueType::Length(_)) if u8::from(t) == tag_number => {
+                Ok(self.data)
+            }
+            (t, l) => Err(Error::InvalidTag(format!(
+                "Expected context tag {}, found {:?} {:?}",
+                tag_number, t, l
+            ))),
+        }
+    }
+
+    pub fn is_context(&self, tag_number: u8) -> bool {
+        matches!(self.tag_number, TagNumber::Context(t) if u8::from(t) == tag_number)
+            && matches!(self.lvt, LengthValueType::Length(_))
+    }
+
+    pub fn is_opening(&self, tag_number: u8) -> bool {
+        matches!(self.tag_number, TagNumber::Context(t) if u8::from(t) == tag_number)
+            && self.lvt == LengthValueType::Opening
+    }
+
+    pub fn is_closing(&self, tag_number: u8) -> bool {
+        matches!(self.tag_number, TagNumber::Context(t) if u8::from(t) == tag_number)
+            && self.lvt == LengthValueType::Closing
+    }
+
+    pub fn tag_number(&self) -> TagNumber {
+        self.tag_number
+    }
+
+    pub fn lvt(&self) -> LengthValueType {
+        self.lvt
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Decode the context tag `tag_number` at the start of `input`, returning its content octets
+/// together with the remaining input
+pub fn decode_context(input: &[u8], tag_number: u8) -> crate::Result<(&[u8], &[u8])> {
+    let (tag, rest) = Tag::decode(input)?;
+    Ok((tag.context_data(tag_number)?, rest))
+}
+
+/// Like `decode_context`, but the tag may be absent in which case the input is left untouched
+pub fn decode_optional_context(
+    input: &[u8],
+    tag_number: u8,
+) -> crate::Result<(Option<&[u8]>, &[u8])> {
+    match Tag::decode(input) {
+        Ok((tag, rest)) if tag.is_context(tag_number) => Ok((Some(tag.data), rest)),
+        _ => Ok((None, input)),
+    }
+}
+
+/// Skip the opening tag `tag_number` at the start of `input`
+pub fn decode_opening(input: &[u8], tag_number: u8) -> crate::Result<&[u8]> {
+    match Tag::decode(input)? {
+        (tag, rest) if tag.is_opening(tag_number) => Ok(rest),
+        (tag, _) => Err(Error::InvalidTag(format!(
+            "Expected opening tag {}, found {:?}",
+            tag_number, tag
+        ))),
+    }
+}
+
+/// Skip the closing tag `tag_number` at the start of `input`
+pub fn decode_closing(input: &[u8], tag_number: u8) -> crate::Result<&[u8]> {
+    match Tag::decode(input)? {
+        (tag, rest) if tag.is_closing(tag_number) => Ok(rest),
+        (tag, _) => Err(Error::InvalidTag(format!(
+            "Expected closing tag {}, found {:?}",
+            tag_number, tag
+        ))),
+    }
+}
+
+/// Decode the content enclosed by the opening and closing tag `tag_number` at the start of
+/// `input`, nested opening and closing tags are part of the content (20.2.1.3.2)
+pub fn decode_enclosed(input: &[u8], tag_number: u8) -> crate::Result<(&[u8], &[u8])> {
+    let content = decode_opening(input, tag_number)?;
+    let mut rest = content;
+    let mut depth = 0usize;
+    loop {
+        let (tag, next) = Tag::decode(rest)?;
+        match tag.lvt {
+            LengthValueType::Closing if depth == 0 => {
+                if !tag.is_closing(tag_number) {
+                    break;
+                }
+                let length = content.len() - rest.len();
+                return Ok((&content[..length], next));
+            }
+            LengthValueType::Opening => depth += 1,
+            LengthValueType::Closing => depth -= 1,
+            _ => (),
+        }
+        rest = next;
+    }
+    Err(Error::InvalidTag(format!(
+        "Mismatched closing tag for {}",
+        tag_number
+    )))
+}
+
+impl Encode for Tag<'_> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        // 20.2.1.1 Class
+        let (tag_number, class) = match self.tag_number {
+            TagNumber::Application(t) => (u8::from(t), 0b0000_0000),
+            TagNumber::Context(t) => (u8::from(t), 0b0000_1000),
+        };
+        // 20.2.1.3 Length/Value/Type
+        let (lvt, extended_length) = match self.lvt {
+            LengthValueType::Value(v) => (v & 0b0000_0111, None),
+            LengthValueType::Length(l @ 0..=4) => (l as u8, None),
+            LengthValueType::Length(l) => (0b101, Some(l)),
+            LengthValueType::Opening => (0b110, None),
+            LengthValueType::Closing => (0b111, None),
+        };
+        // 20.2.1.2 Tag Number
+        match tag_number {
+            t @ 0..=14 => writer.write_u8(t << 4 | class | lvt)?,
+            t @ 15..=255 => {
+                writer.write_u8(0b1111_0000 | class | lvt)?;
+                writer.write_u8(t)?;
+            }
+        }
+        // 20.2.1.3.1 Primitive Data
+        match extended_length {
+            None => (),
+            Some(l @ 5..=253) => writer.write_u8(l as u8)?,
+            Some(l @ 254..=65535) => {
+                writer.write_u8(254)?;
+                writer.write_u16(l as u16)?;
+            }
+            Some(l) => {
+                writer.write_u8(255)?;
+                writer.write_u32(l)?;
+            }
+        }
+        writer.write_all(self.data)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 1; // Tag Number, Class and Length/Value/Type
+        l += match self.tag_number {
+            TagNumber::Application(t) if u8::from(t) >= 15 => 1, // Extended Tag Number
+            TagNumber::Context(t) if u8::from(t) >= 15 => 1,
+            _ => 0,
+        };
+        l += match self.lvt {
+            LengthValueType::Length(5..=253) => 1, // Extended Length
+            LengthValueType::Length(254..=65535) => 3,
+            LengthValueType::Length(65536..) => 5,
+            _ => 0,
+        };
+        l += self.data.len(); // Content
+        l
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TagNumber {
     Application(ApplicationTag),
     Context(ContextTag),
 }
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum LengthValueType {
     Length(u32),
     Value(u8),
@@ -17,6 +279,7 @@ pub enum LengthValueType {
     Closing,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ApplicationTag {
     Null,                   //= 0,
     Boolean,                //= 1,
@@ -79,6 +342,7 @@ impl From<ApplicationTag> for u8 {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ContextTag {
     Other(u8),
 }
@@ -97,3 +361,193 @@ impl From<ContextTag> for u8 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Encode, Error};
+
+    #[test]
+    fn test_encode_context_tag_3_length_4() {
+        let data = [0x00, 0xc0, 0x00, 0x0f];
+        let tag = Tag::context(3, &data);
+
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(encoded, vec![0x3c, 0x00, 0xc0, 0x00, 0x0f]);
+        assert_eq!(encoded.len(), tag.len());
+
+        let (decoded, rest) = Tag::decode(&encoded).expect("Decode tag");
+        assert_eq!(decoded, tag);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_encode_application_tag_length_10() {
+        let data = b"\x00123456789";
+        let tag = Tag::application(ApplicationTag::CharacterString, data);
+
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(&encoded[..2], &[0x75, 10]);
+        assert_eq!(&encoded[2..], data);
+        assert_eq!(encoded.len(), tag.len());
+
+        let (decoded, rest) = Tag::decode(&encoded).expect("Decode tag");
+        assert_eq!(
+            decoded.tag_number(),
+            TagNumber::Application(ApplicationTag::CharacterString)
+        );
+        assert_eq!(decoded.lvt(), LengthValueType::Length(10));
+        assert_eq!(decoded.data(), data);
+        assert!(rest.is_empty());
+    }
+
+    fn assert_length_round_trip(length: usize, header: &[u8]) {
+        let data = vec![0u8; length];
+        let tag = Tag::application(ApplicationTag::OctetString, &data);
+
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(&encoded[..header.len()], header);
+        assert_eq!(encoded.len(), header.len() + length);
+        assert_eq!(encoded.len(), tag.len());
+
+        let (decoded, rest) = Tag::decode(&encoded).expect("Decode tag");
+        assert_eq!(decoded.lvt(), LengthValueType::Length(length as u32));
+        assert_eq!(decoded.data().len(), length);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_encode_length_4() {
+        assert_length_round_trip(4, &[0x64]);
+    }
+
+    #[test]
+    fn test_encode_length_5() {
+        assert_length_round_trip(5, &[0x65, 5]);
+    }
+
+    #[test]
+    fn test_encode_length_253() {
+        assert_length_round_trip(253, &[0x65, 253]);
+    }
+
+    #[test]
+    fn test_encode_length_254() {
+        assert_length_round_trip(254, &[0x65, 254, 0x00, 0xfe]);
+    }
+
+    #[test]
+    fn test_encode_octet_string_300() {
+        assert_length_round_trip(300, &[0x65, 254, 0x01, 0x2c]);
+    }
+
+    #[test]
+    fn test_encode_length_65536() {
+        assert_length_round_trip(65536, &[0x65, 255, 0x00, 0x01, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_context_tag_14_inline() {
+        let tag = Tag::context(14, &[0x48]);
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(encoded, vec![0xe9, 0x48]);
+        assert_eq!(encoded.len(), tag.len());
+        assert_eq!(Tag::decode(&encoded).unwrap().0, tag);
+    }
+
+    #[test]
+    fn test_encode_context_tag_15_extended() {
+        let tag = Tag::context(15, &[0x48]);
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(encoded, vec![0xf9, 15, 0x48]);
+        assert_eq!(encoded.len(), tag.len());
+        assert_eq!(Tag::decode(&encoded).unwrap().0, tag);
+    }
+
+    #[test]
+    fn test_encode_context_tag_200_extended() {
+        let tag = Tag::context(200, &[0x01, 0x00]);
+        let encoded = tag.encode_vec().expect("Write tag to buffer");
+        assert_eq!(encoded, vec![0xfa, 200, 0x01, 0x00]);
+        assert_eq!(encoded.len(), tag.len());
+        assert_eq!(Tag::decode(&encoded).unwrap().0, tag);
+
+        let encoded = Tag::opening(200).encode_vec().unwrap();
+        assert_eq!(encoded, vec![0xfe, 200]);
+        assert_eq!(Tag::decode(&encoded).unwrap().0, Tag::opening(200));
+    }
+
+    #[test]
+    fn test_encode_opening_and_closing_tag() {
+        assert_eq!(Tag::opening(3).encode_vec().unwrap(), vec![0x3e]);
+        assert_eq!(Tag::closing(3).encode_vec().unwrap(), vec![0x3f]);
+    }
+
+    #[test]
+    fn test_decode_sequence_of_tags() {
+        let input = [0x09, 0x01, 0x3e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x3f];
+
+        let (tag, rest) = Tag::decode(&input).unwrap();
+        assert_eq!(tag, Tag::context(0, &[0x01]));
+        let (tag, rest) = Tag::decode(rest).unwrap();
+        assert_eq!(tag, Tag::opening(3));
+        let (tag, rest) = Tag::decode(rest).unwrap();
+        assert_eq!(
+            tag,
+            Tag::application(ApplicationTag::Real, &[0x42, 0x90, 0x00, 0x00])
+        );
+        let (tag, rest) = Tag::decode(rest).unwrap();
+        assert_eq!(tag, Tag::closing(3));
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_decode_context_helpers() {
+        let input = [0x09, 0x01, 0x3e, 0x21, 0x48, 0x3f];
+
+        let (data, rest) = decode_context(&input, 0).unwrap();
+        assert_eq!(data, &[0x01]);
+        let (data, rest) = decode_optional_context(rest, 1).unwrap();
+        assert_eq!(data, None);
+        assert!(decode_context(rest, 3).is_err());
+        let rest = decode_opening(rest, 3).unwrap();
+        let (_, rest) = Tag::decode(rest).unwrap();
+        assert!(decode_opening(rest, 3).is_err());
+        let rest = decode_closing(rest, 3).unwrap();
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_decode_enclosed() {
+        // [3] { [0] { REAL } [1] 1 } [4] 8
+        let input = [
+            0x3e, 0x0e, 0x44, 0x42, 0x90, 0, 0, 0x0f, 0x19, 0x01, 0x3f, 0x49, 0x08,
+        ];
+
+        let (content, rest) = decode_enclosed(&input, 3).unwrap();
+        assert_eq!(content, &input[1..10]);
+        assert_eq!(rest, &[0x49, 0x08]);
+
+        assert!(decode_enclosed(&input[..10], 3).is_err());
+        assert!(decode_enclosed(&[0x3e, 0x21, 0x01, 0x4f], 3).is_err());
+        assert!(decode_enclosed(&input, 4).is_err());
+    }
+
+    #[test]
+    fn test_decode_truncated_tag() {
+        let err = Tag::decode(&[0x44, 0x42, 0x90]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+        let err = Tag::decode(&[]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_unexpected_tag() {
+        let err = decode_context(&[0x19, 0x55], 0).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+        let err = decode_opening(&[0x3f], 3).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+        let err = decode_enclosed(&[0x3e, 0x4f], 3).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+    }
+}
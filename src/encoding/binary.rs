@@ -0,0 +1,156 @@
+use crate::Error;
+use crate::encoding::{ApplicationTag, ApplicationValue, PropertyValue};
+use alloc::string::String;
+
+/// BACnetBinaryPV (21), the present-value of a binary object
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BinaryPV {
+    Inactive, // = 0;
+    Active,   // = 1;
+}
+
+impl TryFrom<u32> for BinaryPV {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Inactive),
+            1 => Ok(Self::Active),
+            v => Err(Error::InvalidValue(format!("Invalid binary value: {}", v))),
+        }
+    }
+}
+
+impl From<BinaryPV> for u32 {
+    fn from(value: BinaryPV) -> Self {
+        match value {
+            BinaryPV::Inactive => 0,
+            BinaryPV::Active => 1,
+        }
+    }
+}
+
+/// A binary value is Enumerated, devices encoding it as Unsigned are accepted as well
+impl TryFrom<&ApplicationValue> for BinaryPV {
+    type Error = Error;
+
+    fn try_from(value: &ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Enumerated(v) | ApplicationValue::Unsigned(v) => Self::try_from(*v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::Enumerated,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl From<BinaryPV> for ApplicationValue {
+    fn from(value: BinaryPV) -> Self {
+        Self::Enumerated(value.into())
+    }
+}
+
+/// Values read from a binary object, such as for showing its state
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinarySnapshot {
+    pub present_value: BinaryPV,
+    /// Description of the active state, from the active-text property
+    pub active_text: Option<String>,
+    /// Description of the inactive state, from the inactive-text property
+    pub inactive_text: Option<String>,
+}
+
+impl BinarySnapshot {
+    pub fn new(present_value: BinaryPV) -> Self {
+        Self {
+            present_value,
+            active_text: None,
+            inactive_text: None,
+        }
+    }
+
+    /// Decode the values read from the active-text and inactive-text properties
+    pub fn texts(
+        mut self,
+        active_text: &PropertyValue,
+        inactive_text: &PropertyValue,
+    ) -> crate::Result<Self> {
+        self.active_text = Some(decode_text(active_text)?);
+        self.inactive_text = Some(decode_text(inactive_text)?);
+        Ok(self)
+    }
+
+    /// Text of the active or the inactive state, "active" or "inactive" without a text
+    pub fn active_inactive_text(&self, present_value: bool) -> &str {
+        match present_value {
+            true => self.active_text.as_deref().unwrap_or("active"),
+            false => self.inactive_text.as_deref().unwrap_or("inactive"),
+        }
+    }
+
+    /// Text of the present value
+    pub fn present_value_text(&self) -> &str {
+        self.active_inactive_text(self.present_value == BinaryPV::Active)
+    }
+}
+
+fn decode_text(value: &PropertyValue) -> crate::Result<String> {
+    match value.single() {
+        Some(value) => String::try_from(value.clone()),
+        None => Err(Error::InvalidValue(format!(
+            "Not a single text: {:?}",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+    use crate::encoding::PropertyValue;
+
+    #[test]
+    fn test_binary_present_value() {
+        let enumerated = PropertyValue::decode_slice(&[0x91, 0x01]).unwrap();
+        let unsigned = PropertyValue::decode_slice(&[0x21, 0x01]).unwrap();
+        for value in [enumerated, unsigned] {
+            let value = value.single().unwrap();
+            assert_eq!(BinaryPV::try_from(value).unwrap(), BinaryPV::Active);
+        }
+        assert_eq!(
+            BinaryPV::try_from(&ApplicationValue::Unsigned(0)).unwrap(),
+            BinaryPV::Inactive
+        );
+        assert_eq!(
+            ApplicationValue::from(BinaryPV::Active),
+            ApplicationValue::Enumerated(1)
+        );
+
+        let err = BinaryPV::try_from(&ApplicationValue::Unsigned(2)).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = BinaryPV::try_from(&ApplicationValue::Real(1.0)).unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }), "{:?}", err);
+    }
+
+    #[test]
+    fn test_binary_snapshot_texts() {
+        let active_text = PropertyValue::decode_slice(&[0x73, 0x00, 0x4f, 0x6e]).unwrap();
+        let inactive_text = PropertyValue::decode_slice(&[0x74, 0x00, 0x4f, 0x66, 0x66]).unwrap();
+
+        let snapshot = BinarySnapshot::new(BinaryPV::Active)
+            .texts(&active_text, &inactive_text)
+            .unwrap();
+        assert_eq!(snapshot.active_inactive_text(true), "On");
+        assert_eq!(snapshot.active_inactive_text(false), "Off");
+        assert_eq!(snapshot.present_value_text(), "On");
+
+        let snapshot = BinarySnapshot::new(BinaryPV::Inactive);
+        assert_eq!(snapshot.present_value_text(), "inactive");
+        let err = snapshot
+            .texts(&active_text, &PropertyValue::Values(vec![]))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
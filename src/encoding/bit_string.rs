@@ -0,0 +1,126 @@
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// Bit String contents (20.2.10), the first bit is the most significant bit of the first octet
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitString {
+    bits: Vec<bool>,
+}
+
+impl BitString {
+    pub fn new(bits: Vec<bool>) -> Self {
+        Self { bits }
+    }
+
+    pub fn bits(&self) -> &[bool] {
+        &self.bits
+    }
+
+    /// Value of the bit at `index`, bits beyond the end of the string are false
+    pub fn get(&self, index: usize) -> bool {
+        self.bits.get(index).copied().unwrap_or(false)
+    }
+
+    /// Number of unused bits in the final octet
+    fn unused_bits(&self) -> u8 {
+        ((8 - self.bits.len() % 8) % 8) as u8
+    }
+}
+
+impl From<Vec<bool>> for BitString {
+    fn from(bits: Vec<bool>) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl Encode for BitString {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(self.unused_bits())?;
+        for chunk in self.bits.chunks(8) {
+            let octet = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |o, (i, &b)| o | ((b as u8) << (7 - i)));
+            writer.write_u8(octet)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        1 + self.bits.len().div_ceil(8)
+    }
+}
+
+impl Decode for BitString {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let unused = reader.read_u8()?;
+        let mut octets = Vec::new();
+        reader.read_to_end(&mut octets)?;
+
+        if unused > 7 || (octets.is_empty() && unused != 0) {
+            return Err(Error::InvalidValue(format!(
+                "Invalid number of unused bits: {}",
+                unused
+            )));
+        }
+
+        let length = octets.len() * 8 - unused as usize;
+        let bits = (0..length)
+            .map(|i| octets[i / 8] & (0b1000_0000 >> (i % 8)) != 0)
+            .collect();
+        Ok(Self { bits })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationTag, Tag};
+
+    #[test]
+    fn test_encode_bit_string_10_bits() {
+        let bits = vec![
+            true, false, true, false, true, false, false, false, true, true,
+        ];
+        let bit_string = BitString::new(bits.clone());
+
+        let data = bit_string.encode_vec().unwrap();
+        assert_eq!(data, vec![6, 0b1010_1000, 0b1100_0000]);
+        assert_eq!(data.len(), bit_string.len());
+
+        let encoded = Tag::application(ApplicationTag::BitString, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::BitString).unwrap();
+        let decoded = BitString::decode_slice(data).unwrap();
+        assert_eq!(decoded, bit_string);
+        assert_eq!(decoded.bits(), &bits[..]);
+    }
+
+    #[test]
+    fn test_decode_status_flags() {
+        // in-alarm, fault, overridden, out-of-service
+        let bit_string = BitString::decode_slice(&[0x04, 0b0100_0000]).unwrap();
+        assert_eq!(bit_string.bits(), &[false, true, false, false]);
+        assert!(bit_string.get(1));
+        assert!(!bit_string.get(10));
+    }
+
+    #[test]
+    fn test_empty_bit_string() {
+        let bit_string = BitString::default();
+        assert_eq!(bit_string.encode_vec().unwrap(), vec![0x00]);
+        assert_eq!(BitString::decode_slice(&[0x00]).unwrap(), bit_string);
+    }
+
+    #[test]
+    fn test_decode_invalid_unused_bits() {
+        assert!(BitString::decode_slice(&[0x08, 0x00]).is_err());
+        assert!(BitString::decode_slice(&[0x03]).is_err());
+        assert!(BitString::decode_slice(&[]).is_err());
+    }
+}
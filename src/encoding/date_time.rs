@@ -0,0 +1,213 @@
+use crate::{Decode, Encode, Error};
+
+/// Value of a Date or Time field that is unspecified
+pub const UNSPECIFIED: u8 = 0xFF;
+
+/// Date contents (20.2.12), any field may be `UNSPECIFIED`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    /// Years since 1900
+    pub year: u8,
+    /// 1..=12, 13 = odd months, 14 = even months
+    pub month: u8,
+    /// 1..=31, 32 = last day of month, 33 = odd days, 34 = even days
+    pub day: u8,
+    /// 1 = Monday ..= 7 = Sunday
+    pub day_of_week: u8,
+}
+
+impl Date {
+    /// Date of `year` AD, which must be within 1900..=2154
+    pub fn new(year: u16, month: u8, day: u8, day_of_week: u8) -> crate::Result<Self> {
+        let year = match year.checked_sub(1900) {
+            Some(y) if y < UNSPECIFIED as u16 => y as u8,
+            _ => return Err(Error::InvalidValue(format!("Year out of range: {}", year))),
+        };
+        Ok(Self {
+            year,
+            month,
+            day,
+            day_of_week,
+        })
+    }
+
+    /// Date with every field unspecified
+    pub fn unspecified() -> Self {
+        Self {
+            year: UNSPECIFIED,
+            month: UNSPECIFIED,
+            day: UNSPECIFIED,
+            day_of_week: UNSPECIFIED,
+        }
+    }
+
+    /// Year AD, or None if unspecified
+    pub fn full_year(&self) -> Option<u16> {
+        match self.year {
+            UNSPECIFIED => None,
+            y => Some(1900 + y as u16),
+        }
+    }
+}
+
+impl Encode for Date {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(self.year)?;
+        writer.write_u8(self.month)?;
+        writer.write_u8(self.day)?;
+        writer.write_u8(self.day_of_week)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        4
+    }
+}
+
+impl Decode for Date {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        Ok(Self {
+            year: reader.read_u8()?,
+            month: reader.read_u8()?,
+            day: reader.read_u8()?,
+            day_of_week: reader.read_u8()?,
+        })
+    }
+}
+
+/// Time contents (20.2.13), any field may be `UNSPECIFIED`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub hundredths: u8,
+}
+
+impl Time {
+    pub fn new(hour: u8, minute: u8, second: u8, hundredths: u8) -> Self {
+        Self {
+            hour,
+            minute,
+            second,
+            hundredths,
+        }
+    }
+
+    /// Time with every field unspecified
+    pub fn unspecified() -> Self {
+        Self::new(UNSPECIFIED, UNSPECIFIED, UNSPECIFIED, UNSPECIFIED)
+    }
+}
+
+impl Encode for Time {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(self.hour)?;
+        writer.write_u8(self.minute)?;
+        writer.write_u8(self.second)?;
+        writer.write_u8(self.hundredths)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        4
+    }
+}
+
+impl Decode for Time {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        Ok(Self {
+            hour: reader.read_u8()?,
+            minute: reader.read_u8()?,
+            second: reader.read_u8()?,
+            hundredths: reader.read_u8()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationTag, Tag};
+
+    fn round_trip_date(date: Date) -> Vec<u8> {
+        let data = date.encode_vec().unwrap();
+        let encoded = Tag::application(ApplicationTag::Date, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::Date).unwrap();
+        assert_eq!(Date::decode_slice(data).unwrap(), date);
+        encoded
+    }
+
+    fn round_trip_time(time: Time) -> Vec<u8> {
+        let data = time.encode_vec().unwrap();
+        let encoded = Tag::application(ApplicationTag::Time, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::Time).unwrap();
+        assert_eq!(Time::decode_slice(data).unwrap(), time);
+        encoded
+    }
+
+    #[test]
+    fn test_time_specific_value() {
+        let time = Time::new(23, 59, 59, 99);
+        assert_eq!(round_trip_time(time), vec![0xb4, 23, 59, 59, 99]);
+    }
+
+    #[test]
+    fn test_time_wildcard_minute_and_second() {
+        let time = Time::new(17, UNSPECIFIED, UNSPECIFIED, 0);
+        assert_eq!(round_trip_time(time), vec![0xb4, 17, 0xff, 0xff, 0x00]);
+        assert_eq!(
+            round_trip_time(Time::unspecified()),
+            vec![0xb4, 0xff, 0xff, 0xff, 0xff]
+        );
+    }
+
+    #[test]
+    fn test_date_specific_value() {
+        let date = Date::new(1991, 1, 24, 4).unwrap();
+        assert_eq!(round_trip_date(date), vec![0xa4, 0x5b, 0x01, 0x18, 0x04]);
+        assert_eq!(date.full_year(), Some(1991));
+    }
+
+    #[test]
+    fn test_date_year_out_of_range() {
+        assert_eq!(Date::new(1900, 1, 1, 1).unwrap().year, 0);
+        assert_eq!(Date::new(2154, 1, 1, 1).unwrap().year, 254);
+        let err = Date::new(1899, 12, 31, 7).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = Date::new(2155, 1, 1, 1).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_date_unspecified() {
+        let date = Date::unspecified();
+        assert_eq!(round_trip_date(date), vec![0xa4, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(date.full_year(), None);
+    }
+
+    #[test]
+    fn test_date_pattern() {
+        let date = Date::decode_slice(&[0x5b, 0xff, 0x18, 0xff]).unwrap();
+        assert_eq!(date.month, UNSPECIFIED);
+        assert_eq!(date.day, 24);
+        round_trip_date(date);
+    }
+
+    #[test]
+    fn test_decode_date_truncated() {
+        assert!(Date::decode_slice(&[0x5b, 0x01, 0x18]).is_err());
+    }
+}
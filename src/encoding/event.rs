@@ -0,0 +1,326 @@
+use crate::encoding::{
+    ApplicationValue, BitString, Date, PropertyValue, Tag, Time, decode_application_value,
+    decode_enclosed, decode_unsigned, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{string::String, vec::Vec};
+
+/// BACnetEventState (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EventState {
+    Normal,          // = 0;
+    Fault,           // = 1;
+    Offnormal,       // = 2;
+    HighLimit,       // = 3;
+    LowLimit,        // = 4;
+    LifeSafetyAlarm, // = 5;
+    /// Vendor specific event state, 64 to 65535
+    Proprietary(u16),
+}
+
+impl TryFrom<u32> for EventState {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Fault),
+            2 => Ok(Self::Offnormal),
+            3 => Ok(Self::HighLimit),
+            4 => Ok(Self::LowLimit),
+            5 => Ok(Self::LifeSafetyAlarm),
+            v @ 64..=65535 => Ok(Self::Proprietary(v as u16)),
+            v => Err(Error::InvalidValue(format!(
+                "Event state not supported: {}",
+                v
+            ))),
+        }
+    }
+}
+
+impl From<EventState> for u32 {
+    fn from(value: EventState) -> Self {
+        match value {
+            EventState::Normal => 0,
+            EventState::Fault => 1,
+            EventState::Offnormal => 2,
+            EventState::HighLimit => 3,
+            EventState::LowLimit => 4,
+            EventState::LifeSafetyAlarm => 5,
+            EventState::Proprietary(v) => v as u32,
+        }
+    }
+}
+
+/// BACnetNotifyType (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NotifyType {
+    Alarm,           // = 0;
+    Event,           // = 1;
+    AckNotification, // = 2;
+}
+
+impl TryFrom<u32> for NotifyType {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Alarm),
+            1 => Ok(Self::Event),
+            2 => Ok(Self::AckNotification),
+            v => Err(Error::InvalidValue(format!(
+                "Notify type not supported: {}",
+                v
+            ))),
+        }
+    }
+}
+
+impl From<NotifyType> for u32 {
+    fn from(value: NotifyType) -> Self {
+        match value {
+            NotifyType::Alarm => 0,
+            NotifyType::Event => 1,
+            NotifyType::AckNotification => 2,
+        }
+    }
+}
+
+/// BACnetEventTransitionBits (21), one flag per transition into an event state
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventTransitionBits {
+    pub to_offnormal: bool,
+    pub to_fault: bool,
+    pub to_normal: bool,
+}
+
+impl EventTransitionBits {
+    pub fn new(to_offnormal: bool, to_fault: bool, to_normal: bool) -> Self {
+        Self {
+            to_offnormal,
+            to_fault,
+            to_normal,
+        }
+    }
+}
+
+impl From<&BitString> for EventTransitionBits {
+    fn from(bits: &BitString) -> Self {
+        Self::new(bits.get(0), bits.get(1), bits.get(2))
+    }
+}
+
+impl From<EventTransitionBits> for BitString {
+    fn from(bits: EventTransitionBits) -> Self {
+        BitString::new(vec![bits.to_offnormal, bits.to_fault, bits.to_normal])
+    }
+}
+
+/// Transitions an object with intrinsic reporting reports, its event-enable property
+pub type EventEnable = EventTransitionBits;
+
+/// BACnetLimitEnable (21), the limits an object with intrinsic reporting checks
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LimitEnable {
+    pub low_limit: bool,
+    pub high_limit: bool,
+}
+
+impl LimitEnable {
+    pub fn new(low_limit: bool, high_limit: bool) -> Self {
+        Self {
+            low_limit,
+            high_limit,
+        }
+    }
+}
+
+impl From<&BitString> for LimitEnable {
+    fn from(bits: &BitString) -> Self {
+        Self::new(bits.get(0), bits.get(1))
+    }
+}
+
+impl From<LimitEnable> for BitString {
+    fn from(limits: LimitEnable) -> Self {
+        BitString::new(vec![limits.low_limit, limits.high_limit])
+    }
+}
+
+/// Decode the event-message-texts property of an object, the texts of its latest to-offnormal,
+/// to-fault and to-normal transitions
+pub fn decode_event_message_texts(value: &PropertyValue) -> crate::Result<[String; 3]> {
+    match value {
+        PropertyValue::Values(values) => match &values[..] {
+            [
+                ApplicationValue::CharacterString(to_offnormal),
+                ApplicationValue::CharacterString(to_fault),
+                ApplicationValue::CharacterString(to_normal),
+            ] => Ok([to_offnormal.clone(), to_fault.clone(), to_normal.clone()]),
+            v => Err(Error::InvalidValue(format!(
+                "Not three event message texts: {:?}",
+                v
+            ))),
+        },
+        v => Err(Error::InvalidValue(format!(
+            "Not three event message texts: {:?}",
+            v
+        ))),
+    }
+}
+
+/// BACnetTimeStamp (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u16),
+    DateTime(Date, Time),
+}
+
+impl Encode for TimeStamp {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        match self {
+            Self::Time(time) => Tag::context(0, &time.encode_vec()?).encode(writer),
+            Self::SequenceNumber(n) => Tag::context(1, &encode_unsigned(*n as u32)).encode(writer),
+            Self::DateTime(date, time) => {
+                Tag::opening(2).encode(writer)?;
+                ApplicationValue::Date(*date).encode(writer)?;
+                ApplicationValue::Time(*time).encode(writer)?;
+                Tag::closing(2).encode(writer)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Time(_) => 1 + 4,
+            Self::SequenceNumber(n) => 1 + encode_unsigned(*n as u32).len(),
+            Self::DateTime(_, _) => 1 + 5 + 5 + 1,
+        }
+    }
+}
+
+impl Decode for TimeStamp {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Ok(decode_time_stamp(&data)?.0)
+    }
+}
+
+/// Decode the time stamp at the start of `input`, returning it together with the remaining
+/// input
+pub fn decode_time_stamp(input: &[u8]) -> crate::Result<(TimeStamp, &[u8])> {
+    let (tag, rest) = Tag::decode(input)?;
+    if tag.is_context(0) {
+        return Ok((TimeStamp::Time(Time::decode_slice(tag.data())?), rest));
+    }
+    if tag.is_context(1) {
+        let n = decode_unsigned(tag.data())?;
+        let n = u16::try_from(n)
+            .map_err(|_| Error::InvalidValue(format!("Sequence number out of range: {}", n)))?;
+        return Ok((TimeStamp::SequenceNumber(n), rest));
+    }
+    let (date_time, rest) = decode_enclosed(input, 2)?;
+    let (date, remaining) = decode_application_value(date_time)?;
+    let (time, _) = decode_application_value(remaining)?;
+    match (date, time) {
+        (ApplicationValue::Date(date), ApplicationValue::Time(time)) => {
+            Ok((TimeStamp::DateTime(date, time), rest))
+        }
+        v => Err(Error::InvalidValue(format!("Invalid date-time: {:?}", v))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_stamp_choices() {
+        let cases = [
+            (TimeStamp::Time(Time::new(15, 35, 0, 20)), "0c0f230014"),
+            (TimeStamp::SequenceNumber(300), "1a012c"),
+            (
+                TimeStamp::DateTime(Date::new(2024, 3, 15, 5).unwrap(), Time::new(15, 45, 0, 0)),
+                "2ea47c030f05b40f2d00002f",
+            ),
+        ];
+        for (time_stamp, expected) in cases {
+            let data = time_stamp.encode_vec().unwrap();
+            assert_eq!(data, hex::decode(expected).unwrap());
+            assert_eq!(data.len(), time_stamp.len());
+            assert_eq!(TimeStamp::decode_slice(&data).unwrap(), time_stamp);
+        }
+    }
+
+    #[test]
+    fn test_decode_time_stamp_invalid() {
+        let err = TimeStamp::decode_slice(&[0x1b, 0x01, 0x00, 0x00]).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = TimeStamp::decode_slice(&[0x39, 0x00]).unwrap_err();
+        assert!(matches!(err, Error::InvalidTag(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_event_transition_bits() {
+        let bits = EventTransitionBits::new(false, true, true);
+        let bit_string = BitString::from(bits);
+        assert_eq!(bit_string.encode_vec().unwrap(), vec![0x05, 0x60]);
+        assert_eq!(EventTransitionBits::from(&bit_string), bits);
+        assert_eq!(EventState::try_from(3).unwrap(), EventState::HighLimit);
+        assert!(NotifyType::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_limit_enable_and_event_enable() {
+        let limits = LimitEnable::new(false, true);
+        let bit_string = BitString::from(limits);
+        assert_eq!(bit_string.encode_vec().unwrap(), vec![0x06, 0x40]);
+        assert_eq!(LimitEnable::from(&bit_string), limits);
+
+        let enable = EventEnable::new(true, false, true);
+        let bit_string = BitString::from(enable);
+        assert_eq!(bit_string.encode_vec().unwrap(), vec![0x05, 0xa0]);
+        assert_eq!(EventEnable::from(&bit_string), enable);
+    }
+
+    #[test]
+    fn test_decode_event_message_texts() {
+        let texts = [
+            "Température haute",
+            "Capteur défaillant",
+            "Retour à la normale",
+        ];
+        let mut data = Vec::new();
+        for text in texts {
+            ApplicationValue::CharacterString(text.to_string())
+                .encode(&mut data)
+                .unwrap();
+        }
+        let value = PropertyValue::decode_slice(&data).unwrap();
+        assert_eq!(decode_event_message_texts(&value).unwrap(), texts);
+
+        let value = PropertyValue::Values(vec![ApplicationValue::CharacterString(
+            "Alarme".to_string(),
+        )]);
+        let err = decode_event_message_texts(&value).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_event_state_proprietary() {
+        assert_eq!(
+            EventState::try_from(64).unwrap(),
+            EventState::Proprietary(64)
+        );
+        assert_eq!(u32::from(EventState::Proprietary(65535)), 65535);
+        let err = EventState::try_from(6).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = EventState::try_from(65536).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+}
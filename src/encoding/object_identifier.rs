@@ -0,0 +1,211 @@
+use crate::encoding::ObjectType;
+use crate::{Decode, Encode, Error};
+
+/// Largest instance number of an object identifier, also used as wildcard instance
+pub const MAX_INSTANCE: u32 = 0x3F_FFFF;
+
+/// Largest object type of an object identifier
+pub const MAX_OBJECT_TYPE: u16 = 0x3FF;
+
+/// BACnetObjectIdentifier contents (20.2.14), 10 bits object type and 22 bits instance number
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectIdentifier {
+    object_type: ObjectType,
+    instance: u32,
+}
+
+impl ObjectIdentifier {
+    /// Identifier of `instance` of `object_type`, rejecting object types that don't encode as
+    /// themselves, such as `Proprietary(8)`
+    pub fn new(object_type: ObjectType, instance: u32) -> crate::Result<Self> {
+        if !ObjectType::try_from(u16::from(object_type)).is_ok_and(|t| t == object_type) {
+            return Err(Error::InvalidValue(format!(
+                "Invalid object type: {:?}",
+                object_type
+            )));
+        }
+        if instance > MAX_INSTANCE {
+            return Err(Error::InvalidValue(format!(
+                "Instance number out of range: {}",
+                instance
+            )));
+        }
+        Ok(Self {
+            object_type,
+            instance,
+        })
+    }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    pub fn instance(&self) -> u32 {
+        self.instance
+    }
+
+    /// Whether this identifies a Device object
+    pub fn is_device(&self) -> bool {
+        self.object_type == ObjectType::Device
+    }
+}
+
+impl From<u32> for ObjectIdentifier {
+    fn from(value: u32) -> Self {
+        let object_type = (value >> 22) as u16;
+        Self {
+            object_type: ObjectType::try_from(object_type)
+                .unwrap_or(ObjectType::Proprietary(object_type)),
+            instance: value & MAX_INSTANCE,
+        }
+    }
+}
+
+impl From<ObjectIdentifier> for u32 {
+    fn from(value: ObjectIdentifier) -> Self {
+        (u16::from(value.object_type) as u32) << 22 | value.instance
+    }
+}
+
+/// Formatted as "object-type,instance", e.g. "analog-input,1"
+impl core::fmt::Display for ObjectIdentifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{},{}", self.object_type, self.instance)
+    }
+}
+
+impl core::str::FromStr for ObjectIdentifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (object_type, instance) = s
+            .split_once(',')
+            .ok_or_else(|| Error::InvalidValue(format!("Expected object-type,instance: {}", s)))?;
+        let instance = instance.trim().parse().map_err(|e| {
+            Error::InvalidValue(format!("Invalid instance number {}: {}", instance, e))
+        })?;
+        Self::new(object_type.trim().parse()?, instance)
+    }
+}
+
+impl Encode for ObjectIdentifier {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u32(u32::from(*self))
+    }
+
+    fn len(&self) -> usize {
+        4
+    }
+}
+
+impl Decode for ObjectIdentifier {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        Ok(Self::from(reader.read_u32()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationTag, Tag};
+
+    fn round_trip(object_identifier: ObjectIdentifier) -> Vec<u8> {
+        let data = object_identifier.encode_vec().unwrap();
+        let encoded = Tag::application(ApplicationTag::BACnetObjectIdentifier, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag
+            .application_data(ApplicationTag::BACnetObjectIdentifier)
+            .unwrap();
+        assert_eq!(
+            ObjectIdentifier::decode_slice(data).unwrap(),
+            object_identifier
+        );
+        encoded
+    }
+
+    #[test]
+    fn test_object_identifier_device_260001() {
+        let encoded = round_trip(ObjectIdentifier::new(ObjectType::Device, 260001).unwrap());
+        assert_eq!(encoded, vec![0xc4, 0x02, 0x03, 0xf7, 0xa1]);
+    }
+
+    #[test]
+    fn test_object_identifier_is_device() {
+        let object_identifier = ObjectIdentifier::decode_slice(&[0x02, 0x03, 0xf7, 0xa1]).unwrap();
+        assert_eq!(object_identifier.object_type(), ObjectType::Device);
+        assert!(object_identifier.is_device());
+        assert!(
+            !ObjectIdentifier::new(ObjectType::AnalogInput, 260001)
+                .unwrap()
+                .is_device()
+        );
+    }
+
+    #[test]
+    fn test_object_identifier_analog_input_0() {
+        let encoded = round_trip(ObjectIdentifier::new(ObjectType::AnalogInput, 0).unwrap());
+        assert_eq!(encoded, vec![0xc4, 0x00, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_object_identifier_binary_input_15() {
+        let object_identifier = ObjectIdentifier::decode_slice(&[0x00, 0xc0, 0x00, 0x0f]).unwrap();
+        assert_eq!(object_identifier.object_type(), ObjectType::BinaryInput);
+        assert_eq!(object_identifier.instance(), 15);
+    }
+
+    #[test]
+    fn test_object_identifier_out_of_range() {
+        assert!(ObjectIdentifier::new(ObjectType::Device, MAX_INSTANCE).is_ok());
+        assert!(ObjectIdentifier::new(ObjectType::Device, MAX_INSTANCE + 1).is_err());
+        assert!(ObjectIdentifier::new(ObjectType::Proprietary(MAX_OBJECT_TYPE + 1), 0).is_err());
+    }
+
+    #[test]
+    fn test_object_identifier_invalid_type() {
+        assert!(ObjectIdentifier::new(ObjectType::Proprietary(8), 1).is_err());
+        assert!(ObjectIdentifier::new(ObjectType::Proprietary(127), 1).is_err());
+        assert!(ObjectIdentifier::new(ObjectType::Reserved(8), 1).is_err());
+        assert!(ObjectIdentifier::new(ObjectType::Reserved(128), 1).is_err());
+        assert!(ObjectIdentifier::new(ObjectType::Reserved(100), 1).is_ok());
+        assert!(ObjectIdentifier::new(ObjectType::Proprietary(128), 1).is_ok());
+    }
+
+    #[test]
+    fn test_decode_object_identifier_reserved_type() {
+        // object type 100 is reserved
+        let object_identifier = ObjectIdentifier::decode_slice(&[0x19, 0x00, 0x00, 0x01]).unwrap();
+        assert_eq!(object_identifier.object_type(), ObjectType::Reserved(100));
+        assert_eq!(object_identifier.instance(), 1);
+        assert_eq!(
+            object_identifier.encode_vec().unwrap(),
+            [0x19, 0x00, 0x00, 0x01]
+        );
+    }
+
+    #[test]
+    fn test_object_identifier_display_from_str() {
+        let object_identifier = ObjectIdentifier::new(ObjectType::AnalogInput, 1).unwrap();
+        assert_eq!(object_identifier.to_string(), "analog-input,1");
+        assert_eq!(
+            "analog-input,1".parse::<ObjectIdentifier>().unwrap(),
+            object_identifier
+        );
+
+        let object_identifier = ObjectIdentifier::new(ObjectType::Proprietary(200), 7).unwrap();
+        assert_eq!(object_identifier.to_string(), "200,7");
+        assert_eq!(
+            "200,7".parse::<ObjectIdentifier>().unwrap(),
+            object_identifier
+        );
+
+        assert!("analog-input".parse::<ObjectIdentifier>().is_err());
+        assert!("analog-input,4194304".parse::<ObjectIdentifier>().is_err());
+        assert!("widget,1".parse::<ObjectIdentifier>().is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use crate::Error;
+use crate::encoding::{ApplicationValue, BitString, PropertyValue};
+
+/// BACnetReliability (21)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Reliability {
+    #[default]
+    NoFaultDetected, // = 0;
+    NoSensor,             // = 1;
+    OverRange,            // = 2;
+    UnderRange,           // = 3;
+    OpenLoop,             // = 4;
+    ShortedLoop,          // = 5;
+    NoOutput,             // = 6;
+    UnreliableOther,      // = 7;
+    ProcessError,         // = 8;
+    MultiStateFault,      // = 9;
+    ConfigurationError,   // = 10;
+    CommunicationFailure, // = 12;
+    MemberFault,          // = 13;
+    /// Standard reliability not listed above, below 64
+    Reserved(u16),
+    /// Vendor specific reliability, 64 to 65535
+    Proprietary(u16),
+}
+
+impl TryFrom<u32> for Reliability {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::NoFaultDetected),
+            1 => Ok(Self::NoSensor),
+            2 => Ok(Self::OverRange),
+            3 => Ok(Self::UnderRange),
+            4 => Ok(Self::OpenLoop),
+            5 => Ok(Self::ShortedLoop),
+            6 => Ok(Self::NoOutput),
+            7 => Ok(Self::UnreliableOther),
+            8 => Ok(Self::ProcessError),
+            9 => Ok(Self::MultiStateFault),
+            10 => Ok(Self::ConfigurationError),
+            12 => Ok(Self::CommunicationFailure),
+            13 => Ok(Self::MemberFault),
+            v @ 64..=65535 => Ok(Self::Proprietary(v as u16)),
+            v if v > 65535 => Err(Error::InvalidValue(format!(
+                "Reliability out of range: {}",
+                v
+            ))),
+            v => Ok(Self::Reserved(v as u16)),
+        }
+    }
+}
+
+impl From<Reliability> for u32 {
+    fn from(value: Reliability) -> Self {
+        match value {
+            Reliability::NoFaultDetected => 0,
+            Reliability::NoSensor => 1,
+            Reliability::OverRange => 2,
+            Reliability::UnderRange => 3,
+            Reliability::OpenLoop => 4,
+            Reliability::ShortedLoop => 5,
+            Reliability::NoOutput => 6,
+            Reliability::UnreliableOther => 7,
+            Reliability::ProcessError => 8,
+            Reliability::MultiStateFault => 9,
+            Reliability::ConfigurationError => 10,
+            Reliability::CommunicationFailure => 12,
+            Reliability::MemberFault => 13,
+            Reliability::Reserved(v) | Reliability::Proprietary(v) => v as u32,
+        }
+    }
+}
+
+/// Health of an object, from its status-flags and reliability properties
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectStatus {
+    pub in_alarm: bool,
+    pub fault: bool,
+    pub overridden: bool,
+    pub out_of_service: bool,
+    pub reliability: Reliability,
+}
+
+impl ObjectStatus {
+    /// Status from the BACnetStatusFlags (21) bit string and the reliability of an object, an
+    /// object without a reliability property is reported as NoFaultDetected
+    pub fn new(status_flags: &BitString, reliability: Reliability) -> Self {
+        Self {
+            in_alarm: status_flags.get(0),
+            fault: status_flags.get(1),
+            overridden: status_flags.get(2),
+            out_of_service: status_flags.get(3),
+            reliability,
+        }
+    }
+
+    /// The object is in alarm, faulty or not reliable
+    pub fn is_abnormal(&self) -> bool {
+        self.in_alarm || self.fault || self.reliability != Reliability::NoFaultDetected
+    }
+}
+
+/// BACnetDeviceStatus (21), the system-status of a device
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SystemStatus {
+    Operational,         // = 0;
+    OperationalReadOnly, // = 1;
+    DownloadRequired,    // = 2;
+    DownloadInProgress,  // = 3;
+    NonOperational,      // = 4;
+    BackupInProgress,    // = 5;
+    /// Standard status not listed above, below 64
+    Reserved(u16),
+    /// Vendor specific status, 64 to 65535
+    Proprietary(u16),
+}
+
+impl TryFrom<u32> for SystemStatus {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Operational),
+            1 => Ok(Self::OperationalReadOnly),
+            2 => Ok(Self::DownloadRequired),
+            3 => Ok(Self::DownloadInProgress),
+            4 => Ok(Self::NonOperational),
+            5 => Ok(Self::BackupInProgress),
+            v @ 64..=65535 => Ok(Self::Proprietary(v as u16)),
+            v if v > 65535 => Err(Error::InvalidValue(format!(
+                "System status out of range: {}",
+                v
+            ))),
+            v => Ok(Self::Reserved(v as u16)),
+        }
+    }
+}
+
+impl From<SystemStatus> for u32 {
+    fn from(value: SystemStatus) -> Self {
+        match value {
+            SystemStatus::Operational => 0,
+            SystemStatus::OperationalReadOnly => 1,
+            SystemStatus::DownloadRequired => 2,
+            SystemStatus::DownloadInProgress => 3,
+            SystemStatus::NonOperational => 4,
+            SystemStatus::BackupInProgress => 5,
+            SystemStatus::Reserved(v) | SystemStatus::Proprietary(v) => v as u32,
+        }
+    }
+}
+
+/// Health of a device, from its system-status and database-revision properties
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DeviceStatus {
+    pub system_status: SystemStatus,
+    pub database_revision: u32,
+}
+
+impl DeviceStatus {
+    /// Decode the values read from the system-status and database-revision properties
+    pub fn decode(
+        system_status: &PropertyValue,
+        database_revision: &PropertyValue,
+    ) -> crate::Result<Self> {
+        let system_status = match system_status.single() {
+            Some(ApplicationValue::Enumerated(v)) => SystemStatus::try_from(*v)?,
+            _ => {
+                return Err(Error::InvalidValue(format!(
+                    "Invalid system status: {:?}",
+                    system_status
+                )));
+            }
+        };
+        let database_revision = match database_revision.single() {
+            Some(ApplicationValue::Unsigned(v)) => *v,
+            _ => {
+                return Err(Error::InvalidValue(format!(
+                    "Invalid database revision: {:?}",
+                    database_revision
+                )));
+            }
+        };
+        Ok(Self {
+            system_status,
+            database_revision,
+        })
+    }
+
+    /// The device was reconfigured since `previous` was read, its database revision changed
+    pub fn reconfigured_since(&self, previous: &Self) -> bool {
+        self.database_revision != previous.database_revision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decode;
+
+    #[test]
+    fn test_object_status_from_decoded_values() {
+        // status-flags {false, true, false, true}, reliability over-range
+        let status_flags = PropertyValue::decode_slice(&[0x82, 0x04, 0x50]).unwrap();
+        let reliability = PropertyValue::decode_slice(&[0x91, 0x02]).unwrap();
+
+        let Some(ApplicationValue::BitString(status_flags)) = status_flags.single() else {
+            panic!("Not a bit string: {:?}", status_flags);
+        };
+        let Some(ApplicationValue::Enumerated(reliability)) = reliability.single() else {
+            panic!("Not enumerated: {:?}", reliability);
+        };
+        let status = ObjectStatus::new(status_flags, Reliability::try_from(*reliability).unwrap());
+
+        assert_eq!(
+            status,
+            ObjectStatus {
+                in_alarm: false,
+                fault: true,
+                overridden: false,
+                out_of_service: true,
+                reliability: Reliability::OverRange,
+            }
+        );
+        assert!(status.is_abnormal());
+        assert!(!ObjectStatus::default().is_abnormal());
+    }
+
+    #[test]
+    fn test_reliability_values() {
+        assert_eq!(
+            Reliability::try_from(11).unwrap(),
+            Reliability::Reserved(11)
+        );
+        assert_eq!(
+            Reliability::try_from(300).unwrap(),
+            Reliability::Proprietary(300)
+        );
+        assert_eq!(u32::from(Reliability::CommunicationFailure), 12);
+        let err = Reliability::try_from(65536).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_device_status() {
+        // system-status operational, database-revision 42
+        let system_status = PropertyValue::decode_slice(&[0x91, 0x00]).unwrap();
+        let database_revision = PropertyValue::decode_slice(&[0x21, 0x2a]).unwrap();
+
+        let status = DeviceStatus::decode(&system_status, &database_revision).unwrap();
+        assert_eq!(
+            status,
+            DeviceStatus {
+                system_status: SystemStatus::Operational,
+                database_revision: 42,
+            }
+        );
+
+        let revised = PropertyValue::decode_slice(&[0x21, 0x2b]).unwrap();
+        let revised = DeviceStatus::decode(&system_status, &revised).unwrap();
+        assert!(revised.reconfigured_since(&status));
+        assert!(!status.reconfigured_since(&status));
+
+        let err = DeviceStatus::decode(&database_revision, &database_revision).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        assert_eq!(
+            SystemStatus::try_from(70).unwrap(),
+            SystemStatus::Proprietary(70)
+        );
+    }
+}
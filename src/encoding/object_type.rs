@@ -0,0 +1,392 @@
+use crate::Error;
+use crate::encoding::MAX_OBJECT_TYPE;
+
+/// First object type of the proprietary range, values below are reserved by ASHRAE
+pub const FIRST_PROPRIETARY_OBJECT_TYPE: u16 = 128;
+
+/// BACnetObjectType (21)
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObjectType {
+    AnalogInput,           // = 0;
+    AnalogOutput,          // = 1;
+    AnalogValue,           // = 2;
+    BinaryInput,           // = 3;
+    BinaryOutput,          // = 4;
+    BinaryValue,           // = 5;
+    Calendar,              // = 6;
+    Command,               // = 7;
+    Device,                // = 8;
+    EventEnrollment,       // = 9;
+    File,                  // = 10;
+    Group,                 // = 11;
+    Loop,                  // = 12;
+    MultiStateInput,       // = 13;
+    MultiStateOutput,      // = 14;
+    NotificationClass,     // = 15;
+    Program,               // = 16;
+    Schedule,              // = 17;
+    Averaging,             // = 18;
+    MultiStateValue,       // = 19;
+    TrendLog,              // = 20;
+    LifeSafetyPoint,       // = 21;
+    LifeSafetyZone,        // = 22;
+    Accumulator,           // = 23;
+    PulseConverter,        // = 24;
+    EventLog,              // = 25;
+    GlobalGroup,           // = 26;
+    TrendLogMultiple,      // = 27;
+    LoadControl,           // = 28;
+    StructuredView,        // = 29;
+    AccessDoor,            // = 30;
+    Timer,                 // = 31;
+    AccessCredential,      // = 32;
+    AccessPoint,           // = 33;
+    AccessRights,          // = 34;
+    AccessUser,            // = 35;
+    AccessZone,            // = 36;
+    CredentialDataInput,   // = 37;
+    NetworkSecurity,       // = 38;
+    BitStringValue,        // = 39;
+    CharacterStringValue,  // = 40;
+    DatePatternValue,      // = 41;
+    DateValue,             // = 42;
+    DateTimePatternValue,  // = 43;
+    DateTimeValue,         // = 44;
+    IntegerValue,          // = 45;
+    LargeAnalogValue,      // = 46;
+    OctetStringValue,      // = 47;
+    PositiveIntegerValue,  // = 48;
+    TimePatternValue,      // = 49;
+    TimeValue,             // = 50;
+    NotificationForwarder, // = 51;
+    AlertEnrollment,       // = 52;
+    Channel,               // = 53;
+    LightingOutput,        // = 54;
+    BinaryLightingOutput,  // = 55;
+    NetworkPort,           // = 56;
+    ElevatorGroup,         // = 57;
+    Escalator,             // = 58;
+    Lift,                  // = 59;
+    Staging,               // = 60;
+    AuditLog,              // = 61;
+    AuditReporter,         // = 62;
+    Color,                 // = 63;
+    ColorTemperature,      // = 64;
+    /// Standard object type not listed above, 0 to 127
+    Reserved(u16),
+    /// Vendor specific object type, 128 to 1023
+    Proprietary(u16),
+}
+
+impl ObjectType {
+    /// Name of the object type as written in the standard, e.g. "analog-input"
+    pub fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::AnalogInput => "analog-input",
+            Self::AnalogOutput => "analog-output",
+            Self::AnalogValue => "analog-value",
+            Self::BinaryInput => "binary-input",
+            Self::BinaryOutput => "binary-output",
+            Self::BinaryValue => "binary-value",
+            Self::Calendar => "calendar",
+            Self::Command => "command",
+            Self::Device => "device",
+            Self::EventEnrollment => "event-enrollment",
+            Self::File => "file",
+            Self::Group => "group",
+            Self::Loop => "loop",
+            Self::MultiStateInput => "multi-state-input",
+            Self::MultiStateOutput => "multi-state-output",
+            Self::NotificationClass => "notification-class",
+            Self::Program => "program",
+            Self::Schedule => "schedule",
+            Self::Averaging => "averaging",
+            Self::MultiStateValue => "multi-state-value",
+            Self::TrendLog => "trend-log",
+            Self::LifeSafetyPoint => "life-safety-point",
+            Self::LifeSafetyZone => "life-safety-zone",
+            Self::Accumulator => "accumulator",
+            Self::PulseConverter => "pulse-converter",
+            Self::EventLog => "event-log",
+            Self::GlobalGroup => "global-group",
+            Self::TrendLogMultiple => "trend-log-multiple",
+            Self::LoadControl => "load-control",
+            Self::StructuredView => "structured-view",
+            Self::AccessDoor => "access-door",
+            Self::Timer => "timer",
+            Self::AccessCredential => "access-credential",
+            Self::AccessPoint => "access-point",
+            Self::AccessRights => "access-rights",
+            Self::AccessUser => "access-user",
+            Self::AccessZone => "access-zone",
+            Self::CredentialDataInput => "credential-data-input",
+            Self::NetworkSecurity => "network-security",
+            Self::BitStringValue => "bitstring-value",
+            Self::CharacterStringValue => "characterstring-value",
+            Self::DatePatternValue => "date-pattern-value",
+            Self::DateValue => "date-value",
+            Self::DateTimePatternValue => "datetime-pattern-value",
+            Self::DateTimeValue => "datetime-value",
+            Self::IntegerValue => "integer-value",
+            Self::LargeAnalogValue => "large-analog-value",
+            Self::OctetStringValue => "octetstring-value",
+            Self::PositiveIntegerValue => "positive-integer-value",
+            Self::TimePatternValue => "time-pattern-value",
+            Self::TimeValue => "time-value",
+            Self::NotificationForwarder => "notification-forwarder",
+            Self::AlertEnrollment => "alert-enrollment",
+            Self::Channel => "channel",
+            Self::LightingOutput => "lighting-output",
+            Self::BinaryLightingOutput => "binary-lighting-output",
+            Self::NetworkPort => "network-port",
+            Self::ElevatorGroup => "elevator-group",
+            Self::Escalator => "escalator",
+            Self::Lift => "lift",
+            Self::Staging => "staging",
+            Self::AuditLog => "audit-log",
+            Self::AuditReporter => "audit-reporter",
+            Self::Color => "color",
+            Self::ColorTemperature => "color-temperature",
+            Self::Reserved(_) | Self::Proprietary(_) => return None,
+        })
+    }
+}
+
+impl core::fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", u16::from(*self)),
+        }
+    }
+}
+
+impl core::str::FromStr for ObjectType {
+    type Err = Error;
+
+    /// Parse a standard object type name or a numeric object type
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = s.parse::<u16>() {
+            return Self::try_from(value);
+        }
+        (0..FIRST_PROPRIETARY_OBJECT_TYPE)
+            .filter_map(|v| Self::try_from(v).ok())
+            .find(|t| t.name() == Some(s))
+            .ok_or_else(|| Error::InvalidValue(format!("Unknown object type: {}", s)))
+    }
+}
+
+impl TryFrom<u16> for ObjectType {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::AnalogInput),
+            1 => Ok(Self::AnalogOutput),
+            2 => Ok(Self::AnalogValue),
+            3 => Ok(Self::BinaryInput),
+            4 => Ok(Self::BinaryOutput),
+            5 => Ok(Self::BinaryValue),
+            6 => Ok(Self::Calendar),
+            7 => Ok(Self::Command),
+            8 => Ok(Self::Device),
+            9 => Ok(Self::EventEnrollment),
+            10 => Ok(Self::File),
+            11 => Ok(Self::Group),
+            12 => Ok(Self::Loop),
+            13 => Ok(Self::MultiStateInput),
+            14 => Ok(Self::MultiStateOutput),
+            15 => Ok(Self::NotificationClass),
+            16 => Ok(Self::Program),
+            17 => Ok(Self::Schedule),
+            18 => Ok(Self::Averaging),
+            19 => Ok(Self::MultiStateValue),
+            20 => Ok(Self::TrendLog),
+            21 => Ok(Self::LifeSafetyPoint),
+            22 => Ok(Self::LifeSafetyZone),
+            23 => Ok(Self::Accumulator),
+            24 => Ok(Self::PulseConverter),
+            25 => Ok(Self::EventLog),
+            26 => Ok(Self::GlobalGroup),
+            27 => Ok(Self::TrendLogMultiple),
+            28 => Ok(Self::LoadControl),
+            29 => Ok(Self::StructuredView),
+            30 => Ok(Self::AccessDoor),
+            31 => Ok(Self::Timer),
+            32 => Ok(Self::AccessCredential),
+            33 => Ok(Self::AccessPoint),
+            34 => Ok(Self::AccessRights),
+            35 => Ok(Self::AccessUser),
+            36 => Ok(Self::AccessZone),
+            37 => Ok(Self::CredentialDataInput),
+            38 => Ok(Self::NetworkSecurity),
+            39 => Ok(Self::BitStringValue),
+            40 => Ok(Self::CharacterStringValue),
+            41 => Ok(Self::DatePatternValue),
+            42 => Ok(Self::DateValue),
+            43 => Ok(Self::DateTimePatternValue),
+            44 => Ok(Self::DateTimeValue),
+            45 => Ok(Self::IntegerValue),
+            46 => Ok(Self::LargeAnalogValue),
+            47 => Ok(Self::OctetStringValue),
+            48 => Ok(Self::PositiveIntegerValue),
+            49 => Ok(Self::TimePatternValue),
+            50 => Ok(Self::TimeValue),
+            51 => Ok(Self::NotificationForwarder),
+            52 => Ok(Self::AlertEnrollment),
+            53 => Ok(Self::Channel),
+            54 => Ok(Self::LightingOutput),
+            55 => Ok(Self::BinaryLightingOutput),
+            56 => Ok(Self::NetworkPort),
+            57 => Ok(Self::ElevatorGroup),
+            58 => Ok(Self::Escalator),
+            59 => Ok(Self::Lift),
+            60 => Ok(Self::Staging),
+            61 => Ok(Self::AuditLog),
+            62 => Ok(Self::AuditReporter),
+            63 => Ok(Self::Color),
+            64 => Ok(Self::ColorTemperature),
+            FIRST_PROPRIETARY_OBJECT_TYPE..=MAX_OBJECT_TYPE => Ok(Self::Proprietary(value)),
+            v if v > MAX_OBJECT_TYPE => Err(Error::InvalidValue(format!(
+                "Object type out of range: {}",
+                v
+            ))),
+            v => Ok(Self::Reserved(v)),
+        }
+    }
+}
+
+impl From<ObjectType> for u16 {
+    fn from(value: ObjectType) -> Self {
+        match value {
+            ObjectType::AnalogInput => 0,
+            ObjectType::AnalogOutput => 1,
+            ObjectType::AnalogValue => 2,
+            ObjectType::BinaryInput => 3,
+            ObjectType::BinaryOutput => 4,
+            ObjectType::BinaryValue => 5,
+            ObjectType::Calendar => 6,
+            ObjectType::Command => 7,
+            ObjectType::Device => 8,
+            ObjectType::EventEnrollment => 9,
+            ObjectType::File => 10,
+            ObjectType::Group => 11,
+            ObjectType::Loop => 12,
+            ObjectType::MultiStateInput => 13,
+            ObjectType::MultiStateOutput => 14,
+            ObjectType::NotificationClass => 15,
+            ObjectType::Program => 16,
+            ObjectType::Schedule => 17,
+            ObjectType::Averaging => 18,
+            ObjectType::MultiStateValue => 19,
+            ObjectType::TrendLog => 20,
+            ObjectType::LifeSafetyPoint => 21,
+            ObjectType::LifeSafetyZone => 22,
+            ObjectType::Accumulator => 23,
+            ObjectType::PulseConverter => 24,
+            ObjectType::EventLog => 25,
+            ObjectType::GlobalGroup => 26,
+            ObjectType::TrendLogMultiple => 27,
+            ObjectType::LoadControl => 28,
+            ObjectType::StructuredView => 29,
+            ObjectType::AccessDoor => 30,
+            ObjectType::Timer => 31,
+            ObjectType::AccessCredential => 32,
+            ObjectType::AccessPoint => 33,
+            ObjectType::AccessRights => 34,
+            ObjectType::AccessUser => 35,
+            ObjectType::AccessZone => 36,
+            ObjectType::CredentialDataInput => 37,
+            ObjectType::NetworkSecurity => 38,
+            ObjectType::BitStringValue => 39,
+            ObjectType::CharacterStringValue => 40,
+            ObjectType::DatePatternValue => 41,
+            ObjectType::DateValue => 42,
+            ObjectType::DateTimePatternValue => 43,
+            ObjectType::DateTimeValue => 44,
+            ObjectType::IntegerValue => 45,
+            ObjectType::LargeAnalogValue => 46,
+            ObjectType::OctetStringValue => 47,
+            ObjectType::PositiveIntegerValue => 48,
+            ObjectType::TimePatternValue => 49,
+            ObjectType::TimeValue => 50,
+            ObjectType::NotificationForwarder => 51,
+            ObjectType::AlertEnrollment => 52,
+            ObjectType::Channel => 53,
+            ObjectType::LightingOutput => 54,
+            ObjectType::BinaryLightingOutput => 55,
+            ObjectType::NetworkPort => 56,
+            ObjectType::ElevatorGroup => 57,
+            ObjectType::Escalator => 58,
+            ObjectType::Lift => 59,
+            ObjectType::Staging => 60,
+            ObjectType::AuditLog => 61,
+            ObjectType::AuditReporter => 62,
+            ObjectType::Color => 63,
+            ObjectType::ColorTemperature => 64,
+            ObjectType::Reserved(v) => v,
+            ObjectType::Proprietary(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_type_from_u16() {
+        assert_eq!(ObjectType::try_from(0).unwrap(), ObjectType::AnalogInput);
+        assert_eq!(ObjectType::try_from(8).unwrap(), ObjectType::Device);
+        assert_eq!(ObjectType::try_from(17).unwrap(), ObjectType::Schedule);
+        assert_eq!(
+            ObjectType::try_from(200).unwrap(),
+            ObjectType::Proprietary(200)
+        );
+    }
+
+    #[test]
+    fn test_object_type_round_trip() {
+        for value in 0..=MAX_OBJECT_TYPE {
+            let object_type = ObjectType::try_from(value).unwrap();
+            assert_eq!(u16::from(object_type), value);
+        }
+    }
+
+    #[test]
+    fn test_object_type_display_from_str() {
+        assert_eq!(ObjectType::AnalogInput.to_string(), "analog-input");
+        assert_eq!(ObjectType::MultiStateValue.to_string(), "multi-state-value");
+        assert_eq!(ObjectType::Proprietary(200).to_string(), "200");
+        assert_eq!("device".parse::<ObjectType>().unwrap(), ObjectType::Device);
+        assert_eq!(
+            "datetime-value".parse::<ObjectType>().unwrap(),
+            ObjectType::DateTimeValue
+        );
+        assert_eq!(
+            "200".parse::<ObjectType>().unwrap(),
+            ObjectType::Proprietary(200)
+        );
+        assert!("analog".parse::<ObjectType>().is_err());
+    }
+
+    #[test]
+    fn test_object_type_reserved() {
+        assert_eq!(ObjectType::try_from(65).unwrap(), ObjectType::Reserved(65));
+        assert_eq!(
+            ObjectType::try_from(127).unwrap(),
+            ObjectType::Reserved(127)
+        );
+        assert_eq!(ObjectType::Reserved(100).to_string(), "100");
+        assert_eq!(
+            "100".parse::<ObjectType>().unwrap(),
+            ObjectType::Reserved(100)
+        );
+    }
+
+    #[test]
+    fn test_object_type_out_of_range() {
+        assert!(ObjectType::try_from(MAX_OBJECT_TYPE + 1).is_err());
+    }
+}
@@ -1,18 +1,20 @@
 use nom::IResult;
-use std::io::Cursor;
+use nom::bytes::complete::take;
+use nom::combinator::map;
+use nom::number::complete::{be_u8, be_u16, be_u32};
 
+use crate::Error;
 use crate::encoding::{ApplicationTag, ContextTag, LengthValueType, Tag, TagNumber};
+use alloc::vec::Vec;
 
-#[allow(dead_code)]
 pub fn parse_bacnet_tag(input: &[u8]) -> IResult<&[u8], Tag<'_>> {
-    let mut cur = Cursor::new(input);
-    let first_byte = cur.get_u8();
+    let (input, first_byte) = be_u8(input)?;
     let tag_number = (first_byte & 0b1111_0000) >> 4;
 
     // 20.2.1.2 Tag Number
-    let tag_number = match tag_number {
-        t @ 0..=14 => t,
-        15..=255 => cur.get_u8(),
+    let (input, tag_number) = match tag_number {
+        t @ 0..=14 => (input, t),
+        15..=255 => be_u8(input)?,
     };
 
     // 20.2.1.1 Class
@@ -24,36 +26,28 @@ pub fn parse_bacnet_tag(input: &[u8]) -> IResult<&[u8], Tag<'_>> {
 
     // 20.2.1.3 Length/Value/Type
     let lvt = first_byte & 0b0000_0111;
-    let lvt = match lvt {
-        l if std::matches!(tag_number, TagNumber::Application(ApplicationTag::Boolean)) => {
-            LengthValueType::Value(l)
+    let (input, lvt) = match lvt {
+        l if matches!(tag_number, TagNumber::Application(ApplicationTag::Boolean)) => {
+            (input, LengthValueType::Value(l))
         }
-        l if l < 0b101 => LengthValueType::Length(l as u32),
+        l if l < 0b101 => (input, LengthValueType::Length(l as u32)),
         0b101 => {
-            let extended = cur.get_u8();
-            let length = match extended {
-                l @ 0..=253 => l as u32,
-                254 => cur.get_u16() as u32,
-                255 => cur.get_u32(),
+            let (input, extended) = be_u8(input)?;
+            let (input, length) = match extended {
+                l @ 0..=253 => (input, l as u32),
+                254 => map(be_u16, |l| l as u32)(input)?,
+                255 => be_u32(input)?,
             };
-            LengthValueType::Length(length)
+            (input, LengthValueType::Length(length))
         }
-        0b110 => LengthValueType::Opening,
-        0b111 => LengthValueType::Closing,
-        _ => unreachable!("Length is only 3 bits"),
+        0b110 => (input, LengthValueType::Opening),
+        _ => (input, LengthValueType::Closing),
     };
 
-    let data_start = cur.position() as usize;
-    let mut data_end = data_start;
-
-    if let LengthValueType::Length(l) = lvt {
-        data_end += l as usize;
-    }
-
-    // TODO: Throw a proper error if slice is not long enough, currently it just panicks which is still safe
-
-    let data = &input[data_start..data_end];
-    let output = &input[data_end..];
+    let (output, data) = match lvt {
+        LengthValueType::Length(l) => take(l)(input)?,
+        _ => (input, &input[..0]),
+    };
 
     let tag = Tag {
         tag_number,
@@ -67,16 +61,17 @@ pub fn parse_bacnet_tag(input: &[u8]) -> IResult<&[u8], Tag<'_>> {
 use bytes::{Buf, BufMut};
 
 #[allow(dead_code)]
-pub fn decode_buf(buf: &[u8]) -> Result<(u8, bool, u32, &[u8]), String> {
-    let mut cur = Cursor::new(buf);
+pub fn decode_buf(buf: &[u8]) -> crate::Result<(u8, bool, u32, &[u8])> {
+    let mut cur = buf;
+    let truncated = |_| Error::Truncated;
 
-    let first_byte = cur.get_u8();
+    let first_byte = cur.try_get_u8().map_err(truncated)?;
     let tag_number = (first_byte & 0b1111_0000) >> 4;
 
     // 20.2.1.2 Tag Number
     let tag_number = match tag_number {
         t @ 0..=14 => t,
-        15..=255 => cur.get_u8(),
+        15..=255 => cur.try_get_u8().map_err(truncated)?,
     };
 
     // 20.2.1.1 Class
@@ -87,26 +82,28 @@ pub fn decode_buf(buf: &[u8]) -> Result<(u8, bool, u32, &[u8]), String> {
     let length: u32 = if length < 0b101 {
         length as u32
     } else {
-        let extended = cur.get_u8();
+        let extended = cur.try_get_u8().map_err(truncated)?;
         match extended {
             l @ 0..=253 => l as u32,
-            254 => cur.get_u16() as u32,
-            255 => cur.get_u32(),
+            254 => cur.try_get_u16().map_err(truncated)? as u32,
+            255 => cur.try_get_u32().map_err(truncated)?,
         }
     };
 
     // Offset where the data starts,
     // depends on how length is encoded
-    let offset = cur.position() as usize;
+    let offset = buf.len() - cur.remaining();
 
-    // TODO: Throw a proper error if slice is not long enough, currently it just panicks which is still safe
-    let data = &buf[offset..offset + (length as usize)];
+    let data = offset
+        .checked_add(length as usize)
+        .and_then(|end| buf.get(offset..end))
+        .ok_or(Error::Truncated)?;
 
     Ok((tag_number, class, length, data))
 }
 
 #[allow(dead_code)]
-pub fn encode_buf(tag_number: u8, class: bool, length: u32) -> Result<Vec<u8>, String> {
+pub fn encode_buf(tag_number: u8, class: bool, length: u32) -> crate::Result<Vec<u8>> {
     let mut buf: Vec<u8> = vec![0x00]; // Initial tag set to zero so we can do bitwise or
 
     match tag_number {
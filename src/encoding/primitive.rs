@@ -0,0 +1,533 @@
+use crate::Error;
+use crate::encoding::{ApplicationTag, LengthValueType, Tag, TagNumber};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Big-endian value of up to 8 content octets
+fn read_be(data: &[u8]) -> u64 {
+    data.iter().fold(0, |value, b| value << 8 | *b as u64)
+}
+
+/// Decode a Null, which has no content octets (20.2.2)
+pub fn decode_null(tag: &Tag) -> crate::Result<()> {
+    match (tag.tag_number, tag.lvt) {
+        (TagNumber::Application(ApplicationTag::Null), LengthValueType::Length(0))
+        | (TagNumber::Context(_), LengthValueType::Length(0)) => Ok(()),
+        (t, l) => Err(Error::InvalidTag(format!("Not a Null: {:?} {:?}", t, l))),
+    }
+}
+
+/// Encode the contents of a context tagged Boolean, an application tagged Boolean
+/// carries its value in the tag itself, see `Tag::boolean` (20.2.3)
+pub fn encode_boolean(value: bool) -> Vec<u8> {
+    vec![value as u8]
+}
+
+/// Decode a Boolean from either an application tag (value in the LVT) or a context tag
+/// (value in the content octet) (20.2.3)
+pub fn decode_boolean(tag: &Tag) -> crate::Result<bool> {
+    let value = match (tag.tag_number, tag.lvt, tag.data) {
+        (TagNumber::Application(ApplicationTag::Boolean), LengthValueType::Value(v), _) => v,
+        (TagNumber::Context(_), LengthValueType::Length(1), [v]) => *v,
+        (t, _, _) => {
+            return Err(Error::InvalidTag(format!("Not a Boolean: {:?}", t)));
+        }
+    };
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        v => Err(Error::InvalidValue(format!(
+            "Boolean value not supported: {}",
+            v
+        ))),
+    }
+}
+
+/// Encode the contents of an Unsigned Integer using the fewest octets possible (20.2.4)
+pub fn encode_unsigned(value: u32) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = (value.leading_zeros() / 8).min(3) as usize;
+    bytes[skip..].to_vec()
+}
+
+/// Decode the contents of an Unsigned Integer (20.2.4)
+pub fn decode_unsigned(data: &[u8]) -> crate::Result<u32> {
+    match data.len() {
+        1..=4 => Ok(read_be(data) as u32),
+        l => Err(Error::InvalidValue(format!(
+            "Unsigned Integer length not supported: {}",
+            l
+        ))),
+    }
+}
+
+/// Encode the contents of a Signed Integer in two's complement using the fewest octets possible (20.2.5)
+pub fn encode_signed(value: i32) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    // Redundant sign octets, keeping the top bit of the first remaining octet as the sign
+    let redundant = match value {
+        v if v >= 0 => v.leading_zeros(),
+        v => v.leading_ones(),
+    };
+    let skip = ((redundant.saturating_sub(1)) / 8).min(3) as usize;
+    bytes[skip..].to_vec()
+}
+
+/// Decode the contents of a Signed Integer (20.2.5)
+pub fn decode_signed(data: &[u8]) -> crate::Result<i32> {
+    match data.len() {
+        l @ 1..=4 => {
+            // Sign extend from the top bit of the first octet
+            let shift = 64 - 8 * l;
+            Ok(((read_be(data) << shift) as i64 >> shift) as i32)
+        }
+        l => Err(Error::InvalidValue(format!(
+            "Signed Integer length not supported: {}",
+            l
+        ))),
+    }
+}
+
+/// Encode the contents of an Octet String, the octets are written verbatim (20.2.8)
+pub fn encode_octet_string(value: &[u8]) -> Vec<u8> {
+    value.to_vec()
+}
+
+/// Decode the contents of an Octet String, borrowing from the encoded tag (20.2.8)
+pub fn decode_octet_string(data: &[u8]) -> &[u8] {
+    data
+}
+
+/// Character set of a Character String, only those decodable by this crate are listed (20.2.9)
+const CHARSET_UTF8: u8 = 0;
+const CHARSET_UCS2: u8 = 4;
+const CHARSET_ISO_8859_1: u8 = 5;
+
+/// Encode the contents of a Character String as UTF-8 (20.2.9)
+pub fn encode_character_string(value: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + value.len());
+    data.push(CHARSET_UTF8);
+    data.extend_from_slice(value.as_bytes());
+    data
+}
+
+/// Decode the contents of a Character String in UTF-8, UCS-2 or ISO 8859-1 (20.2.9)
+pub fn decode_character_string(data: &[u8]) -> crate::Result<String> {
+    match data {
+        [] => Err(Error::InvalidValue(
+            "Character String without character set".to_string(),
+        )),
+        [CHARSET_UTF8, s @ ..] => String::from_utf8(s.to_vec())
+            .map_err(|e| Error::InvalidValue(format!("Invalid UTF-8 Character String: {}", e))),
+        [CHARSET_UCS2, s @ ..] if s.len() % 2 == 0 => {
+            let units: Vec<u16> = s.chunks(2).map(|c| read_be(c) as u16).collect();
+            String::from_utf16(&units)
+                .map_err(|e| Error::InvalidValue(format!("Invalid UCS-2 Character String: {}", e)))
+        }
+        [CHARSET_UCS2, ..] => Err(Error::InvalidValue(
+            "Invalid UCS-2 Character String length".to_string(),
+        )),
+        [CHARSET_ISO_8859_1, s @ ..] => Ok(s.iter().map(|&c| c as char).collect()),
+        [charset, ..] => Err(Error::UnsupportedCharset(*charset)),
+    }
+}
+
+/// Encode the contents of an Enumerated, encoded as an Unsigned Integer (20.2.11)
+pub fn encode_enumerated(value: u32) -> Vec<u8> {
+    encode_unsigned(value)
+}
+
+/// Decode the contents of an Enumerated to its raw value (20.2.11)
+pub fn decode_enumerated(data: &[u8]) -> crate::Result<u32> {
+    decode_unsigned(data).map_err(|_| {
+        Error::InvalidValue(format!("Enumerated length not supported: {}", data.len()))
+    })
+}
+
+/// Encode the contents of a Real as a big-endian IEEE-754 single precision value (20.2.6)
+pub fn encode_real(value: f32) -> Vec<u8> {
+    value.to_bits().to_be_bytes().to_vec()
+}
+
+/// Decode the contents of a Real (20.2.6)
+pub fn decode_real(data: &[u8]) -> crate::Result<f32> {
+    match data.len() {
+        4 => Ok(f32::from_bits(read_be(data) as u32)),
+        l => Err(Error::InvalidValue(format!(
+            "Real length not supported: {}",
+            l
+        ))),
+    }
+}
+
+/// Encode the contents of a Double as a big-endian IEEE-754 double precision value (20.2.7)
+pub fn encode_double(value: f64) -> Vec<u8> {
+    value.to_bits().to_be_bytes().to_vec()
+}
+
+/// Decode the contents of a Double (20.2.7)
+pub fn decode_double(data: &[u8]) -> crate::Result<f64> {
+    match data.len() {
+        8 => Ok(f64::from_bits(read_be(data))),
+        l => Err(Error::InvalidValue(format!(
+            "Double length not supported: {}",
+            l
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Encode;
+    use crate::encoding::{ApplicationTag, LengthValueType, Tag};
+
+    #[test]
+    fn test_null() {
+        let encoded = Tag::null().encode_vec().unwrap();
+        assert_eq!(encoded, vec![0x00]);
+        let (tag, rest) = Tag::decode(&encoded).unwrap();
+        assert!(decode_null(&tag).is_ok());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_null_relinquish_priority_array_slot() {
+        // WriteProperty value and priority: [3] { NULL } [4] 8
+        let priority = encode_unsigned(8);
+        let mut encoded = vec![];
+        Tag::opening(3).encode(&mut encoded).unwrap();
+        Tag::null().encode(&mut encoded).unwrap();
+        Tag::closing(3).encode(&mut encoded).unwrap();
+        Tag::context(4, &priority).encode(&mut encoded).unwrap();
+        assert_eq!(encoded, vec![0x3e, 0x00, 0x3f, 0x49, 0x08]);
+    }
+
+    #[test]
+    fn test_decode_null_invalid() {
+        let (tag, _) = Tag::decode(&[0x01, 0x00]).unwrap();
+        assert!(decode_null(&tag).is_err());
+        let (tag, _) = Tag::decode(&[0x21, 0x00]).unwrap();
+        assert!(decode_null(&tag).is_err());
+    }
+
+    #[test]
+    fn test_boolean_application_and_context() {
+        for value in [false, true] {
+            let application = Tag::boolean(value).encode_vec().unwrap();
+            assert_eq!(application, vec![0x10 | value as u8]);
+
+            let data = encode_boolean(value);
+            let context = Tag::context(2, &data).encode_vec().unwrap();
+            assert_eq!(context, vec![0x29, value as u8]);
+
+            let (tag, _) = Tag::decode(&application).unwrap();
+            assert_eq!(decode_boolean(&tag).unwrap(), value);
+            let (tag, _) = Tag::decode(&context).unwrap();
+            assert_eq!(decode_boolean(&tag).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_decode_boolean_invalid() {
+        let (tag, _) = Tag::decode(&[0x12]).unwrap();
+        assert!(decode_boolean(&tag).is_err());
+        let (tag, _) = Tag::decode(&[0x21, 0x01]).unwrap();
+        assert!(decode_boolean(&tag).is_err());
+        let (tag, _) = Tag::decode(&[0x2a, 0x00, 0x01]).unwrap();
+        assert!(decode_boolean(&tag).is_err());
+    }
+
+    fn assert_octet_string(value: &[u8], header: &[u8]) {
+        let data = encode_octet_string(value);
+        let encoded = Tag::application(ApplicationTag::OctetString, &data)
+            .encode_vec()
+            .unwrap();
+        assert_eq!(&encoded[..header.len()], header);
+        assert_eq!(encoded.len(), header.len() + value.len());
+
+        let (tag, rest) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::OctetString).unwrap();
+        assert_eq!(decode_octet_string(data), value);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_octet_string_empty() {
+        assert_octet_string(&[], &[0x60]);
+    }
+
+    #[test]
+    fn test_octet_string_4() {
+        assert_octet_string(&[0xc0, 0xa8, 0x01, 0x64], &[0x64]);
+    }
+
+    #[test]
+    fn test_octet_string_300() {
+        let value: Vec<u8> = (0..300).map(|i| i as u8).collect();
+        assert_octet_string(&value, &[0x65, 254, 0x01, 0x2c]);
+    }
+
+    #[test]
+    fn test_octet_string_500() {
+        assert_octet_string(&[0xaa; 500], &[0x65, 254, 0x01, 0xf4]);
+    }
+
+    fn assert_character_string(value: &str) {
+        let data = encode_character_string(value);
+        assert_eq!(data.len(), 1 + value.len());
+        let encoded = Tag::application(ApplicationTag::CharacterString, &data)
+            .encode_vec()
+            .unwrap();
+
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag
+            .application_data(ApplicationTag::CharacterString)
+            .unwrap();
+        assert_eq!(decode_character_string(data).unwrap(), value);
+    }
+
+    #[test]
+    fn test_character_string_ascii() {
+        assert_character_string("This is a BACnet string!");
+        assert_eq!(
+            Tag::application(
+                ApplicationTag::CharacterString,
+                &encode_character_string("AHU-1")
+            )
+            .encode_vec()
+            .unwrap(),
+            vec![0x75, 0x06, 0x00, b'A', b'H', b'U', b'-', b'1']
+        );
+    }
+
+    #[test]
+    fn test_character_string_utf8_multibyte() {
+        assert_character_string("Français");
+        assert_character_string("温度センサー");
+    }
+
+    #[test]
+    fn test_decode_character_string_ucs2_and_iso_8859_1() {
+        let data = hex::decode("04004600720061006e00e7006100690073").unwrap();
+        assert_eq!(decode_character_string(&data).unwrap(), "Français");
+        let data = hex::decode("054672616ee7616973").unwrap();
+        assert_eq!(decode_character_string(&data).unwrap(), "Français");
+    }
+
+    #[test]
+    fn test_decode_character_string_unsupported_charset() {
+        let data = hex::decode("010352546869732069732061204241436E657420737472696E6721").unwrap();
+        let err = decode_character_string(&data).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedCharset(1)), "{:?}", err);
+        assert!(decode_character_string(&[]).is_err());
+        assert!(decode_character_string(&[0x00, 0xff]).is_err());
+        assert!(decode_character_string(&[0x04, 0x00]).is_err());
+    }
+
+    fn assert_unsigned(value: u32, octets: usize) {
+        let data = encode_unsigned(value);
+        assert_eq!(data.len(), octets);
+
+        let encoded = Tag::application(ApplicationTag::UnsignedInteger, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        assert_eq!(tag.lvt(), LengthValueType::Length(octets as u32));
+        let data = tag
+            .application_data(ApplicationTag::UnsignedInteger)
+            .unwrap();
+        assert_eq!(decode_unsigned(data).unwrap(), value);
+    }
+
+    #[test]
+    fn test_unsigned_0() {
+        assert_unsigned(0, 1);
+    }
+
+    #[test]
+    fn test_unsigned_255() {
+        assert_unsigned(255, 1);
+    }
+
+    #[test]
+    fn test_unsigned_256() {
+        assert_unsigned(256, 2);
+    }
+
+    #[test]
+    fn test_unsigned_300() {
+        assert_eq!(encode_unsigned(300), vec![0x01, 0x2c]);
+    }
+
+    #[test]
+    fn test_unsigned_u32_max() {
+        assert_unsigned(u32::MAX, 4);
+    }
+
+    fn assert_signed(value: i32, octets: usize) {
+        let data = encode_signed(value);
+        assert_eq!(data.len(), octets);
+
+        let encoded = Tag::application(ApplicationTag::SignedInteger, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::SignedInteger).unwrap();
+        assert_eq!(decode_signed(data).unwrap(), value);
+    }
+
+    #[test]
+    fn test_signed_minus_1() {
+        assert_signed(-1, 1);
+        assert_eq!(encode_signed(-1), vec![0xff]);
+    }
+
+    #[test]
+    fn test_signed_minus_128() {
+        assert_signed(-128, 1);
+    }
+
+    #[test]
+    fn test_signed_minus_129() {
+        assert_signed(-129, 2);
+    }
+
+    #[test]
+    fn test_signed_127() {
+        assert_signed(127, 1);
+    }
+
+    #[test]
+    fn test_signed_128() {
+        assert_signed(128, 2);
+        assert_eq!(encode_signed(128), vec![0x00, 0x80]);
+    }
+
+    #[test]
+    fn test_signed_i32_min_and_max() {
+        assert_signed(i32::MIN, 4);
+        assert_signed(i32::MAX, 4);
+    }
+
+    #[test]
+    fn test_decode_signed_invalid_length() {
+        assert!(decode_signed(&[]).is_err());
+        assert!(decode_signed(&[0xff, 0, 0, 0, 0]).is_err());
+    }
+
+    fn assert_enumerated(value: u32, octets: usize) {
+        let data = encode_enumerated(value);
+        assert_eq!(data.len(), octets);
+
+        let encoded = Tag::application(ApplicationTag::Enumerated, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::Enumerated).unwrap();
+        assert_eq!(decode_enumerated(data).unwrap(), value);
+    }
+
+    #[test]
+    fn test_enumerated_0() {
+        assert_enumerated(0, 1);
+        assert_eq!(
+            Tag::application(ApplicationTag::Enumerated, &encode_enumerated(0))
+                .encode_vec()
+                .unwrap(),
+            vec![0x91, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_enumerated_proprietary() {
+        assert_enumerated(4_194_303, 3);
+        assert_enumerated(u32::MAX, 4);
+    }
+
+    #[test]
+    fn test_decode_enumerated_invalid_length() {
+        assert!(decode_enumerated(&[]).is_err());
+    }
+
+    fn assert_real(value: f32) {
+        let data = encode_real(value);
+        assert_eq!(data.len(), 4);
+
+        let encoded = Tag::application(ApplicationTag::Real, &data)
+            .encode_vec()
+            .unwrap();
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        let data = tag.application_data(ApplicationTag::Real).unwrap();
+        assert_eq!(decode_real(data).unwrap().to_bits(), value.to_bits());
+    }
+
+    #[test]
+    fn test_real_72_5() {
+        assert_real(72.5);
+        assert_eq!(encode_real(72.0), vec![0x42, 0x90, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_real_negative() {
+        assert_real(-33.3);
+        assert_eq!(encode_real(-33.3), vec![0xc2, 0x05, 0x33, 0x33]);
+    }
+
+    #[test]
+    fn test_real_special_values() {
+        assert_real(f32::INFINITY);
+        assert_real(f32::NEG_INFINITY);
+        assert_real(f32::from_bits(0x7fc0_0001)); // NaN with payload
+    }
+
+    #[test]
+    fn test_decode_real_invalid_length() {
+        assert!(decode_real(&[0x42, 0x90, 0x00]).is_err());
+    }
+
+    fn assert_double(value: f64) {
+        let data = encode_double(value);
+
+        let encoded = Tag::application(ApplicationTag::Double, &data)
+            .encode_vec()
+            .unwrap();
+        assert_eq!(&encoded[..2], &[0x55, 8]);
+        let (tag, _) = Tag::decode(&encoded).unwrap();
+        assert_eq!(tag.lvt(), LengthValueType::Length(8));
+        let data = tag.application_data(ApplicationTag::Double).unwrap();
+        assert_eq!(decode_double(data).unwrap().to_bits(), value.to_bits());
+    }
+
+    #[test]
+    fn test_double_pi() {
+        assert_double(std::f64::consts::PI);
+        assert_eq!(
+            encode_double(72.0),
+            vec![0x40, 0x52, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_double_special_values() {
+        let subnormal = f64::MIN_POSITIVE / 4.0;
+        assert!(subnormal.is_subnormal());
+        assert_double(subnormal);
+        assert_double(-0.0);
+        assert_double(f64::NEG_INFINITY);
+        assert_double(f64::NAN);
+    }
+
+    #[test]
+    fn test_decode_double_invalid_length() {
+        assert!(decode_double(&[0x40, 0x52, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_decode_unsigned_invalid_length() {
+        assert!(decode_unsigned(&[]).is_err());
+        assert!(decode_unsigned(&[1, 0, 0, 0, 0]).is_err());
+    }
+}
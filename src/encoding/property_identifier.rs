@@ -0,0 +1,764 @@
+use crate::Error;
+
+/// First property identifier of the proprietary range, values below are reserved by ASHRAE
+pub const FIRST_PROPRIETARY_PROPERTY: u32 = 512;
+
+/// Largest property identifier, limited to 22 bits like instance numbers
+pub const MAX_PROPERTY_IDENTIFIER: u32 = 0x3F_FFFF;
+
+/// BACnetPropertyIdentifier (21), the commonly used standard properties
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PropertyIdentifier {
+    AckedTransitions,               // = 0;
+    AckRequired,                    // = 1;
+    Action,                         // = 2;
+    ActionText,                     // = 3;
+    ActiveText,                     // = 4;
+    ActiveVtSessions,               // = 5;
+    AlarmValue,                     // = 6;
+    AlarmValues,                    // = 7;
+    All,                            // = 8;
+    AllWritesSuccessful,            // = 9;
+    ApduSegmentTimeout,             // = 10;
+    ApduTimeout,                    // = 11;
+    ApplicationSoftwareVersion,     // = 12;
+    Archive,                        // = 13;
+    Bias,                           // = 14;
+    ChangeOfStateCount,             // = 15;
+    ChangeOfStateTime,              // = 16;
+    NotificationClass,              // = 17;
+    ControlledVariableReference,    // = 19;
+    ControlledVariableUnits,        // = 20;
+    ControlledVariableValue,        // = 21;
+    CovIncrement,                   // = 22;
+    DateList,                       // = 23;
+    DaylightSavingsStatus,          // = 24;
+    Deadband,                       // = 25;
+    DerivativeConstant,             // = 26;
+    DerivativeConstantUnits,        // = 27;
+    Description,                    // = 28;
+    DescriptionOfHalt,              // = 29;
+    DeviceAddressBinding,           // = 30;
+    DeviceType,                     // = 31;
+    EffectivePeriod,                // = 32;
+    ElapsedActiveTime,              // = 33;
+    ErrorLimit,                     // = 34;
+    EventEnable,                    // = 35;
+    EventState,                     // = 36;
+    EventType,                      // = 37;
+    ExceptionSchedule,              // = 38;
+    FaultValues,                    // = 39;
+    FeedbackValue,                  // = 40;
+    FileAccessMethod,               // = 41;
+    FileSize,                       // = 42;
+    FileType,                       // = 43;
+    FirmwareRevision,               // = 44;
+    HighLimit,                      // = 45;
+    InactiveText,                   // = 46;
+    InProcess,                      // = 47;
+    InstanceOf,                     // = 48;
+    IntegralConstant,               // = 49;
+    IntegralConstantUnits,          // = 50;
+    LimitEnable,                    // = 52;
+    ListOfGroupMembers,             // = 53;
+    ListOfObjectPropertyReferences, // = 54;
+    LocalDate,                      // = 56;
+    LocalTime,                      // = 57;
+    Location,                       // = 58;
+    LowLimit,                       // = 59;
+    ManipulatedVariableReference,   // = 60;
+    MaximumOutput,                  // = 61;
+    MaxApduLengthAccepted,          // = 62;
+    MaxInfoFrames,                  // = 63;
+    MaxMaster,                      // = 64;
+    MaxPresValue,                   // = 65;
+    MinimumOffTime,                 // = 66;
+    MinimumOnTime,                  // = 67;
+    MinimumOutput,                  // = 68;
+    MinPresValue,                   // = 69;
+    ModelName,                      // = 70;
+    ModificationDate,               // = 71;
+    NotifyType,                     // = 72;
+    NumberOfApduRetries,            // = 73;
+    NumberOfStates,                 // = 74;
+    ObjectIdentifier,               // = 75;
+    ObjectList,                     // = 76;
+    ObjectName,                     // = 77;
+    ObjectPropertyReference,        // = 78;
+    ObjectType,                     // = 79;
+    Optional,                       // = 80;
+    OutOfService,                   // = 81;
+    OutputUnits,                    // = 82;
+    EventParameters,                // = 83;
+    Polarity,                       // = 84;
+    PresentValue,                   // = 85;
+    Priority,                       // = 86;
+    PriorityArray,                  // = 87;
+    PriorityForWriting,             // = 88;
+    ProcessIdentifier,              // = 89;
+    ProgramChange,                  // = 90;
+    ProgramLocation,                // = 91;
+    ProgramState,                   // = 92;
+    ProportionalConstant,           // = 93;
+    ProportionalConstantUnits,      // = 94;
+    ProtocolObjectTypesSupported,   // = 96;
+    ProtocolServicesSupported,      // = 97;
+    ProtocolVersion,                // = 98;
+    ReadOnly,                       // = 99;
+    ReasonForHalt,                  // = 100;
+    RecipientList,                  // = 102;
+    Reliability,                    // = 103;
+    RelinquishDefault,              // = 104;
+    Required,                       // = 105;
+    Resolution,                     // = 106;
+    SegmentationSupported,          // = 107;
+    Setpoint,                       // = 108;
+    SetpointReference,              // = 109;
+    StateText,                      // = 110;
+    StatusFlags,                    // = 111;
+    SystemStatus,                   // = 112;
+    TimeDelay,                      // = 113;
+    TimeOfActiveTimeReset,          // = 114;
+    TimeOfStateCountReset,          // = 115;
+    TimeSynchronizationRecipients,  // = 116;
+    Units,                          // = 117;
+    UpdateInterval,                 // = 118;
+    UtcOffset,                      // = 119;
+    VendorIdentifier,               // = 120;
+    VendorName,                     // = 121;
+    VtClassesSupported,             // = 122;
+    WeeklySchedule,                 // = 123;
+    AttemptedSamples,               // = 124;
+    AverageValue,                   // = 125;
+    BufferSize,                     // = 126;
+    ClientCovIncrement,             // = 127;
+    CovResubscriptionInterval,      // = 128;
+    EventTimeStamps,                // = 130;
+    LogBuffer,                      // = 131;
+    LogDeviceObjectProperty,        // = 132;
+    Enable,                         // = 133;
+    LogInterval,                    // = 134;
+    MaximumValue,                   // = 135;
+    MinimumValue,                   // = 136;
+    NotificationThreshold,          // = 137;
+    ProtocolRevision,               // = 139;
+    RecordsSinceNotification,       // = 140;
+    RecordCount,                    // = 141;
+    StartTime,                      // = 142;
+    StopTime,                       // = 143;
+    StopWhenFull,                   // = 144;
+    TotalRecordCount,               // = 145;
+    ValidSamples,                   // = 146;
+    WindowInterval,                 // = 147;
+    WindowSamples,                  // = 148;
+    MaximumValueTimestamp,          // = 149;
+    MinimumValueTimestamp,          // = 150;
+    VarianceValue,                  // = 151;
+    ActiveCovSubscriptions,         // = 152;
+    BackupFailureTimeout,           // = 153;
+    ConfigurationFiles,             // = 154;
+    DatabaseRevision,               // = 155;
+    DirectReading,                  // = 156;
+    LastRestoreTime,                // = 157;
+    MaintenanceRequired,            // = 158;
+    MemberOf,                       // = 159;
+    Mode,                           // = 160;
+    OperationExpected,              // = 161;
+    Setting,                        // = 162;
+    Silenced,                       // = 163;
+    TrackingValue,                  // = 164;
+    ZoneMembers,                    // = 165;
+    LifeSafetyAlarmValues,          // = 166;
+    MaxSegmentsAccepted,            // = 167;
+    ProfileName,                    // = 168;
+    EventMessageTexts,              // = 351;
+    PropertyList,                   // = 371;
+    /// Standard property not listed above, 0 to 511
+    Reserved(u32),
+    /// Vendor specific property, 512 to 4194303
+    Proprietary(u32),
+}
+
+impl PropertyIdentifier {
+    /// Name of the property as written in the standard, e.g. "present-value"
+    pub fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::AckedTransitions => "acked-transitions",
+            Self::AckRequired => "ack-required",
+            Self::Action => "action",
+            Self::ActionText => "action-text",
+            Self::ActiveText => "active-text",
+            Self::ActiveVtSessions => "active-vt-sessions",
+            Self::AlarmValue => "alarm-value",
+            Self::AlarmValues => "alarm-values",
+            Self::All => "all",
+            Self::AllWritesSuccessful => "all-writes-successful",
+            Self::ApduSegmentTimeout => "apdu-segment-timeout",
+            Self::ApduTimeout => "apdu-timeout",
+            Self::ApplicationSoftwareVersion => "application-software-version",
+            Self::Archive => "archive",
+            Self::Bias => "bias",
+            Self::ChangeOfStateCount => "change-of-state-count",
+            Self::ChangeOfStateTime => "change-of-state-time",
+            Self::NotificationClass => "notification-class",
+            Self::ControlledVariableReference => "controlled-variable-reference",
+            Self::ControlledVariableUnits => "controlled-variable-units",
+            Self::ControlledVariableValue => "controlled-variable-value",
+            Self::CovIncrement => "cov-increment",
+            Self::DateList => "date-list",
+            Self::DaylightSavingsStatus => "daylight-savings-status",
+            Self::Deadband => "deadband",
+            Self::DerivativeConstant => "derivative-constant",
+            Self::DerivativeConstantUnits => "derivative-constant-units",
+            Self::Description => "description",
+            Self::DescriptionOfHalt => "description-of-halt",
+            Self::DeviceAddressBinding => "device-address-binding",
+            Self::DeviceType => "device-type",
+            Self::EffectivePeriod => "effective-period",
+            Self::ElapsedActiveTime => "elapsed-active-time",
+            Self::ErrorLimit => "error-limit",
+            Self::EventEnable => "event-enable",
+            Self::EventState => "event-state",
+            Self::EventType => "event-type",
+            Self::ExceptionSchedule => "exception-schedule",
+            Self::FaultValues => "fault-values",
+            Self::FeedbackValue => "feedback-value",
+            Self::FileAccessMethod => "file-access-method",
+            Self::FileSize => "file-size",
+            Self::FileType => "file-type",
+            Self::FirmwareRevision => "firmware-revision",
+            Self::HighLimit => "high-limit",
+            Self::InactiveText => "inactive-text",
+            Self::InProcess => "in-process",
+            Self::InstanceOf => "instance-of",
+            Self::IntegralConstant => "integral-constant",
+            Self::IntegralConstantUnits => "integral-constant-units",
+            Self::LimitEnable => "limit-enable",
+            Self::ListOfGroupMembers => "list-of-group-members",
+            Self::ListOfObjectPropertyReferences => "list-of-object-property-references",
+            Self::LocalDate => "local-date",
+            Self::LocalTime => "local-time",
+            Self::Location => "location",
+            Self::LowLimit => "low-limit",
+            Self::ManipulatedVariableReference => "manipulated-variable-reference",
+            Self::MaximumOutput => "maximum-output",
+            Self::MaxApduLengthAccepted => "max-apdu-length-accepted",
+            Self::MaxInfoFrames => "max-info-frames",
+            Self::MaxMaster => "max-master",
+            Self::MaxPresValue => "max-pres-value",
+            Self::MinimumOffTime => "minimum-off-time",
+            Self::MinimumOnTime => "minimum-on-time",
+            Self::MinimumOutput => "minimum-output",
+            Self::MinPresValue => "min-pres-value",
+            Self::ModelName => "model-name",
+            Self::ModificationDate => "modification-date",
+            Self::NotifyType => "notify-type",
+            Self::NumberOfApduRetries => "number-of-apdu-retries",
+            Self::NumberOfStates => "number-of-states",
+            Self::ObjectIdentifier => "object-identifier",
+            Self::ObjectList => "object-list",
+            Self::ObjectName => "object-name",
+            Self::ObjectPropertyReference => "object-property-reference",
+            Self::ObjectType => "object-type",
+            Self::Optional => "optional",
+            Self::OutOfService => "out-of-service",
+            Self::OutputUnits => "output-units",
+            Self::EventParameters => "event-parameters",
+            Self::Polarity => "polarity",
+            Self::PresentValue => "present-value",
+            Self::Priority => "priority",
+            Self::PriorityArray => "priority-array",
+            Self::PriorityForWriting => "priority-for-writing",
+            Self::ProcessIdentifier => "process-identifier",
+            Self::ProgramChange => "program-change",
+            Self::ProgramLocation => "program-location",
+            Self::ProgramState => "program-state",
+            Self::ProportionalConstant => "proportional-constant",
+            Self::ProportionalConstantUnits => "proportional-constant-units",
+            Self::ProtocolObjectTypesSupported => "protocol-object-types-supported",
+            Self::ProtocolServicesSupported => "protocol-services-supported",
+            Self::ProtocolVersion => "protocol-version",
+            Self::ReadOnly => "read-only",
+            Self::ReasonForHalt => "reason-for-halt",
+            Self::RecipientList => "recipient-list",
+            Self::Reliability => "reliability",
+            Self::RelinquishDefault => "relinquish-default",
+            Self::Required => "required",
+            Self::Resolution => "resolution",
+            Self::SegmentationSupported => "segmentation-supported",
+            Self::Setpoint => "setpoint",
+            Self::SetpointReference => "setpoint-reference",
+            Self::StateText => "state-text",
+            Self::StatusFlags => "status-flags",
+            Self::SystemStatus => "system-status",
+            Self::TimeDelay => "time-delay",
+            Self::TimeOfActiveTimeReset => "time-of-active-time-reset",
+            Self::TimeOfStateCountReset => "time-of-state-count-reset",
+            Self::TimeSynchronizationRecipients => "time-synchronization-recipients",
+            Self::Units => "units",
+            Self::UpdateInterval => "update-interval",
+            Self::UtcOffset => "utc-offset",
+            Self::VendorIdentifier => "vendor-identifier",
+            Self::VendorName => "vendor-name",
+            Self::VtClassesSupported => "vt-classes-supported",
+            Self::WeeklySchedule => "weekly-schedule",
+            Self::AttemptedSamples => "attempted-samples",
+            Self::AverageValue => "average-value",
+            Self::BufferSize => "buffer-size",
+            Self::ClientCovIncrement => "client-cov-increment",
+            Self::CovResubscriptionInterval => "cov-resubscription-interval",
+            Self::EventTimeStamps => "event-time-stamps",
+            Self::LogBuffer => "log-buffer",
+            Self::LogDeviceObjectProperty => "log-device-object-property",
+            Self::Enable => "enable",
+            Self::LogInterval => "log-interval",
+            Self::MaximumValue => "maximum-value",
+            Self::MinimumValue => "minimum-value",
+            Self::NotificationThreshold => "notification-threshold",
+            Self::ProtocolRevision => "protocol-revision",
+            Self::RecordsSinceNotification => "records-since-notification",
+            Self::RecordCount => "record-count",
+            Self::StartTime => "start-time",
+            Self::StopTime => "stop-time",
+            Self::StopWhenFull => "stop-when-full",
+            Self::TotalRecordCount => "total-record-count",
+            Self::ValidSamples => "valid-samples",
+            Self::WindowInterval => "window-interval",
+            Self::WindowSamples => "window-samples",
+            Self::MaximumValueTimestamp => "maximum-value-timestamp",
+            Self::MinimumValueTimestamp => "minimum-value-timestamp",
+            Self::VarianceValue => "variance-value",
+            Self::ActiveCovSubscriptions => "active-cov-subscriptions",
+            Self::BackupFailureTimeout => "backup-failure-timeout",
+            Self::ConfigurationFiles => "configuration-files",
+            Self::DatabaseRevision => "database-revision",
+            Self::DirectReading => "direct-reading",
+            Self::LastRestoreTime => "last-restore-time",
+            Self::MaintenanceRequired => "maintenance-required",
+            Self::MemberOf => "member-of",
+            Self::Mode => "mode",
+            Self::OperationExpected => "operation-expected",
+            Self::Setting => "setting",
+            Self::Silenced => "silenced",
+            Self::TrackingValue => "tracking-value",
+            Self::ZoneMembers => "zone-members",
+            Self::LifeSafetyAlarmValues => "life-safety-alarm-values",
+            Self::MaxSegmentsAccepted => "max-segments-accepted",
+            Self::ProfileName => "profile-name",
+            Self::EventMessageTexts => "event-message-texts",
+            Self::PropertyList => "property-list",
+            Self::Reserved(_) | Self::Proprietary(_) => return None,
+        })
+    }
+}
+
+impl TryFrom<u32> for PropertyIdentifier {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::AckedTransitions),
+            1 => Ok(Self::AckRequired),
+            2 => Ok(Self::Action),
+            3 => Ok(Self::ActionText),
+            4 => Ok(Self::ActiveText),
+            5 => Ok(Self::ActiveVtSessions),
+            6 => Ok(Self::AlarmValue),
+            7 => Ok(Self::AlarmValues),
+            8 => Ok(Self::All),
+            9 => Ok(Self::AllWritesSuccessful),
+            10 => Ok(Self::ApduSegmentTimeout),
+            11 => Ok(Self::ApduTimeout),
+            12 => Ok(Self::ApplicationSoftwareVersion),
+            13 => Ok(Self::Archive),
+            14 => Ok(Self::Bias),
+            15 => Ok(Self::ChangeOfStateCount),
+            16 => Ok(Self::ChangeOfStateTime),
+            17 => Ok(Self::NotificationClass),
+            19 => Ok(Self::ControlledVariableReference),
+            20 => Ok(Self::ControlledVariableUnits),
+            21 => Ok(Self::ControlledVariableValue),
+            22 => Ok(Self::CovIncrement),
+            23 => Ok(Self::DateList),
+            24 => Ok(Self::DaylightSavingsStatus),
+            25 => Ok(Self::Deadband),
+            26 => Ok(Self::DerivativeConstant),
+            27 => Ok(Self::DerivativeConstantUnits),
+            28 => Ok(Self::Description),
+            29 => Ok(Self::DescriptionOfHalt),
+            30 => Ok(Self::DeviceAddressBinding),
+            31 => Ok(Self::DeviceType),
+            32 => Ok(Self::EffectivePeriod),
+            33 => Ok(Self::ElapsedActiveTime),
+            34 => Ok(Self::ErrorLimit),
+            35 => Ok(Self::EventEnable),
+            36 => Ok(Self::EventState),
+            37 => Ok(Self::EventType),
+            38 => Ok(Self::ExceptionSchedule),
+            39 => Ok(Self::FaultValues),
+            40 => Ok(Self::FeedbackValue),
+            41 => Ok(Self::FileAccessMethod),
+            42 => Ok(Self::FileSize),
+            43 => Ok(Self::FileType),
+            44 => Ok(Self::FirmwareRevision),
+            45 => Ok(Self::HighLimit),
+            46 => Ok(Self::InactiveText),
+            47 => Ok(Self::InProcess),
+            48 => Ok(Self::InstanceOf),
+            49 => Ok(Self::IntegralConstant),
+            50 => Ok(Self::IntegralConstantUnits),
+            52 => Ok(Self::LimitEnable),
+            53 => Ok(Self::ListOfGroupMembers),
+            54 => Ok(Self::ListOfObjectPropertyReferences),
+            56 => Ok(Self::LocalDate),
+            57 => Ok(Self::LocalTime),
+            58 => Ok(Self::Location),
+            59 => Ok(Self::LowLimit),
+            60 => Ok(Self::ManipulatedVariableReference),
+            61 => Ok(Self::MaximumOutput),
+            62 => Ok(Self::MaxApduLengthAccepted),
+            63 => Ok(Self::MaxInfoFrames),
+            64 => Ok(Self::MaxMaster),
+            65 => Ok(Self::MaxPresValue),
+            66 => Ok(Self::MinimumOffTime),
+            67 => Ok(Self::MinimumOnTime),
+            68 => Ok(Self::MinimumOutput),
+            69 => Ok(Self::MinPresValue),
+            70 => Ok(Self::ModelName),
+            71 => Ok(Self::ModificationDate),
+            72 => Ok(Self::NotifyType),
+            73 => Ok(Self::NumberOfApduRetries),
+            74 => Ok(Self::NumberOfStates),
+            75 => Ok(Self::ObjectIdentifier),
+            76 => Ok(Self::ObjectList),
+            77 => Ok(Self::ObjectName),
+            78 => Ok(Self::ObjectPropertyReference),
+            79 => Ok(Self::ObjectType),
+            80 => Ok(Self::Optional),
+            81 => Ok(Self::OutOfService),
+            82 => Ok(Self::OutputUnits),
+            83 => Ok(Self::EventParameters),
+            84 => Ok(Self::Polarity),
+            85 => Ok(Self::PresentValue),
+            86 => Ok(Self::Priority),
+            87 => Ok(Self::PriorityArray),
+            88 => Ok(Self::PriorityForWriting),
+            89 => Ok(Self::ProcessIdentifier),
+            90 => Ok(Self::ProgramChange),
+            91 => Ok(Self::ProgramLocation),
+            92 => Ok(Self::ProgramState),
+            93 => Ok(Self::ProportionalConstant),
+            94 => Ok(Self::ProportionalConstantUnits),
+            96 => Ok(Self::ProtocolObjectTypesSupported),
+            97 => Ok(Self::ProtocolServicesSupported),
+            98 => Ok(Self::ProtocolVersion),
+            99 => Ok(Self::ReadOnly),
+            100 => Ok(Self::ReasonForHalt),
+            102 => Ok(Self::RecipientList),
+            103 => Ok(Self::Reliability),
+            104 => Ok(Self::RelinquishDefault),
+            105 => Ok(Self::Required),
+            106 => Ok(Self::Resolution),
+            107 => Ok(Self::SegmentationSupported),
+            108 => Ok(Self::Setpoint),
+            109 => Ok(Self::SetpointReference),
+            110 => Ok(Self::StateText),
+            111 => Ok(Self::StatusFlags),
+            112 => Ok(Self::SystemStatus),
+            113 => Ok(Self::TimeDelay),
+            114 => Ok(Self::TimeOfActiveTimeReset),
+            115 => Ok(Self::TimeOfStateCountReset),
+            116 => Ok(Self::TimeSynchronizationRecipients),
+            117 => Ok(Self::Units),
+            118 => Ok(Self::UpdateInterval),
+            119 => Ok(Self::UtcOffset),
+            120 => Ok(Self::VendorIdentifier),
+            121 => Ok(Self::VendorName),
+            122 => Ok(Self::VtClassesSupported),
+            123 => Ok(Self::WeeklySchedule),
+            124 => Ok(Self::AttemptedSamples),
+            125 => Ok(Self::AverageValue),
+            126 => Ok(Self::BufferSize),
+            127 => Ok(Self::ClientCovIncrement),
+            128 => Ok(Self::CovResubscriptionInterval),
+            130 => Ok(Self::EventTimeStamps),
+            131 => Ok(Self::LogBuffer),
+            132 => Ok(Self::LogDeviceObjectProperty),
+            133 => Ok(Self::Enable),
+            134 => Ok(Self::LogInterval),
+            135 => Ok(Self::MaximumValue),
+            136 => Ok(Self::MinimumValue),
+            137 => Ok(Self::NotificationThreshold),
+            139 => Ok(Self::ProtocolRevision),
+            140 => Ok(Self::RecordsSinceNotification),
+            141 => Ok(Self::RecordCount),
+            142 => Ok(Self::StartTime),
+            143 => Ok(Self::StopTime),
+            144 => Ok(Self::StopWhenFull),
+            145 => Ok(Self::TotalRecordCount),
+            146 => Ok(Self::ValidSamples),
+            147 => Ok(Self::WindowInterval),
+            148 => Ok(Self::WindowSamples),
+            149 => Ok(Self::MaximumValueTimestamp),
+            150 => Ok(Self::MinimumValueTimestamp),
+            151 => Ok(Self::VarianceValue),
+            152 => Ok(Self::ActiveCovSubscriptions),
+            153 => Ok(Self::BackupFailureTimeout),
+            154 => Ok(Self::ConfigurationFiles),
+            155 => Ok(Self::DatabaseRevision),
+            156 => Ok(Self::DirectReading),
+            157 => Ok(Self::LastRestoreTime),
+            158 => Ok(Self::MaintenanceRequired),
+            159 => Ok(Self::MemberOf),
+            160 => Ok(Self::Mode),
+            161 => Ok(Self::OperationExpected),
+            162 => Ok(Self::Setting),
+            163 => Ok(Self::Silenced),
+            164 => Ok(Self::TrackingValue),
+            165 => Ok(Self::ZoneMembers),
+            166 => Ok(Self::LifeSafetyAlarmValues),
+            167 => Ok(Self::MaxSegmentsAccepted),
+            168 => Ok(Self::ProfileName),
+            351 => Ok(Self::EventMessageTexts),
+            371 => Ok(Self::PropertyList),
+            FIRST_PROPRIETARY_PROPERTY..=MAX_PROPERTY_IDENTIFIER => Ok(Self::Proprietary(value)),
+            v if v > MAX_PROPERTY_IDENTIFIER => Err(Error::InvalidValue(format!(
+                "Property identifier out of range: {}",
+                v
+            ))),
+            v => Ok(Self::Reserved(v)),
+        }
+    }
+}
+
+impl From<PropertyIdentifier> for u32 {
+    fn from(value: PropertyIdentifier) -> Self {
+        match value {
+            PropertyIdentifier::AckedTransitions => 0,
+            PropertyIdentifier::AckRequired => 1,
+            PropertyIdentifier::Action => 2,
+            PropertyIdentifier::ActionText => 3,
+            PropertyIdentifier::ActiveText => 4,
+            PropertyIdentifier::ActiveVtSessions => 5,
+            PropertyIdentifier::AlarmValue => 6,
+            PropertyIdentifier::AlarmValues => 7,
+            PropertyIdentifier::All => 8,
+            PropertyIdentifier::AllWritesSuccessful => 9,
+            PropertyIdentifier::ApduSegmentTimeout => 10,
+            PropertyIdentifier::ApduTimeout => 11,
+            PropertyIdentifier::ApplicationSoftwareVersion => 12,
+            PropertyIdentifier::Archive => 13,
+            PropertyIdentifier::Bias => 14,
+            PropertyIdentifier::ChangeOfStateCount => 15,
+            PropertyIdentifier::ChangeOfStateTime => 16,
+            PropertyIdentifier::NotificationClass => 17,
+            PropertyIdentifier::ControlledVariableReference => 19,
+            PropertyIdentifier::ControlledVariableUnits => 20,
+            PropertyIdentifier::ControlledVariableValue => 21,
+            PropertyIdentifier::CovIncrement => 22,
+            PropertyIdentifier::DateList => 23,
+            PropertyIdentifier::DaylightSavingsStatus => 24,
+            PropertyIdentifier::Deadband => 25,
+            PropertyIdentifier::DerivativeConstant => 26,
+            PropertyIdentifier::DerivativeConstantUnits => 27,
+            PropertyIdentifier::Description => 28,
+            PropertyIdentifier::DescriptionOfHalt => 29,
+            PropertyIdentifier::DeviceAddressBinding => 30,
+            PropertyIdentifier::DeviceType => 31,
+            PropertyIdentifier::EffectivePeriod => 32,
+            PropertyIdentifier::ElapsedActiveTime => 33,
+            PropertyIdentifier::ErrorLimit => 34,
+            PropertyIdentifier::EventEnable => 35,
+            PropertyIdentifier::EventState => 36,
+            PropertyIdentifier::EventType => 37,
+            PropertyIdentifier::ExceptionSchedule => 38,
+            PropertyIdentifier::FaultValues => 39,
+            PropertyIdentifier::FeedbackValue => 40,
+            PropertyIdentifier::FileAccessMethod => 41,
+            PropertyIdentifier::FileSize => 42,
+            PropertyIdentifier::FileType => 43,
+            PropertyIdentifier::FirmwareRevision => 44,
+            PropertyIdentifier::HighLimit => 45,
+            PropertyIdentifier::InactiveText => 46,
+            PropertyIdentifier::InProcess => 47,
+            PropertyIdentifier::InstanceOf => 48,
+            PropertyIdentifier::IntegralConstant => 49,
+            PropertyIdentifier::IntegralConstantUnits => 50,
+            PropertyIdentifier::LimitEnable => 52,
+            PropertyIdentifier::ListOfGroupMembers => 53,
+            PropertyIdentifier::ListOfObjectPropertyReferences => 54,
+            PropertyIdentifier::LocalDate => 56,
+            PropertyIdentifier::LocalTime => 57,
+            PropertyIdentifier::Location => 58,
+            PropertyIdentifier::LowLimit => 59,
+            PropertyIdentifier::ManipulatedVariableReference => 60,
+            PropertyIdentifier::MaximumOutput => 61,
+            PropertyIdentifier::MaxApduLengthAccepted => 62,
+            PropertyIdentifier::MaxInfoFrames => 63,
+            PropertyIdentifier::MaxMaster => 64,
+            PropertyIdentifier::MaxPresValue => 65,
+            PropertyIdentifier::MinimumOffTime => 66,
+            PropertyIdentifier::MinimumOnTime => 67,
+            PropertyIdentifier::MinimumOutput => 68,
+            PropertyIdentifier::MinPresValue => 69,
+            PropertyIdentifier::ModelName => 70,
+            PropertyIdentifier::ModificationDate => 71,
+            PropertyIdentifier::NotifyType => 72,
+            PropertyIdentifier::NumberOfApduRetries => 73,
+            PropertyIdentifier::NumberOfStates => 74,
+            PropertyIdentifier::ObjectIdentifier => 75,
+            PropertyIdentifier::ObjectList => 76,
+            PropertyIdentifier::ObjectName => 77,
+            PropertyIdentifier::ObjectPropertyReference => 78,
+            PropertyIdentifier::ObjectType => 79,
+            PropertyIdentifier::Optional => 80,
+            PropertyIdentifier::OutOfService => 81,
+            PropertyIdentifier::OutputUnits => 82,
+            PropertyIdentifier::EventParameters => 83,
+            PropertyIdentifier::Polarity => 84,
+            PropertyIdentifier::PresentValue => 85,
+            PropertyIdentifier::Priority => 86,
+            PropertyIdentifier::PriorityArray => 87,
+            PropertyIdentifier::PriorityForWriting => 88,
+            PropertyIdentifier::ProcessIdentifier => 89,
+            PropertyIdentifier::ProgramChange => 90,
+            PropertyIdentifier::ProgramLocation => 91,
+            PropertyIdentifier::ProgramState => 92,
+            PropertyIdentifier::ProportionalConstant => 93,
+            PropertyIdentifier::ProportionalConstantUnits => 94,
+            PropertyIdentifier::ProtocolObjectTypesSupported => 96,
+            PropertyIdentifier::ProtocolServicesSupported => 97,
+            PropertyIdentifier::ProtocolVersion => 98,
+            PropertyIdentifier::ReadOnly => 99,
+            PropertyIdentifier::ReasonForHalt => 100,
+            PropertyIdentifier::RecipientList => 102,
+            PropertyIdentifier::Reliability => 103,
+            PropertyIdentifier::RelinquishDefault => 104,
+            PropertyIdentifier::Required => 105,
+            PropertyIdentifier::Resolution => 106,
+            PropertyIdentifier::SegmentationSupported => 107,
+            PropertyIdentifier::Setpoint => 108,
+            PropertyIdentifier::SetpointReference => 109,
+            PropertyIdentifier::StateText => 110,
+            PropertyIdentifier::StatusFlags => 111,
+            PropertyIdentifier::SystemStatus => 112,
+            PropertyIdentifier::TimeDelay => 113,
+            PropertyIdentifier::TimeOfActiveTimeReset => 114,
+            PropertyIdentifier::TimeOfStateCountReset => 115,
+            PropertyIdentifier::TimeSynchronizationRecipients => 116,
+            PropertyIdentifier::Units => 117,
+            PropertyIdentifier::UpdateInterval => 118,
+            PropertyIdentifier::UtcOffset => 119,
+            PropertyIdentifier::VendorIdentifier => 120,
+            PropertyIdentifier::VendorName => 121,
+            PropertyIdentifier::VtClassesSupported => 122,
+            PropertyIdentifier::WeeklySchedule => 123,
+            PropertyIdentifier::AttemptedSamples => 124,
+            PropertyIdentifier::AverageValue => 125,
+            PropertyIdentifier::BufferSize => 126,
+            PropertyIdentifier::ClientCovIncrement => 127,
+            PropertyIdentifier::CovResubscriptionInterval => 128,
+            PropertyIdentifier::EventTimeStamps => 130,
+            PropertyIdentifier::LogBuffer => 131,
+            PropertyIdentifier::LogDeviceObjectProperty => 132,
+            PropertyIdentifier::Enable => 133,
+            PropertyIdentifier::LogInterval => 134,
+            PropertyIdentifier::MaximumValue => 135,
+            PropertyIdentifier::MinimumValue => 136,
+            PropertyIdentifier::NotificationThreshold => 137,
+            PropertyIdentifier::ProtocolRevision => 139,
+            PropertyIdentifier::RecordsSinceNotification => 140,
+            PropertyIdentifier::RecordCount => 141,
+            PropertyIdentifier::StartTime => 142,
+            PropertyIdentifier::StopTime => 143,
+            PropertyIdentifier::StopWhenFull => 144,
+            PropertyIdentifier::TotalRecordCount => 145,
+            PropertyIdentifier::ValidSamples => 146,
+            PropertyIdentifier::WindowInterval => 147,
+            PropertyIdentifier::WindowSamples => 148,
+            PropertyIdentifier::MaximumValueTimestamp => 149,
+            PropertyIdentifier::MinimumValueTimestamp => 150,
+            PropertyIdentifier::VarianceValue => 151,
+            PropertyIdentifier::ActiveCovSubscriptions => 152,
+            PropertyIdentifier::BackupFailureTimeout => 153,
+            PropertyIdentifier::ConfigurationFiles => 154,
+            PropertyIdentifier::DatabaseRevision => 155,
+            PropertyIdentifier::DirectReading => 156,
+            PropertyIdentifier::LastRestoreTime => 157,
+            PropertyIdentifier::MaintenanceRequired => 158,
+            PropertyIdentifier::MemberOf => 159,
+            PropertyIdentifier::Mode => 160,
+            PropertyIdentifier::OperationExpected => 161,
+            PropertyIdentifier::Setting => 162,
+            PropertyIdentifier::Silenced => 163,
+            PropertyIdentifier::TrackingValue => 164,
+            PropertyIdentifier::ZoneMembers => 165,
+            PropertyIdentifier::LifeSafetyAlarmValues => 166,
+            PropertyIdentifier::MaxSegmentsAccepted => 167,
+            PropertyIdentifier::ProfileName => 168,
+            PropertyIdentifier::EventMessageTexts => 351,
+            PropertyIdentifier::PropertyList => 371,
+            PropertyIdentifier::Reserved(v) => v,
+            PropertyIdentifier::Proprietary(v) => v,
+        }
+    }
+}
+
+impl core::fmt::Display for PropertyIdentifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", u32::from(*self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_identifier_present_value() {
+        assert_eq!(
+            PropertyIdentifier::try_from(85).unwrap(),
+            PropertyIdentifier::PresentValue
+        );
+        assert_eq!(u32::from(PropertyIdentifier::PresentValue), 85);
+        assert_eq!(u32::from(PropertyIdentifier::StatusFlags), 111);
+        assert_eq!(u32::from(PropertyIdentifier::PriorityArray), 87);
+    }
+
+    #[test]
+    fn test_property_identifier_proprietary() {
+        let property = PropertyIdentifier::try_from(5000).unwrap();
+        assert_eq!(property, PropertyIdentifier::Proprietary(5000));
+        assert_eq!(u32::from(property), 5000);
+    }
+
+    #[test]
+    fn test_property_identifier_reserved() {
+        for value in [18, 169, 511] {
+            let property = PropertyIdentifier::try_from(value).unwrap();
+            assert_eq!(property, PropertyIdentifier::Reserved(value));
+            assert_eq!(u32::from(property), value);
+        }
+    }
+
+    #[test]
+    fn test_property_identifier_out_of_range() {
+        assert!(PropertyIdentifier::try_from(MAX_PROPERTY_IDENTIFIER + 1).is_err());
+    }
+
+    #[test]
+    fn test_display_property_identifier() {
+        assert_eq!(
+            PropertyIdentifier::PresentValue.to_string(),
+            "present-value"
+        );
+        assert_eq!(
+            PropertyIdentifier::MaxApduLengthAccepted.to_string(),
+            "max-apdu-length-accepted"
+        );
+        assert_eq!(PropertyIdentifier::Proprietary(1000).to_string(), "1000");
+    }
+}
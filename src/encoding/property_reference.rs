@@ -0,0 +1,189 @@
+use crate::encoding::{
+    ObjectIdentifier, PropertyValue, Tag, decode_context, decode_enumerated,
+    decode_optional_context, decode_unsigned, encode_enumerated, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::vec::Vec;
+
+/// BACnetDeviceObjectPropertyReference (21), a property of an object that may be in another
+/// device
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DeviceObjectPropertyReference {
+    pub object_identifier: ObjectIdentifier,
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    /// Device containing the object, None for the local device
+    pub device_identifier: Option<ObjectIdentifier>,
+}
+
+impl DeviceObjectPropertyReference {
+    pub fn new(object_identifier: ObjectIdentifier, property_identifier: impl Into<u32>) -> Self {
+        Self {
+            object_identifier,
+            property_identifier: property_identifier.into(),
+            property_array_index: None,
+            device_identifier: None,
+        }
+    }
+
+    pub fn array_index(mut self, index: u32) -> Self {
+        self.property_array_index = Some(index);
+        self
+    }
+
+    pub fn device(mut self, device_identifier: ObjectIdentifier) -> Self {
+        self.device_identifier = Some(device_identifier);
+        self
+    }
+
+    /// Decode the elements of an array or list of references, as read from a property
+    pub fn decode_list(value: &PropertyValue) -> crate::Result<Vec<Self>> {
+        let mut rest = match value {
+            PropertyValue::Constructed(data) => &data[..],
+            PropertyValue::Values(values) if values.is_empty() => &[][..],
+            v => {
+                return Err(Error::InvalidValue(format!(
+                    "Not a list of references: {:?}",
+                    v
+                )));
+            }
+        };
+        let mut references = Vec::new();
+        while !rest.is_empty() {
+            let (reference, next) = decode_device_object_property_reference(rest)?;
+            references.push(reference);
+            rest = next;
+        }
+        Ok(references)
+    }
+}
+
+impl Encode for DeviceObjectPropertyReference {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        Tag::context(0, &self.object_identifier.encode_vec()?).encode(writer)?;
+        Tag::context(1, &encode_enumerated(self.property_identifier)).encode(writer)?;
+        if let Some(index) = self.property_array_index {
+            Tag::context(2, &encode_unsigned(index)).encode(writer)?;
+        }
+        if let Some(device_identifier) = self.device_identifier {
+            Tag::context(3, &device_identifier.encode_vec()?).encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 1 + 4; // Object Identifier
+        l += 1 + encode_enumerated(self.property_identifier).len();
+        if let Some(index) = self.property_array_index {
+            l += 1 + encode_unsigned(index).len();
+        }
+        if self.device_identifier.is_some() {
+            l += 1 + 4; // Device Identifier
+        }
+        l
+    }
+}
+
+impl Decode for DeviceObjectPropertyReference {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Ok(decode_device_object_property_reference(&data)?.0)
+    }
+}
+
+/// Decode the reference at the start of `input`, returning it together with the remaining input
+pub fn decode_device_object_property_reference(
+    input: &[u8],
+) -> crate::Result<(DeviceObjectPropertyReference, &[u8])> {
+    let (object_identifier, rest) = decode_context(input, 0)?;
+    let (property_identifier, rest) = decode_context(rest, 1)?;
+    let (property_array_index, rest) = decode_optional_context(rest, 2)?;
+    let (device_identifier, rest) = decode_optional_context(rest, 3)?;
+    let reference = DeviceObjectPropertyReference {
+        object_identifier: ObjectIdentifier::decode_slice(object_identifier)?,
+        property_identifier: decode_enumerated(property_identifier)?,
+        property_array_index: property_array_index.map(decode_unsigned).transpose()?,
+        device_identifier: device_identifier
+            .map(ObjectIdentifier::decode_slice)
+            .transpose()?,
+    };
+    Ok((reference, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{ApplicationValue, ObjectType, PropertyIdentifier};
+
+    #[test]
+    fn test_device_object_property_reference_round_trip() {
+        let reference = DeviceObjectPropertyReference::new(
+            ObjectIdentifier::new(ObjectType::AnalogInput, 5).unwrap(),
+            PropertyIdentifier::PresentValue,
+        )
+        .device(ObjectIdentifier::new(ObjectType::Device, 100).unwrap());
+
+        let data = reference.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("0c0000000519553c02000064").unwrap());
+        assert_eq!(data.len(), reference.len());
+        assert_eq!(
+            DeviceObjectPropertyReference::decode_slice(&data).unwrap(),
+            reference
+        );
+
+        let reference = reference.array_index(3);
+        let data = reference.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("0c00000005195529033c02000064").unwrap());
+        assert_eq!(
+            DeviceObjectPropertyReference::decode_slice(&data).unwrap(),
+            reference
+        );
+    }
+
+    #[test]
+    fn test_decode_device_object_property_reference_list() {
+        // analog-input,5 present-value on device,100 and analog-value,2 priority-array[8]
+        let data = hex::decode("0c0000000519553c020000640c0080000219572908").unwrap();
+        let value = PropertyValue::decode_slice(&data).unwrap();
+
+        let references = DeviceObjectPropertyReference::decode_list(&value).unwrap();
+        assert_eq!(
+            references,
+            vec![
+                DeviceObjectPropertyReference::new(
+                    ObjectIdentifier::new(ObjectType::AnalogInput, 5).unwrap(),
+                    PropertyIdentifier::PresentValue,
+                )
+                .device(ObjectIdentifier::new(ObjectType::Device, 100).unwrap()),
+                DeviceObjectPropertyReference::new(
+                    ObjectIdentifier::new(ObjectType::AnalogValue, 2).unwrap(),
+                    PropertyIdentifier::PriorityArray,
+                )
+                .array_index(8),
+            ]
+        );
+
+        let empty = PropertyValue::Values(vec![]);
+        assert!(
+            DeviceObjectPropertyReference::decode_list(&empty)
+                .unwrap()
+                .is_empty()
+        );
+        let err = DeviceObjectPropertyReference::decode_list(&PropertyValue::from(
+            ApplicationValue::Real(1.0),
+        ))
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_device_object_property_reference_truncated() {
+        let err = DeviceObjectPropertyReference::decode_slice(&[0x0c, 0x00, 0x00]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+}
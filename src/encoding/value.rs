@@ -0,0 +1,472 @@
+use crate::encoding::{
+    ApplicationTag, BitString, Date, ObjectIdentifier, Tag, TagNumber, Time, decode_boolean,
+    decode_character_string, decode_double, decode_enumerated, decode_null, decode_octet_string,
+    decode_real, decode_signed, decode_unsigned, encode_character_string, encode_double,
+    encode_enumerated, encode_octet_string, encode_real, encode_signed, encode_unsigned,
+};
+use crate::{Decode, Encode, Error};
+use alloc::{string::String, vec::Vec};
+
+/// An application tagged primitive value (20.2.1.4)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApplicationValue {
+    Null,
+    Boolean(bool),
+    Unsigned(u32),
+    Signed(i32),
+    Real(f32),
+    Double(f64),
+    OctetString(Vec<u8>),
+    CharacterString(String),
+    BitString(BitString),
+    Enumerated(u32),
+    Date(Date),
+    Time(Time),
+    ObjectId(ObjectIdentifier),
+}
+
+impl ApplicationValue {
+    pub fn application_tag(&self) -> ApplicationTag {
+        match self {
+            Self::Null => ApplicationTag::Null,
+            Self::Boolean(_) => ApplicationTag::Boolean,
+            Self::Unsigned(_) => ApplicationTag::UnsignedInteger,
+            Self::Signed(_) => ApplicationTag::SignedInteger,
+            Self::Real(_) => ApplicationTag::Real,
+            Self::Double(_) => ApplicationTag::Double,
+            Self::OctetString(_) => ApplicationTag::OctetString,
+            Self::CharacterString(_) => ApplicationTag::CharacterString,
+            Self::BitString(_) => ApplicationTag::BitString,
+            Self::Enumerated(_) => ApplicationTag::Enumerated,
+            Self::Date(_) => ApplicationTag::Date,
+            Self::Time(_) => ApplicationTag::Time,
+            Self::ObjectId(_) => ApplicationTag::BACnetObjectIdentifier,
+        }
+    }
+
+    /// Content octets of the value, a Boolean has none as its value is carried in the tag
+    fn data(&self) -> crate::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Null | Self::Boolean(_) => vec![],
+            Self::Unsigned(v) => encode_unsigned(*v),
+            Self::Signed(v) => encode_signed(*v),
+            Self::Real(v) => encode_real(*v),
+            Self::Double(v) => encode_double(*v),
+            Self::OctetString(v) => encode_octet_string(v),
+            Self::CharacterString(v) => encode_character_string(v),
+            Self::BitString(v) => v.encode_vec()?,
+            Self::Enumerated(v) => encode_enumerated(*v),
+            Self::Date(v) => v.encode_vec()?,
+            Self::Time(v) => v.encode_vec()?,
+            Self::ObjectId(v) => v.encode_vec()?,
+        })
+    }
+
+    fn data_len(&self) -> usize {
+        match self {
+            Self::Null | Self::Boolean(_) => 0,
+            Self::Unsigned(v) | Self::Enumerated(v) => encode_unsigned(*v).len(),
+            Self::Signed(v) => encode_signed(*v).len(),
+            Self::Real(_) | Self::Date(_) | Self::Time(_) | Self::ObjectId(_) => 4,
+            Self::Double(_) => 8,
+            Self::OctetString(v) => v.len(),
+            Self::CharacterString(v) => 1 + v.len(),
+            Self::BitString(v) => v.len(),
+        }
+    }
+}
+
+impl Encode for ApplicationValue {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        match self {
+            Self::Boolean(v) => Tag::boolean(*v).encode(writer),
+            v => Tag::application(v.application_tag(), &v.data()?).encode(writer),
+        }
+    }
+
+    fn len(&self) -> usize {
+        let data_len = self.data_len();
+        let extended_length = match data_len {
+            0..=4 => 0,
+            5..=253 => 1,
+            254..=65535 => 3,
+            _ => 5,
+        };
+        1 + extended_length + data_len
+    }
+}
+
+/// Formatted for logs, e.g. 72.5 or "AHU-1", octet strings as hex
+impl core::fmt::Display for ApplicationValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Boolean(v) => write!(f, "{}", v),
+            Self::Unsigned(v) | Self::Enumerated(v) => write!(f, "{}", v),
+            Self::Signed(v) => write!(f, "{}", v),
+            Self::Real(v) => write!(f, "{}", v),
+            Self::Double(v) => write!(f, "{}", v),
+            Self::OctetString(v) => write!(f, "{}", hex::encode(v)),
+            Self::CharacterString(v) => write!(f, "{:?}", v),
+            Self::BitString(v) => write!(f, "{:?}", v),
+            Self::Date(v) => write!(f, "{:?}", v),
+            Self::Time(v) => write!(f, "{:?}", v),
+            Self::ObjectId(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for bool {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Boolean(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::Boolean,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for u32 {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Unsigned(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::UnsignedInteger,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for i32 {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Signed(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::SignedInteger,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for f32 {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Real(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::Real,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for f64 {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::Double(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::Double,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ApplicationValue> for String {
+    type Error = Error;
+
+    fn try_from(value: ApplicationValue) -> Result<Self, Self::Error> {
+        match value {
+            ApplicationValue::CharacterString(v) => Ok(v),
+            v => Err(Error::TypeMismatch {
+                expected: ApplicationTag::CharacterString,
+                found: v.application_tag(),
+            }),
+        }
+    }
+}
+
+/// Decode the application tagged value at the start of `input`, returning it together with
+/// the remaining input
+pub fn decode_application_value(input: &[u8]) -> crate::Result<(ApplicationValue, &[u8])> {
+    let (tag, rest) = Tag::decode(input)?;
+    let application_tag = match tag.tag_number() {
+        TagNumber::Application(t) => t,
+        t => {
+            return Err(Error::InvalidTag(format!(
+                "Expected application tag, found {:?}",
+                t
+            )));
+        }
+    };
+    let data = tag.data();
+    let value = match application_tag {
+        ApplicationTag::Null => decode_null(&tag).map(|_| ApplicationValue::Null)?,
+        ApplicationTag::Boolean => ApplicationValue::Boolean(decode_boolean(&tag)?),
+        ApplicationTag::UnsignedInteger => ApplicationValue::Unsigned(decode_unsigned(data)?),
+        ApplicationTag::SignedInteger => ApplicationValue::Signed(decode_signed(data)?),
+        ApplicationTag::Real => ApplicationValue::Real(decode_real(data)?),
+        ApplicationTag::Double => ApplicationValue::Double(decode_double(data)?),
+        ApplicationTag::OctetString => {
+            ApplicationValue::OctetString(decode_octet_string(data).to_vec())
+        }
+        ApplicationTag::CharacterString => {
+            ApplicationValue::CharacterString(decode_character_string(data)?)
+        }
+        ApplicationTag::BitString => ApplicationValue::BitString(BitString::decode_slice(data)?),
+        ApplicationTag::Enumerated => ApplicationValue::Enumerated(decode_enumerated(data)?),
+        ApplicationTag::Date => ApplicationValue::Date(Date::decode_slice(data)?),
+        ApplicationTag::Time => ApplicationValue::Time(Time::decode_slice(data)?),
+        ApplicationTag::BACnetObjectIdentifier => {
+            ApplicationValue::ObjectId(ObjectIdentifier::decode_slice(data)?)
+        }
+        t => {
+            return Err(Error::Unsupported(format!(
+                "Application tag not supported: {:?}",
+                t
+            )));
+        }
+    };
+    Ok((value, rest))
+}
+
+/// Value of a property, as enclosed in the property-value of ReadProperty and WriteProperty
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// Application tagged values, a single value or the elements of an array or list
+    Values(Vec<ApplicationValue>),
+    /// A constructed value containing context tags, kept as its encoded octets
+    Constructed(Vec<u8>),
+}
+
+impl PropertyValue {
+    /// The value if it is a single application tagged value
+    pub fn single(&self) -> Option<&ApplicationValue> {
+        match self {
+            Self::Values(v) if v.len() == 1 => v.first(),
+            _ => None,
+        }
+    }
+
+    /// Encoded octets of a constructed value whose structure is not decoded, such as a
+    /// security property of a device
+    pub fn raw(&self) -> Option<&[u8]> {
+        match self {
+            Self::Constructed(data) => Some(data),
+            Self::Values(_) => None,
+        }
+    }
+
+    /// Take the value if it is a single application tagged value
+    pub fn into_single(self) -> Option<ApplicationValue> {
+        match self {
+            Self::Values(mut v) if v.len() == 1 => v.pop(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ApplicationValue> for PropertyValue {
+    fn from(value: ApplicationValue) -> Self {
+        Self::Values(vec![value])
+    }
+}
+
+/// Formatted for logs, a list of values in braces and a constructed value by its length only
+impl core::fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Values(values) if values.len() == 1 => write!(f, "{}", values[0]),
+            Self::Values(values) => {
+                f.write_str("{")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("}")
+            }
+            Self::Constructed(data) => write!(f, "constructed value of {} octets", data.len()),
+        }
+    }
+}
+
+impl Encode for PropertyValue {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        match self {
+            Self::Values(values) => values.iter().try_for_each(|v| v.encode(writer)),
+            Self::Constructed(data) => writer.write_all(data),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Values(values) => values.iter().map(|v| v.len()).sum(),
+            Self::Constructed(data) => data.len(),
+        }
+    }
+}
+
+impl Decode for PropertyValue {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut values = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (tag, _) = Tag::decode(rest)?;
+            if !matches!(tag.tag_number(), TagNumber::Application(_)) {
+                return Ok(Self::Constructed(data));
+            }
+            let (value, next) = decode_application_value(rest)?;
+            values.push(value);
+            rest = next;
+        }
+        Ok(Self::Values(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+
+    #[test]
+    fn test_decode_mixed_values() {
+        let mut input = hex::decode("4442910000").unwrap(); // REAL 72.5
+        input.extend(hex::decode("7506004148552d31").unwrap()); // "AHU-1"
+
+        let (value, rest) = decode_application_value(&input).unwrap();
+        assert_eq!(value, ApplicationValue::Real(72.5));
+        let (value, rest) = decode_application_value(rest).unwrap();
+        assert_eq!(
+            value,
+            ApplicationValue::CharacterString("AHU-1".to_string())
+        );
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_application_value_round_trip() {
+        let values = vec![
+            ApplicationValue::Null,
+            ApplicationValue::Boolean(true),
+            ApplicationValue::Unsigned(300),
+            ApplicationValue::Signed(-129),
+            ApplicationValue::Real(-33.3),
+            ApplicationValue::Double(std::f64::consts::E),
+            ApplicationValue::OctetString(vec![0xaa; 300]),
+            ApplicationValue::CharacterString("Température".to_string()),
+            ApplicationValue::BitString(BitString::new(vec![false, true, false, false])),
+            ApplicationValue::Enumerated(8),
+            ApplicationValue::Date(Date::new(2024, 3, 1, 5).unwrap()),
+            ApplicationValue::Time(Time::new(12, 30, 0, 0)),
+            ApplicationValue::ObjectId(ObjectIdentifier::new(ObjectType::Device, 260001).unwrap()),
+        ];
+        for value in values {
+            let encoded = value.encode_vec().unwrap();
+            assert_eq!(encoded.len(), value.len(), "{:?}", value);
+            let (decoded, rest) = decode_application_value(&encoded).unwrap();
+            assert_eq!(decoded, value);
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_property_value() {
+        let value = PropertyValue::decode_slice(&[0x44, 0x42, 0x91, 0x00, 0x00]).unwrap();
+        assert_eq!(value.single(), Some(&ApplicationValue::Real(72.5)));
+
+        let data = hex::decode("c40200000ac400000001").unwrap();
+        let value = PropertyValue::decode_slice(&data).unwrap();
+        assert_eq!(value.single(), None);
+        assert_eq!(value.encode_vec().unwrap(), data);
+
+        let data = hex::decode("0e1c000000011f").unwrap();
+        let value = PropertyValue::decode_slice(&data).unwrap();
+        assert_eq!(value, PropertyValue::Constructed(data.clone()));
+        assert_eq!(value.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_context_tag_is_not_an_application_value() {
+        assert!(decode_application_value(&[0x09, 0x01]).is_err());
+        assert!(decode_application_value(&[0xd1, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_convert_application_value() {
+        assert_eq!(f32::try_from(ApplicationValue::Real(72.5)).unwrap(), 72.5);
+        assert!(bool::try_from(ApplicationValue::Boolean(true)).unwrap());
+        assert_eq!(
+            String::try_from(ApplicationValue::CharacterString("AHU-1".to_string())).unwrap(),
+            "AHU-1"
+        );
+
+        let err = u32::try_from(ApplicationValue::Enumerated(1)).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::TypeMismatch {
+                    expected: ApplicationTag::UnsignedInteger,
+                    found: ApplicationTag::Enumerated
+                }
+            ),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_display_values() {
+        assert_eq!(ApplicationValue::Real(72.5).to_string(), "72.5");
+        assert_eq!(
+            ApplicationValue::CharacterString("AHU-1".to_string()).to_string(),
+            "\"AHU-1\""
+        );
+        let object_identifier = ObjectIdentifier::new(ObjectType::Device, 1).unwrap();
+        let value = PropertyValue::Values(vec![
+            ApplicationValue::ObjectId(object_identifier),
+            ApplicationValue::Unsigned(3),
+        ]);
+        assert_eq!(value.to_string(), "{device,1, 3}");
+        assert_eq!(
+            PropertyValue::Constructed(vec![0x0e, 0x0f]).to_string(),
+            "constructed value of 2 octets"
+        );
+    }
+
+    #[test]
+    fn test_unknown_constructed_value_kept_raw() {
+        // Unsigned 5 followed by [0] { [1] OCTET STRING 010203 }, a structure not modelled
+        let data = hex::decode("21050e1b0102030f").unwrap();
+        let value = PropertyValue::decode_slice(&data).unwrap();
+
+        assert_eq!(value.raw(), Some(&data[..]));
+        assert_eq!(value.single(), None);
+        assert_eq!(value.encode_vec().unwrap(), data);
+        assert_eq!(
+            PropertyValue::from(ApplicationValue::Unsigned(5)).raw(),
+            None
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::application::{AbortReason, BACnetError, RejectReason};
+use crate::encoding::ApplicationTag;
+use alloc::string::String;
+use core::fmt;
+
+/// Result of encoding or decoding BACnet data
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error of a failed encode or decode, telling truncated input apart from malformed or
+/// unsupported data
+#[derive(Debug)]
+pub enum Error {
+    /// Reading from or writing to the underlying stream failed
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// The input ended before the data was complete
+    Truncated,
+    /// The output buffer is too small for the encoded data
+    BufferTooSmall,
+    /// A malformed tag, or a tag other than the expected one (20.2.1)
+    InvalidTag(String),
+    /// A field whose value is malformed or out of range
+    InvalidValue(String),
+    /// A Character String in a character set that is not supported (20.2.9)
+    UnsupportedCharset(u8),
+    /// A service choice that is not supported
+    UnknownService(u8),
+    /// A write priority outside 1..=16 (19.2)
+    InvalidPriority(u8),
+    /// A value of another application type than the expected one
+    TypeMismatch {
+        expected: ApplicationTag,
+        found: ApplicationTag,
+    },
+    /// A PDU type, message type or protocol version that is not supported
+    Unsupported(String),
+    /// A confirmed request was answered with a BACnet-Error-PDU
+    Service(BACnetError),
+    /// A confirmed request was answered with a BACnet-Reject-PDU
+    Rejected(RejectReason),
+    /// A confirmed request was answered with a BACnet-Abort-PDU
+    Aborted(AbortReason),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Truncated => write!(f, "Input truncated"),
+            Self::BufferTooSmall => write!(f, "Output buffer too small"),
+            Self::InvalidTag(msg) | Self::InvalidValue(msg) | Self::Unsupported(msg) => {
+                f.write_str(msg)
+            }
+            Self::UnsupportedCharset(c) => write!(f, "Character set not supported: {}", c),
+            Self::UnknownService(s) => write!(f, "Service not supported: {}", s),
+            Self::InvalidPriority(p) => write!(f, "Priority out of range: {}", p),
+            Self::TypeMismatch { expected, found } => {
+                write!(f, "Expected {:?}, found {:?}", expected, found)
+            }
+            Self::Service(e) => write!(f, "Error: {:?}, {:?}", e.class, e.code),
+            Self::Rejected(reason) => write!(f, "Rejected: {:?}", reason),
+            Self::Aborted(reason) => write!(f, "Aborted: {:?}", reason),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A reader running out of data is reported as `Truncated`
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => Self::Truncated,
+            _ => Self::Io(e),
+        }
+    }
+}
+
+/// Lets codec errors propagate through the I/O based client and server
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            Error::Truncated => Self::new(std::io::ErrorKind::UnexpectedEof, Error::Truncated),
+            Error::BufferTooSmall => {
+                Self::new(std::io::ErrorKind::WriteZero, Error::BufferTooSmall)
+            }
+            e @ (Error::Service(_) | Error::Rejected(_) | Error::Aborted(_)) => Self::other(e),
+            e => Self::new(std::io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error() {
+        let eof = std::io::Error::from(std::io::ErrorKind::UnexpectedEof);
+        assert!(matches!(Error::from(eof), Error::Truncated));
+        let other = std::io::Error::from(std::io::ErrorKind::BrokenPipe);
+        assert!(matches!(Error::from(other), Error::Io(_)));
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let e = std::io::Error::from(Error::Truncated);
+        assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+        let e = std::io::Error::from(Error::UnknownService(42));
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "Service not supported: 42");
+    }
+}
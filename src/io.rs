@@ -0,0 +1,161 @@
+//! Octet sources and sinks of the codecs, usable without std
+//!
+//! With the `std` feature every `std::io::Read` and `std::io::Write` implements these traits,
+//! without it they are implemented for `&[u8]`, `Vec<u8>` and `SliceWriter`.
+use crate::Error;
+use alloc::vec::Vec;
+
+/// Source of the octets read by `Decode`, multi-octet integers are big-endian
+pub trait Read {
+    /// Fill `buf` entirely, failing with `Error::Truncated` if the input ends first
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::Result<()>;
+
+    /// Append all remaining octets to `buf`, returning their number
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::Result<usize>;
+
+    fn read_u8(&mut self) -> crate::Result<u8> {
+        let mut buf = [0u8; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_u16(&mut self) -> crate::Result<u16> {
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn read_u32(&mut self) -> crate::Result<u32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+}
+
+/// Sink of the octets written by `Encode`, multi-octet integers are big-endian
+pub trait Write {
+    /// Write all of `buf`, failing with `Error::BufferTooSmall` if it does not fit
+    fn write_all(&mut self, buf: &[u8]) -> crate::Result<()>;
+
+    fn write_u8(&mut self, value: u8) -> crate::Result<()> {
+        self.write_all(&[value])
+    }
+
+    fn write_u16(&mut self, value: u16) -> crate::Result<()> {
+        self.write_all(&value.to_be_bytes())
+    }
+
+    fn write_u32(&mut self, value: u32) -> crate::Result<()> {
+        self.write_all(&value.to_be_bytes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read + ?Sized> Read for R {
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::Result<()> {
+        Ok(std::io::Read::read_exact(self, buf)?)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::Result<usize> {
+        Ok(std::io::Read::read_to_end(self, buf)?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for W {
+    fn write_all(&mut self, buf: &[u8]) -> crate::Result<()> {
+        Ok(std::io::Write::write_all(self, buf)?)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Read for &[u8] {
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::Result<()> {
+        if self.len() < buf.len() {
+            return Err(Error::Truncated);
+        }
+        let (head, tail) = self.split_at(buf.len());
+        buf.copy_from_slice(head);
+        *self = tail;
+        Ok(())
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> crate::Result<usize> {
+        let n = self.len();
+        buf.extend_from_slice(self);
+        *self = &self[n..];
+        Ok(n)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Write for Vec<u8> {
+    fn write_all(&mut self, buf: &[u8]) -> crate::Result<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+}
+
+/// Writer filling a fixed buffer, for encoding without allocating
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, position: 0 }
+    }
+
+    /// Number of octets written
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The octets written so far
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.position]
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_all(&mut self, buf: &[u8]) -> crate::Result<()> {
+        let end = self.position + buf.len();
+        let dest = self
+            .buf
+            .get_mut(self.position..end)
+            .ok_or(Error::BufferTooSmall)?;
+        dest.copy_from_slice(buf);
+        self.position = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_big_endian() {
+        let mut reader: &[u8] = &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        assert_eq!(reader.read_u8().unwrap(), 0x01);
+        assert_eq!(reader.read_u16().unwrap(), 0x0203);
+        assert_eq!(reader.read_u32().unwrap(), 0x04050607);
+        assert!(matches!(reader.read_u8(), Err(Error::Truncated)));
+    }
+
+    #[test]
+    fn test_slice_writer() {
+        let mut buf = [0u8; 4];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write_u8(0x81).unwrap();
+        writer.write_u16(0x0b00).unwrap();
+        assert_eq!(writer.written(), &[0x81, 0x0b, 0x00]);
+        assert!(matches!(
+            writer.write_u16(0x0c01),
+            Err(Error::BufferTooSmall)
+        ));
+        assert_eq!(writer.position(), 3);
+    }
+}
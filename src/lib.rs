@@ -1,21 +1,38 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
 pub mod application;
+#[cfg(feature = "std")]
+pub mod client;
 pub mod encoding;
+pub mod error;
+pub mod io;
 pub mod network;
+#[cfg(feature = "std")]
+pub mod server;
 pub mod transport;
+pub use error::*;
+
+use alloc::vec::Vec;
+
+pub trait Decode: Sized {
+    /// Error of a failed decode, `Error` for the types of this crate
+    type Error: From<Error>;
 
-pub trait Decode<S: Decode = Self> {
-    fn decode<T: std::io::Read + Sized>(reader: &mut T) -> std::io::Result<S>;
+    fn decode<T: io::Read + Sized>(reader: &mut T) -> core::result::Result<Self, Self::Error>;
 
-    fn decode_slice(slice: &[u8]) -> std::io::Result<S> {
-        let mut reader = std::io::Cursor::new(slice);
-        S::decode(&mut reader)
+    fn decode_slice(slice: &[u8]) -> core::result::Result<Self, Self::Error> {
+        let mut reader = slice;
+        Self::decode(&mut reader)
     }
 }
 
 pub trait Encode {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()>;
+    fn encode<T: io::Write + Sized>(&self, writer: &mut T) -> Result<()>;
 
-    fn encode_vec(&self) -> std::io::Result<Vec<u8>> {
+    fn encode_vec(&self) -> Result<Vec<u8>> {
         let mut v = Vec::with_capacity(self.len());
         self.encode(&mut v)?;
         Ok(v)
@@ -30,13 +47,170 @@ pub trait Encode {
 
 #[cfg(test)]
 mod tests {
+    use crate::application::*;
+    use crate::encoding::*;
+    use crate::network::{NPDU, NPDUMessage};
+    #[cfg(feature = "std")]
+    use crate::transport::bacnetip::BVLC;
+    use crate::transport::ethernet::EthernetFrame;
+    use crate::transport::mstp::MstpFrame;
     use crate::{Decode, Encode};
 
+    /// Decode every prefix of `frame` and every copy of it with one octet replaced, decoding
+    /// may fail but must not panic
+    fn assert_decode_never_panics<D: Decode>(frame: &[u8]) {
+        for n in 0..=frame.len() {
+            let _ = D::decode_slice(&frame[..n]);
+        }
+        for i in 0..frame.len() {
+            for octet in [0x00, 0xff, frame[i] ^ 0x80, frame[i].wrapping_add(1)] {
+                let mut mutated = frame.to_vec();
+                mutated[i] = octet;
+                let _ = D::decode_slice(&mutated);
+            }
+        }
+    }
+
+    fn frames(frames: &[&str]) -> Vec<Vec<u8>> {
+        frames.iter().map(|f| hex::decode(f).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_bvlc_decode_never_panics() {
+        let bvlcs = frames(&[
+            "810b000c0120ffff00ff1008",
+            "810b00180120ffff00ff1000c4020000012205c49103210f",
+            "810a001401001000c4020000012205c49103210f",
+            "81040012c0a80164bac00120ffff00ff1008",
+            "8104001ac0a80164bac001001000c4020002572204009100210f",
+            "81010018c0a8010abac0ffffffff0a000001bac0ffffff00",
+            "81070018c0a80164bac00384012c0a000005bac0003c0005",
+            "8108000a0a000005bac0",
+            "810900060100",
+            "810b001401080002013e1800000a0100030000010a",
+            "81050006003c",
+            "810000060030",
+        ]);
+        for bvlc in &bvlcs {
+            #[cfg(feature = "std")]
+            assert_decode_never_panics::<BVLC>(bvlc);
+            assert_decode_never_panics::<NPDU>(&bvlc[4..]);
+        }
+    }
+
+    #[test]
+    fn test_npdu_decode_never_panics() {
+        let npdus = frames(&[
+            "0120ffff00ff1008",
+            "012800010203aabbcc0006c0a80164bac0ff0005010c0c000000011955",
+            "010800020106c0a80164bac01000c4020002572204009100210f",
+            "0180060201000a010203",
+            "018001000a000b",
+            "0180800104aabb",
+            "0180130001010001",
+        ]);
+        for npdu in &npdus {
+            assert_decode_never_panics::<NPDU>(npdu);
+        }
+        assert_decode_never_panics::<NPDUMessage>(
+            &hex::decode("06020001010aaa0002020000").unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_apdu_decode_never_panics() {
+        let apdus = frames(&[
+            "0005010c0c000000011955",
+            "0d05012a02010c0c000000011955",
+            "1000c4020002572204009100210f",
+            "30010c0c0000000119553e44429100003f",
+            "3c01020a0c0c0000000119553e44429100003f",
+            "40010203",
+            "50010c9101911f",
+            "600109",
+            "700105",
+        ]);
+        for apdu in &apdus {
+            assert_decode_never_panics::<APDU>(apdu);
+            assert_decode_never_panics::<ConfirmedRequest>(apdu);
+            assert_decode_never_panics::<ConfirmedServiceRequest>(apdu);
+        }
+    }
+
+    #[test]
+    fn test_service_decode_never_panics() {
+        assert_decode_never_panics::<UnconfirmedService>(
+            &hex::decode("00c4020002572204009100210f").unwrap(),
+        );
+        assert_decode_never_panics::<IHave>(
+            &hex::decode("c40203f7a1c4008000017506004148552d31").unwrap(),
+        );
+        assert_decode_never_panics::<WhoHas>(&hex::decode("0a03e81a07cf2c00000001").unwrap());
+        assert_decode_never_panics::<WhoHas>(&hex::decode("0a03e81a07cf3d06004148552d31").unwrap());
+        assert_decode_never_panics::<WhoIs>(&hex::decode("0a03e81a07cf").unwrap());
+        assert_decode_never_panics::<ReadProperty>(&hex::decode("0c000000011955").unwrap());
+        assert_decode_never_panics::<ReadPropertyAck>(
+            &hex::decode("0c0000000119553e44429100003f").unwrap(),
+        );
+        assert_decode_never_panics::<ReadPropertyAck>(
+            &hex::decode("0c00000001194e3e0e1c000000011f3f").unwrap(),
+        );
+        assert_decode_never_panics::<WriteProperty>(
+            &hex::decode("0c0080000119553e44429100003f4908").unwrap(),
+        );
+        assert_decode_never_panics::<ReadPropertyMultiple>(
+            &hex::decode("0c000000011e095509751f0c008000021e095509751f").unwrap(),
+        );
+        assert_decode_never_panics::<ReadPropertyMultipleAck>(
+            &hex::decode("0c000000011e29554e44429100004f29675e9101911f5f1f").unwrap(),
+        );
+        assert_decode_never_panics::<WritePropertyMultiple>(
+            &hex::decode("0c000000011e09553e44429100003f49081f").unwrap(),
+        );
+        assert_decode_never_panics::<SubscribeCov>(
+            &hex::decode("09121c0000000a29013a012c").unwrap(),
+        );
+        assert_decode_never_panics::<CovNotification>(
+            &hex::decode("09121c020000042c0000000a3a01184e0955").unwrap(),
+        );
+        assert_decode_never_panics::<PropertyValue>(&hex::decode("c40200000ac400000001").unwrap());
+        assert_decode_never_panics::<PropertyValue>(
+            &hex::decode("8207807506004148552d31a40102030491002105").unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_data_link_decode_never_panics() {
+        let apdu = APDU::new(0x01, 8, vec![]);
+        let npdu = NPDU::new(apdu, None, None, Default::default());
+        let mstp = MstpFrame::npdu(0x01, 0x02, &npdu).unwrap();
+        assert_decode_never_panics::<MstpFrame>(&mstp.encode_vec().unwrap());
+        assert_decode_never_panics::<MstpFrame>(&hex::decode("55ff00100500008c").unwrap());
+        let ethernet = EthernetFrame::broadcast([0x00, 0x11, 0x22, 0x33, 0x44, 0x55], npdu);
+        assert_decode_never_panics::<EthernetFrame>(&ethernet.encode_vec().unwrap());
+    }
+
+    #[test]
+    fn test_tag_decode_never_panics() {
+        let data = hex::decode("3e44429100003f4908f0ff0565ff0000000a5501020304").unwrap();
+        for n in 0..=data.len() {
+            let mut rest = &data[..n];
+            while let Ok((_, next)) = Tag::decode(rest) {
+                if next.len() == rest.len() {
+                    break;
+                }
+                rest = next;
+            }
+            let _ = decode_enclosed(&data[..n], 3);
+            let _ = decode_application_value(&data[..n]);
+        }
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, Default)]
     pub struct Dummy {}
 
     impl Encode for Dummy {
-        fn encode<T: std::io::Write + Sized>(&self, _writer: &mut T) -> std::io::Result<()> {
+        fn encode<T: crate::io::Write + Sized>(&self, _writer: &mut T) -> crate::Result<()> {
             Ok(())
         }
 
@@ -46,7 +220,9 @@ mod tests {
     }
 
     impl Decode for Dummy {
-        fn decode<T: std::io::Read + Sized>(_reader: &mut T) -> std::io::Result<Self> {
+        type Error = crate::Error;
+
+        fn decode<T: crate::io::Read + Sized>(_reader: &mut T) -> crate::Result<Self> {
             Ok(Self {})
         }
     }
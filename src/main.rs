@@ -1,5 +1,7 @@
 use bacnet::application::*;
+use bacnet::encoding::{ObjectIdentifier, ObjectType};
 use bacnet::network::*;
+use bacnet::server::Device;
 use bacnet::transport::bacnetip::*;
 use bacnet::{Decode, Encode};
 
@@ -13,6 +15,10 @@ fn main() {
         let socket = UdpSocket::bind(format!("0.0.0.0:{}", 0)).await.unwrap();
         socket.set_broadcast(true).unwrap();
         let mut buf = vec![0u8; 262144];
+        let device = Device::new(
+            ObjectIdentifier::new(ObjectType::Device, 1234).unwrap(),
+            0xFFFF,
+        );
 
         println!("Listening on {}", socket.local_addr().unwrap());
 
@@ -24,9 +30,13 @@ fn main() {
         let addr = format!("172.30.10.130:{}", 0xBAC0);
         // let addr = format!("172.30.10.177:{}", 0xBAC0);
         let data_ref = hex::decode("810b000c0120ffff00ff1008").unwrap(); // Who-is
-        let apdu = APDU::new(0x01, 0x08, vec![]);
+        let apdu = APDU::new(
+            0x01,
+            WhoIs::SERVICE_CHOICE,
+            WhoIs::new().encode_vec().unwrap(),
+        );
         println!("APDU Len: {}", apdu.len());
-        let dest = NPDUDest::new(0xffff, 0);
+        let dest = NPDUDest::new(0xffff);
         let npdu = NPDU::new(apdu, Some(dest), None, NPDUPriority::Normal);
         let bvlc = BVLC::new(BVLCFunction::OriginalBroadcastNPDU(npdu));
         let data = bvlc.encode_vec().unwrap();
@@ -38,40 +48,66 @@ fn main() {
 
         loop {
             let (n, peer) = socket.recv_from(&mut buf).await.unwrap();
-            buf = buf[..n].to_vec();
+            let data = &buf[..n];
             println!("Received {} bytes from {}", n, peer);
             //let buf = buf[5..buf[4] as usize + 5].to_vec();
             // === Data Structure ===
-            println!("Data: {:02x?}", buf);
+            println!("Data: {:02x?}", data);
 
-            let b = BVLC::decode_slice(&buf).unwrap();
+            let b = match BVLC::decode_slice(data) {
+                Ok(b) => b,
+                Err(e) => {
+                    println!("Invalid BVLC: {}", e);
+                    continue;
+                }
+            };
             println!("BVLC: {:02x?}", b);
-            println!("Function: {:02x?}", b.function);
+            if let std::net::SocketAddr::V4(peer) = peer {
+                println!("Originating address: {}", b.originating_address(peer));
+            }
+            println!("Function: {}", b.function);
             println!("Length: {:?}", b.len());
 
             match b.function {
-                BVLCFunction::OriginalBroadcastNPDU(n) | BVLCFunction::OriginalUnicastNPDU(n) => {
+                BVLCFunction::OriginalBroadcastNPDU(n)
+                | BVLCFunction::OriginalUnicastNPDU(n)
+                | BVLCFunction::DistributeBroadcastToNetwork(n)
+                | BVLCFunction::ForwardedNPDU { npdu: n, .. } => {
                     println!("NPDU: {:02x?}", n);
                     println!("Version: {}", n.version);
-                    println!("Priority: {:?}", n.priority);
+                    println!("Priority: {}", n.priority);
                     match n.content {
                         NPDUContent::APDU(apdu) => {
-                            println!("APDU: {:02x?}", apdu);
+                            println!("APDU: {}", apdu);
+                            println!("PDU Type: {:?}", apdu.pdu_type());
                             match apdu.service_choice {
                                 8 => {
                                     println!("Who-Is received!");
-                                    //let apdu = APDU::new();
-                                    //let sent = socket.send_to().await.unwrap();
-                                }
-                                0 => {
-                                    println!("I-Am received!");
+                                    let reply = match peer {
+                                        std::net::SocketAddr::V4(peer) => {
+                                            device.handle(peer, data).ok().flatten()
+                                        }
+                                        _ => None,
+                                    };
+                                    if let Some((address, reply)) = reply {
+                                        socket.send_to(&reply, address).await.unwrap();
+                                        println!("I-Am sent to {}", address);
+                                    }
                                 }
+                                0 => match IAm::decode_slice(apdu.user_data()) {
+                                    Ok(i_am) => println!("I-Am received: {:?}", i_am),
+                                    Err(e) => println!("Invalid I-Am: {}", e),
+                                },
                                 _ => println!("Unknown Service Choice: {}", apdu.service_choice),
                             }
                         }
-                        _ => unimplemented!(),
+                        NPDUContent::Message(msg) => {
+                            println!("Network Message: {}", msg);
+                        }
+                        NPDUContent::Empty => println!("Empty NPDU"),
                     }
                 }
+                f => println!("BVLC Function without NPDU: {}", f),
             }
         }
     });
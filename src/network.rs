@@ -1,20 +1,24 @@
 use crate::application::*;
-use crate::{Decode, Encode};
+use crate::{Decode, Encode, Error};
 
+use core::convert::TryFrom;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
-use std::convert::TryFrom;
-
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
+use alloc::vec::Vec;
 use tracing::trace;
 
+pub mod router;
+pub use router::*;
+
 /// Network Layer PDU Message Priority (6.2.2)
-#[derive(Copy, Clone, Debug, Eq, PartialEq, FromPrimitive, ToPrimitive)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NPDUPriority {
     LifeSafety = 0b11,
     CriticalEquipment = 0b10,
     Urgent = 0b01,
+    #[default]
     Normal = 0b00,
 }
 
@@ -29,163 +33,510 @@ impl From<NPDUPriority> for u8 {
     }
 }
 
-impl Default for NPDUPriority {
-    fn default() -> Self {
-        Self::Normal
+impl core::fmt::Display for NPDUPriority {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::LifeSafety => "Life Safety",
+            Self::CriticalEquipment => "Critical Equipment",
+            Self::Urgent => "Urgent",
+            Self::Normal => "Normal",
+        })
     }
 }
 
-/// Network Layer PDU Message Type (6.2.4)
+/// Port entry of an Initialize-Routing-Table(-Ack) message (6.4.7)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoutingTablePort {
+    pub dnet: u16,
+    pub port_id: u8,
+    pub port_info: Vec<u8>,
+}
+
+/// Network Layer PDU Message Type (6.2.4) and its parameters (6.4)
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NPDUMessage {
-    WhoIsRouterToNetwork,          // = 0x00,
-    IAmRouterToNetwork,            // = 0x01,
-    ICouldBeRouterToNetwork,       // = 0x02,
-    RejectMessageToNetwork,        // = 0x03,
-    RouterBusyToNetwork,           // = 0x04,
-    RouterAvailableToNetwork,      // = 0x05,
-    InitializeRoutingTable,        // = 0x06,
-    InitializeRoutingTableAck,     // = 0x07,
-    EstablishConnectionToNetwork,  // = 0x08,
-    DisconnectConnectionToNetwork, // = 0x09,
-    ChallengeRequest,              // = 0x0A,
-    SecurityPayload,               // = 0x0B,
-    SecurityResponse,              // = 0x0C,
-    RequestKeyUpdate,              // = 0x0D,
-    UpdateKeySet,                  // = 0x0E,
-    UpdateDistributionKey,         // = 0x0F,
-    RequestMasterKey,              // = 0x10,
-    SetMasterKey,                  // = 0x11,
-    WhatIsNetworkNumber,           // = 0x12,
-    NetworkNumberIs,               // = 0x13,
-    Proprietary(u8),               // = 0x80 to 0xFF, Available for vendor proprietary messages
-    Reserved(u8),                  // = 0x14 to 0x7F, Reserved for use by ASHRAE
+    /// 0x00, optionally for a specific DNET
+    WhoIsRouterToNetwork(Option<u16>),
+    /// 0x01
+    IAmRouterToNetwork(Vec<u16>),
+    /// 0x02
+    ICouldBeRouterToNetwork { dnet: u16, performance_index: u8 },
+    /// 0x03
+    RejectMessageToNetwork { reason: u8, dnet: u16 },
+    /// 0x04
+    RouterBusyToNetwork(Vec<u16>),
+    /// 0x05
+    RouterAvailableToNetwork(Vec<u16>),
+    /// 0x06
+    InitializeRoutingTable(Vec<RoutingTablePort>),
+    /// 0x07
+    InitializeRoutingTableAck(Vec<RoutingTablePort>),
+    /// 0x08
+    EstablishConnectionToNetwork { dnet: u16, termination_time: u8 },
+    /// 0x09
+    DisconnectConnectionToNetwork(u16),
+    /// 0x0A, the network security messages are kept as raw parameters
+    ChallengeRequest(Vec<u8>),
+    /// 0x0B
+    SecurityPayload(Vec<u8>),
+    /// 0x0C
+    SecurityResponse(Vec<u8>),
+    /// 0x0D
+    RequestKeyUpdate(Vec<u8>),
+    /// 0x0E
+    UpdateKeySet(Vec<u8>),
+    /// 0x0F
+    UpdateDistributionKey(Vec<u8>),
+    /// 0x10
+    RequestMasterKey(Vec<u8>),
+    /// 0x11
+    SetMasterKey(Vec<u8>),
+    /// 0x12
+    WhatIsNetworkNumber,
+    /// 0x13
+    NetworkNumberIs { number: u16, flag: u8 },
+    /// 0x80 to 0xFF, Available for vendor proprietary messages
+    Proprietary {
+        message_type: u8,
+        vendor_id: u16,
+        data: Vec<u8>,
+    },
+    /// 0x14 to 0x7F, Reserved for use by ASHRAE
+    Reserved(u8, Vec<u8>),
+}
+
+impl NPDUMessage {
+    pub fn message_type(&self) -> u8 {
+        match self {
+            Self::WhoIsRouterToNetwork(_) => 0x00,
+            Self::IAmRouterToNetwork(_) => 0x01,
+            Self::ICouldBeRouterToNetwork { .. } => 0x02,
+            Self::RejectMessageToNetwork { .. } => 0x03,
+            Self::RouterBusyToNetwork(_) => 0x04,
+            Self::RouterAvailableToNetwork(_) => 0x05,
+            Self::InitializeRoutingTable(_) => 0x06,
+            Self::InitializeRoutingTableAck(_) => 0x07,
+            Self::EstablishConnectionToNetwork { .. } => 0x08,
+            Self::DisconnectConnectionToNetwork(_) => 0x09,
+            Self::ChallengeRequest(_) => 0x0A,
+            Self::SecurityPayload(_) => 0x0B,
+            Self::SecurityResponse(_) => 0x0C,
+            Self::RequestKeyUpdate(_) => 0x0D,
+            Self::UpdateKeySet(_) => 0x0E,
+            Self::UpdateDistributionKey(_) => 0x0F,
+            Self::RequestMasterKey(_) => 0x10,
+            Self::SetMasterKey(_) => 0x11,
+            Self::WhatIsNetworkNumber => 0x12,
+            Self::NetworkNumberIs { .. } => 0x13,
+            Self::Proprietary { message_type, .. } => *message_type,
+            Self::Reserved(t, _) => *t,
+        }
+    }
+}
+
+/// Formatted as the message name followed by its parameters, e.g.
+/// "Who-Is-Router-To-Network(net=5)"
+impl core::fmt::Display for NPDUMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WhoIsRouterToNetwork(Some(net)) => {
+                write!(f, "Who-Is-Router-To-Network(net={})", net)
+            }
+            Self::WhoIsRouterToNetwork(None) => f.write_str("Who-Is-Router-To-Network"),
+            Self::IAmRouterToNetwork(nets) => write!(f, "I-Am-Router-To-Network(nets={:?})", nets),
+            Self::ICouldBeRouterToNetwork {
+                dnet,
+                performance_index,
+            } => write!(
+                f,
+                "I-Could-Be-Router-To-Network(net={}, performance-index={})",
+                dnet, performance_index
+            ),
+            Self::RejectMessageToNetwork { reason, dnet } => write!(
+                f,
+                "Reject-Message-To-Network(reason={}, net={})",
+                reason, dnet
+            ),
+            Self::RouterBusyToNetwork(nets) => write!(f, "Router-Busy-To-Network(nets={:?})", nets),
+            Self::RouterAvailableToNetwork(nets) => {
+                write!(f, "Router-Available-To-Network(nets={:?})", nets)
+            }
+            Self::InitializeRoutingTable(ports) => {
+                write!(f, "Initialize-Routing-Table(ports={})", ports.len())
+            }
+            Self::InitializeRoutingTableAck(ports) => {
+                write!(f, "Initialize-Routing-Table-Ack(ports={})", ports.len())
+            }
+            Self::EstablishConnectionToNetwork {
+                dnet,
+                termination_time,
+            } => write!(
+                f,
+                "Establish-Connection-To-Network(net={}, termination-time={})",
+                dnet, termination_time
+            ),
+            Self::DisconnectConnectionToNetwork(dnet) => {
+                write!(f, "Disconnect-Connection-To-Network(net={})", dnet)
+            }
+            Self::ChallengeRequest(_) => f.write_str("Challenge-Request"),
+            Self::SecurityPayload(_) => f.write_str("Security-Payload"),
+            Self::SecurityResponse(_) => f.write_str("Security-Response"),
+            Self::RequestKeyUpdate(_) => f.write_str("Request-Key-Update"),
+            Self::UpdateKeySet(_) => f.write_str("Update-Key-Set"),
+            Self::UpdateDistributionKey(_) => f.write_str("Update-Distribution-Key"),
+            Self::RequestMasterKey(_) => f.write_str("Request-Master-Key"),
+            Self::SetMasterKey(_) => f.write_str("Set-Master-Key"),
+            Self::WhatIsNetworkNumber => f.write_str("What-Is-Network-Number"),
+            Self::NetworkNumberIs { number, flag } => {
+                write!(f, "Network-Number-Is(net={}, flag={})", number, flag)
+            }
+            Self::Proprietary {
+                message_type,
+                vendor_id,
+                ..
+            } => write!(
+                f,
+                "Proprietary(type=0x{:02X}, vendor-id={})",
+                message_type, vendor_id
+            ),
+            Self::Reserved(t, _) => write!(f, "Reserved(type=0x{:02X})", t),
+        }
+    }
+}
+
+fn parse_networks(data: &[u8]) -> crate::Result<Vec<u16>> {
+    if !data.len().is_multiple_of(2) {
+        return Err(Error::InvalidValue(format!(
+            "Invalid network list length: {}",
+            data.len()
+        )));
+    }
+    Ok(data
+        .chunks_exact(2)
+        .map(|n| u16::from_be_bytes([n[0], n[1]]))
+        .collect())
+}
+
+fn parse_routing_table(data: &[u8]) -> crate::Result<Vec<RoutingTablePort>> {
+    let (count, mut data) = data.split_first().ok_or(Error::Truncated)?;
+    let mut ports = Vec::with_capacity(*count as usize);
+    for _ in 0..*count {
+        if data.len() < 4 {
+            return Err(Error::Truncated);
+        }
+        let dnet = u16::from_be_bytes([data[0], data[1]]);
+        let port_id = data[2];
+        let info_len = data[3] as usize;
+        let port_info = data.get(4..4 + info_len).ok_or(Error::Truncated)?.to_vec();
+        ports.push(RoutingTablePort {
+            dnet,
+            port_id,
+            port_info,
+        });
+        data = &data[4 + info_len..];
+    }
+    Ok(ports)
+}
+
+fn expect_len(data: &[u8], len: usize) -> crate::Result<()> {
+    if data.len() < len {
+        Err(Error::Truncated)
+    } else {
+        Ok(())
+    }
 }
 
-impl TryFrom<u8> for NPDUMessage {
-    type Error = String;
-
-    fn try_from(v: u8) -> Result<Self, Self::Error> {
-        match v {
-            0x00 => Ok(Self::WhoIsRouterToNetwork),
-            0x01 => Ok(Self::IAmRouterToNetwork),
-            0x02 => Ok(Self::ICouldBeRouterToNetwork),
-            0x03 => Ok(Self::RejectMessageToNetwork),
-            0x04 => Ok(Self::RouterBusyToNetwork),
-            0x05 => Ok(Self::RouterAvailableToNetwork),
-            0x06 => Ok(Self::InitializeRoutingTable),
-            0x07 => Ok(Self::InitializeRoutingTableAck),
-            0x08 => Ok(Self::EstablishConnectionToNetwork),
-            0x09 => Ok(Self::DisconnectConnectionToNetwork),
-            0x0A => Ok(Self::ChallengeRequest),
-            0x0B => Ok(Self::SecurityPayload),
-            0x0C => Ok(Self::SecurityResponse),
-            0x0D => Ok(Self::RequestKeyUpdate),
-            0x0E => Ok(Self::UpdateKeySet),
-            0x0F => Ok(Self::UpdateDistributionKey),
-            0x10 => Ok(Self::RequestMasterKey),
-            0x11 => Ok(Self::SetMasterKey),
+/// Parse a network layer message from its message type octet followed by its parameters
+impl TryFrom<&[u8]> for NPDUMessage {
+    type Error = Error;
+
+    fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
+        let (message_type, data) = v.split_first().ok_or(Error::Truncated)?;
+        match message_type {
+            0x00 => match data.len() {
+                0 => Ok(Self::WhoIsRouterToNetwork(None)),
+                _ => {
+                    expect_len(data, 2)?;
+                    Ok(Self::WhoIsRouterToNetwork(Some(u16::from_be_bytes([
+                        data[0], data[1],
+                    ]))))
+                }
+            },
+            0x01 => Ok(Self::IAmRouterToNetwork(parse_networks(data)?)),
+            0x02 => {
+                expect_len(data, 3)?;
+                Ok(Self::ICouldBeRouterToNetwork {
+                    dnet: u16::from_be_bytes([data[0], data[1]]),
+                    performance_index: data[2],
+                })
+            }
+            0x03 => {
+                expect_len(data, 3)?;
+                Ok(Self::RejectMessageToNetwork {
+                    reason: data[0],
+                    dnet: u16::from_be_bytes([data[1], data[2]]),
+                })
+            }
+            0x04 => Ok(Self::RouterBusyToNetwork(parse_networks(data)?)),
+            0x05 => Ok(Self::RouterAvailableToNetwork(parse_networks(data)?)),
+            0x06 => Ok(Self::InitializeRoutingTable(parse_routing_table(data)?)),
+            0x07 => Ok(Self::InitializeRoutingTableAck(parse_routing_table(data)?)),
+            0x08 => {
+                expect_len(data, 3)?;
+                Ok(Self::EstablishConnectionToNetwork {
+                    dnet: u16::from_be_bytes([data[0], data[1]]),
+                    termination_time: data[2],
+                })
+            }
+            0x09 => {
+                expect_len(data, 2)?;
+                Ok(Self::DisconnectConnectionToNetwork(u16::from_be_bytes([
+                    data[0], data[1],
+                ])))
+            }
+            0x0A => Ok(Self::ChallengeRequest(data.to_vec())),
+            0x0B => Ok(Self::SecurityPayload(data.to_vec())),
+            0x0C => Ok(Self::SecurityResponse(data.to_vec())),
+            0x0D => Ok(Self::RequestKeyUpdate(data.to_vec())),
+            0x0E => Ok(Self::UpdateKeySet(data.to_vec())),
+            0x0F => Ok(Self::UpdateDistributionKey(data.to_vec())),
+            0x10 => Ok(Self::RequestMasterKey(data.to_vec())),
+            0x11 => Ok(Self::SetMasterKey(data.to_vec())),
             0x12 => Ok(Self::WhatIsNetworkNumber),
-            0x13 => Ok(Self::NetworkNumberIs),
-            0x14..=0x7F => Ok(Self::Reserved(v)),
-            0x80..=0xFF => Ok(Self::Proprietary(v)),
+            0x13 => {
+                expect_len(data, 3)?;
+                Ok(Self::NetworkNumberIs {
+                    number: u16::from_be_bytes([data[0], data[1]]),
+                    flag: data[2],
+                })
+            }
+            t @ 0x14..=0x7F => Ok(Self::Reserved(*t, data.to_vec())),
+            t @ 0x80..=0xFF => {
+                expect_len(data, 2)?;
+                Ok(Self::Proprietary {
+                    message_type: *t,
+                    vendor_id: u16::from_be_bytes([data[0], data[1]]),
+                    data: data[2..].to_vec(),
+                })
+            }
         }
     }
 }
 
 impl Encode for NPDUMessage {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
-        let _: () = match self {
-            Self::WhoIsRouterToNetwork => writer.write_u8(0x00)?,
-            Self::IAmRouterToNetwork => writer.write_u8(0x01)?,
-            Self::ICouldBeRouterToNetwork => writer.write_u8(0x02)?,
-            Self::RejectMessageToNetwork => writer.write_u8(0x03)?,
-            Self::RouterBusyToNetwork => writer.write_u8(0x04)?,
-            Self::RouterAvailableToNetwork => writer.write_u8(0x05)?,
-            Self::InitializeRoutingTable => writer.write_u8(0x06)?,
-            Self::InitializeRoutingTableAck => writer.write_u8(0x07)?,
-            Self::EstablishConnectionToNetwork => writer.write_u8(0x08)?,
-            Self::DisconnectConnectionToNetwork => writer.write_u8(0x09)?,
-            Self::ChallengeRequest => writer.write_u8(0x0A)?,
-            Self::SecurityPayload => writer.write_u8(0x0B)?,
-            Self::SecurityResponse => writer.write_u8(0x0C)?,
-            Self::RequestKeyUpdate => writer.write_u8(0x0D)?,
-            Self::UpdateKeySet => writer.write_u8(0x0E)?,
-            Self::UpdateDistributionKey => writer.write_u8(0x0F)?,
-            Self::RequestMasterKey => writer.write_u8(0x10)?,
-            Self::SetMasterKey => writer.write_u8(0x11)?,
-            Self::WhatIsNetworkNumber => writer.write_u8(0x12)?,
-            Self::NetworkNumberIs => writer.write_u8(0x13)?,
-            Self::Reserved(v) => writer.write_u8(*v)?,
-            Self::Proprietary(v) => writer.write_u8(*v)?,
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_u8(self.message_type())?;
+        match self {
+            Self::WhoIsRouterToNetwork(dnet) => {
+                if let Some(dnet) = dnet {
+                    writer.write_u16(*dnet)?;
+                }
+            }
+            Self::IAmRouterToNetwork(dnets)
+            | Self::RouterBusyToNetwork(dnets)
+            | Self::RouterAvailableToNetwork(dnets) => {
+                for dnet in dnets {
+                    writer.write_u16(*dnet)?;
+                }
+            }
+            Self::ICouldBeRouterToNetwork {
+                dnet,
+                performance_index,
+            } => {
+                writer.write_u16(*dnet)?;
+                writer.write_u8(*performance_index)?;
+            }
+            Self::RejectMessageToNetwork { reason, dnet } => {
+                writer.write_u8(*reason)?;
+                writer.write_u16(*dnet)?;
+            }
+            Self::InitializeRoutingTable(ports) | Self::InitializeRoutingTableAck(ports) => {
+                writer.write_u8(ports.len() as u8)?;
+                for port in ports {
+                    writer.write_u16(port.dnet)?;
+                    writer.write_u8(port.port_id)?;
+                    writer.write_u8(port.port_info.len() as u8)?;
+                    writer.write_all(&port.port_info)?;
+                }
+            }
+            Self::EstablishConnectionToNetwork {
+                dnet,
+                termination_time,
+            } => {
+                writer.write_u16(*dnet)?;
+                writer.write_u8(*termination_time)?;
+            }
+            Self::DisconnectConnectionToNetwork(dnet) => writer.write_u16(*dnet)?,
+            Self::ChallengeRequest(data)
+            | Self::SecurityPayload(data)
+            | Self::SecurityResponse(data)
+            | Self::RequestKeyUpdate(data)
+            | Self::UpdateKeySet(data)
+            | Self::UpdateDistributionKey(data)
+            | Self::RequestMasterKey(data)
+            | Self::SetMasterKey(data)
+            | Self::Reserved(_, data) => writer.write_all(data)?,
+            Self::WhatIsNetworkNumber => (),
+            Self::NetworkNumberIs { number, flag } => {
+                writer.write_u16(*number)?;
+                writer.write_u8(*flag)?;
+            }
+            Self::Proprietary {
+                vendor_id, data, ..
+            } => {
+                writer.write_u16(*vendor_id)?;
+                writer.write_all(data)?;
+            }
         };
         Ok(())
     }
 
     fn len(&self) -> usize {
-        match self {
-            Self::WhoIsRouterToNetwork => 2,
-            Self::IAmRouterToNetwork => 2,
-            Self::ICouldBeRouterToNetwork => 2,
-            Self::RejectMessageToNetwork => 2,
-            Self::RouterBusyToNetwork => 2,
-            Self::RouterAvailableToNetwork => 2,
-            Self::InitializeRoutingTable => 2,
-            Self::InitializeRoutingTableAck => 2,
-            Self::EstablishConnectionToNetwork => 2,
-            Self::DisconnectConnectionToNetwork => 2,
-            Self::ChallengeRequest => 2,
-            Self::SecurityPayload => 2,
-            Self::SecurityResponse => 2,
-            Self::RequestKeyUpdate => 2,
-            Self::UpdateKeySet => 2,
-            Self::UpdateDistributionKey => 2,
-            Self::RequestMasterKey => 2,
-            Self::SetMasterKey => 2,
-            Self::WhatIsNetworkNumber => 2,
-            Self::NetworkNumberIs => 2,
-            Self::Reserved(_) => 2,
-            Self::Proprietary(_) => 2,
-        }
+        let parameters = match self {
+            Self::WhoIsRouterToNetwork(dnet) => dnet.map(|_| 2).unwrap_or(0),
+            Self::IAmRouterToNetwork(dnets)
+            | Self::RouterBusyToNetwork(dnets)
+            | Self::RouterAvailableToNetwork(dnets) => 2 * dnets.len(),
+            Self::ICouldBeRouterToNetwork { .. } => 3, // DNET(2) + Performance Index(1)
+            Self::RejectMessageToNetwork { .. } => 3,  // Reason(1) + DNET(2)
+            Self::InitializeRoutingTable(ports) | Self::InitializeRoutingTableAck(ports) => {
+                // Number of Ports(1) + DNET(2) + Port ID(1) + Port Info Length(1) + Port Info(*)
+                1 + ports.iter().map(|p| 4 + p.port_info.len()).sum::<usize>()
+            }
+            Self::EstablishConnectionToNetwork { .. } => 3, // DNET(2) + Termination Time(1)
+            Self::DisconnectConnectionToNetwork(_) => 2,
+            Self::ChallengeRequest(data)
+            | Self::SecurityPayload(data)
+            | Self::SecurityResponse(data)
+            | Self::RequestKeyUpdate(data)
+            | Self::UpdateKeySet(data)
+            | Self::UpdateDistributionKey(data)
+            | Self::RequestMasterKey(data)
+            | Self::SetMasterKey(data)
+            | Self::Reserved(_, data) => data.len(),
+            Self::WhatIsNetworkNumber => 0,
+            Self::NetworkNumberIs { .. } => 3, // Network Number(2) + Flag(1)
+            Self::Proprietary { data, .. } => 2 + data.len(), // Vendor ID(2) + Data(*)
+        };
+        1 + parameters // Message Type + Parameters
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+impl Decode for NPDUMessage {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut message = Vec::new();
+        reader.read_to_end(&mut message)?;
+        Self::try_from(&message[..])
+    }
+}
+
+/// Initial hop count of a destination (6.2.2)
+pub const DEFAULT_HOP_COUNT: u8 = 255;
+
+#[derive(Clone, Debug, Default, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NPDUDest {
     net: u16,
     adr: Vec<u8>,
-    hops: u8,
+    /// Hop Count (6.2.2), None until set so NpduBuilder can apply its default, encoded as
+    /// DEFAULT_HOP_COUNT so the message can pass the maximum number of routers
+    hops: Option<u8>,
 }
 
 impl NPDUDest {
-    pub fn new(net: u16, capacity: usize) -> Self {
+    /// Destination without address, a broadcast on network `net`
+    pub fn new(net: u16) -> Self {
         NPDUDest {
             net,
-            adr: Vec::with_capacity(capacity),
-            hops: 255,
+            ..Default::default()
         }
     }
+
+    /// Destination of the station at MAC address `adr` on network `net`
+    pub fn with_address(net: u16, adr: &[u8]) -> Self {
+        Self::new(net).address(adr)
+    }
+
+    pub fn address(mut self, adr: &[u8]) -> Self {
+        self.adr = adr.to_vec();
+        self
+    }
+
+    pub fn with_hops(mut self, hops: u8) -> Self {
+        self.hops = Some(hops);
+        self
+    }
+
+    /// Destination network (DNET)
+    pub fn net(&self) -> u16 {
+        self.net
+    }
+
+    /// MAC address on the destination network (DADR), empty for a broadcast
+    pub fn adr(&self) -> &[u8] {
+        &self.adr
+    }
+
+    pub fn hops(&self) -> u8 {
+        self.hops.unwrap_or(DEFAULT_HOP_COUNT)
+    }
+}
+
+/// Destinations are equal if they encode the same, an unset hop count equals DEFAULT_HOP_COUNT
+impl PartialEq for NPDUDest {
+    fn eq(&self, other: &Self) -> bool {
+        self.net == other.net && self.adr == other.adr && self.hops() == other.hops()
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NPDUSource {
     net: u16,
     adr: Vec<u8>,
 }
 
 impl NPDUSource {
-    pub fn new(net: u16, capacity: usize) -> Self {
+    /// Source without address
+    pub fn new(net: u16) -> Self {
         NPDUSource {
             net,
-            adr: Vec::with_capacity(capacity),
+            adr: Vec::new(),
         }
     }
+
+    /// Source of the station at MAC address `adr` on network `net`
+    pub fn with_address(net: u16, adr: &[u8]) -> Self {
+        Self::new(net).address(adr)
+    }
+
+    pub fn address(mut self, adr: &[u8]) -> Self {
+        self.adr = adr.to_vec();
+        self
+    }
+
+    /// Originating network (SNET)
+    pub fn net(&self) -> u16 {
+        self.net
+    }
+
+    /// MAC address on the originating network (SADR)
+    pub fn adr(&self) -> &[u8] {
+        &self.adr
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NPDUContent<A: Encode = APDU, B: Encode = NPDUMessage> {
     APDU(A),
     Message(B),
+    /// No content, the NPDU only consists of the NPCI (e.g. as a probe)
+    Empty,
 }
 
 impl<A: Encode, B: Encode> From<A> for NPDUContent<A, B> {
@@ -194,11 +545,24 @@ impl<A: Encode, B: Encode> From<A> for NPDUContent<A, B> {
     }
 }
 
+impl<A: Encode + core::fmt::Display, B: Encode + core::fmt::Display> core::fmt::Display
+    for NPDUContent<A, B>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::APDU(a) => a.fmt(f),
+            Self::Message(m) => m.fmt(f),
+            Self::Empty => f.write_str("Empty"),
+        }
+    }
+}
+
 impl<A: Encode, B: Encode> Encode for NPDUContent<A, B> {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
         let _: () = match self {
             Self::APDU(apdu) => apdu.encode(writer)?,
             Self::Message(msg) => msg.encode(writer)?,
+            Self::Empty => (),
         };
         Ok(())
     }
@@ -207,11 +571,13 @@ impl<A: Encode, B: Encode> Encode for NPDUContent<A, B> {
         match self {
             Self::APDU(apdu) => apdu.len(),
             Self::Message(msg) => msg.len(),
+            Self::Empty => 0,
         }
     }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NPDU<A: Encode = APDU, B: Encode = NPDUMessage> {
     /// Protocol Version Number (6.2.1)
     pub version: u8,
@@ -223,6 +589,11 @@ pub struct NPDU<A: Encode = APDU, B: Encode = NPDUMessage> {
 }
 
 impl<A: Encode, B: Encode> NPDU<A, B> {
+    /// Create an NPDU without any content, consisting of the version and control octets only
+    pub fn empty(priority: NPDUPriority) -> Self {
+        Self::new(NPDUContent::Empty, None, None, priority)
+    }
+
     pub fn new<T: Into<NPDUContent<A, B>>>(
         content: T,
         destination: Option<NPDUDest>,
@@ -240,8 +611,72 @@ impl<A: Encode, B: Encode> NPDU<A, B> {
     }
 }
 
+/// Builder for NPDUs carrying an APDU
+#[derive(Clone, Debug)]
+pub struct NpduBuilder {
+    destination: Option<NPDUDest>,
+    source: Option<NPDUSource>,
+    priority: NPDUPriority,
+    hop_count: u8,
+}
+
+impl Default for NpduBuilder {
+    fn default() -> Self {
+        Self {
+            destination: None,
+            source: None,
+            priority: NPDUPriority::default(),
+            hop_count: DEFAULT_HOP_COUNT,
+        }
+    }
+}
+
+impl NpduBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hop count of a destination that does not set one, defaults to DEFAULT_HOP_COUNT. A
+    /// lower count limits the reach of messages on meshed networks
+    pub fn hop_count(mut self, hops: u8) -> Self {
+        self.hop_count = hops;
+        self
+    }
+
+    pub fn destination(mut self, destination: NPDUDest) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    fn build_destination(&self) -> Option<NPDUDest> {
+        self.destination.clone().map(|d| NPDUDest {
+            hops: d.hops.or(Some(self.hop_count)),
+            ..d
+        })
+    }
+
+    pub fn source(mut self, source: NPDUSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn priority(mut self, priority: NPDUPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Builds an NPDU for a confirmed request, setting data_expecting_reply from the APDU
+    pub fn confirmed_request(self, apdu: APDU) -> NPDU {
+        let data_expecting_reply = apdu.expects_reply();
+        let destination = self.build_destination();
+        let mut npdu = NPDU::new(apdu, destination, self.source, self.priority);
+        npdu.data_expecting_reply = data_expecting_reply;
+        npdu
+    }
+}
+
 impl<A: Encode, B: Encode> Encode for NPDU<A, B> {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
         // NPCI
         writer.write_u8(self.version)?;
 
@@ -260,17 +695,17 @@ impl<A: Encode, B: Encode> Encode for NPDU<A, B> {
         }
         writer.write_u8(control)?;
         if let Some(ref d) = self.destination {
-            writer.write_u16::<BigEndian>(d.net)?;
+            writer.write_u16(d.net)?;
             writer.write_u8(d.adr.len() as u8)?;
             writer.write_all(&d.adr)?;
         }
         if let Some(ref s) = self.source {
-            writer.write_u16::<BigEndian>(s.net)?;
+            writer.write_u16(s.net)?;
             writer.write_u8(s.adr.len() as u8)?;
             writer.write_all(&s.adr)?;
         }
         if let Some(ref d) = self.destination {
-            writer.write_u8(d.hops)?;
+            writer.write_u8(d.hops())?;
         }
 
         // Content
@@ -298,68 +733,109 @@ impl<A: Encode, B: Encode> Encode for NPDU<A, B> {
     }
 }
 
-impl Decode for NPDU {
-    fn decode<T: std::io::Read + Sized>(reader: &mut T) -> std::io::Result<Self> {
+impl NPDU {
+    /// Decode an NPDU, returning it together with the length of its NPCI, the offset of the
+    /// APDU or network layer message in `data`
+    pub fn decode_with_npci_len(data: &[u8]) -> crate::Result<(Self, usize)> {
+        let mut reader = data;
+        let (npdu, has_apdu) = Self::decode_npci(&mut reader)?;
+        let npci_len = data.len() - reader.len();
+        let content = Self::decode_content(&mut reader, has_apdu)?;
+        Ok((Self { content, ..npdu }, npci_len))
+    }
+
+    /// Decode the NPCI (6.2.2), returning the NPDU without content and whether an APDU follows
+    fn decode_npci<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<(Self, bool)> {
         let version = reader.read_u8()?;
         trace!("Version: {:02x}", version);
+        // Protocol Version Number (6.2.1)
+        if version != 0x01 {
+            return Err(Error::Unsupported(format!(
+                "NPDU version not supported: {}",
+                version
+            )));
+        }
         // Read and parse the Network Layer Protocol Control Information (6.2.2)
         let control = reader.read_u8()?;
         trace!("Control: {:08b}", control);
-        let priority = NPDUPriority::from_u8(control & 0b0000_00011).unwrap();
+        let priority = NPDUPriority::from_u8(control & 0b0000_0011).unwrap_or_default();
         let has_apdu = (control & 1 << 7) == 0;
         let has_dest = (control & 1 << 5) != 0;
         let has_source = (control & 1 << 3) != 0;
         let data_expecting_reply = (control & 1 << 2) != 0;
 
         let mut destination: Option<NPDUDest> = if has_dest {
-            let net = reader.read_u16::<BigEndian>()?;
+            let net = reader.read_u16()?;
             let len = reader.read_u8()?;
-            let mut dest = NPDUDest::new(net, len as usize);
-            reader.read_exact(&mut dest.adr)?;
-            Some(dest)
+            let mut adr = vec![0u8; len as usize];
+            reader.read_exact(&mut adr)?;
+            Some(NPDUDest {
+                net,
+                adr,
+                ..Default::default()
+            })
         } else {
             None
         };
 
         let source: Option<NPDUSource> = if has_source {
-            let net = reader.read_u16::<BigEndian>()?;
+            let net = reader.read_u16()?;
             let len = reader.read_u8()?;
-            let mut source = NPDUSource::new(net, len as usize);
-            reader.read_exact(&mut source.adr)?;
-            Some(source)
+            let mut adr = vec![0u8; len as usize];
+            reader.read_exact(&mut adr)?;
+            Some(NPDUSource { net, adr })
         } else {
             None
         };
         //println!("{:?}", destination);
         if let Some(dest) = &mut destination {
-            dest.hops = reader.read_u8()?;
+            dest.hops = Some(reader.read_u8()?);
         };
 
-        let content = if has_apdu {
-            APDU::decode(reader)?.into()
-        } else {
-            /*Ok(NPDUContentSlice::Message(NPDUMessage::try_from(
-                self.slice[0],
-            )?))*/
-            unimplemented!();
-        };
-
-        Ok(Self {
+        let npdu = Self {
             version,
             destination,
             source,
             data_expecting_reply,
             priority,
-            content,
-        })
+            content: NPDUContent::Empty,
+        };
+        Ok((npdu, has_apdu))
+    }
+
+    /// Decode the APDU or network layer message following the NPCI
+    fn decode_content<T: crate::io::Read + Sized>(
+        reader: &mut T,
+        has_apdu: bool,
+    ) -> crate::Result<NPDUContent> {
+        if !has_apdu {
+            return Ok(NPDUContent::Message(NPDUMessage::decode(reader)?));
+        }
+        let mut apdu = Vec::new();
+        reader.read_to_end(&mut apdu)?;
+        if apdu.is_empty() {
+            Ok(NPDUContent::Empty)
+        } else {
+            Ok(APDU::decode_slice(&apdu)?.into())
+        }
+    }
+}
+
+impl Decode for NPDU {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let (npdu, has_apdu) = Self::decode_npci(reader)?;
+        let content = Self::decode_content(reader, has_apdu)?;
+        Ok(Self { content, ..npdu })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Encode;
-    use bytes::{BufMut, BytesMut};
+    use crate::io::SliceWriter;
+    use crate::{Decode, Encode};
 
     use crate::tests::*;
 
@@ -368,9 +844,9 @@ mod tests {
         let content = NPDUContent::<Dummy, Dummy>::APDU(Dummy::default());
         let npdu = NPDU::<Dummy, Dummy>::new(content, None, None, NPDUPriority::Normal);
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         npdu.encode(&mut w).expect("Write NPDU to buffer");
-        assert_eq!(w.into_inner().to_vec(), vec![1, 0]);
+        assert_eq!(w, vec![1, 0]);
     }
 
     #[test]
@@ -379,14 +855,14 @@ mod tests {
         let dest = NPDUDest {
             net: 0x126,
             adr: vec![0; 16],
-            hops: 255,
+            hops: Some(255),
         };
         let npdu = NPDU::<Dummy, Dummy>::new(content, Some(dest), None, NPDUPriority::Normal);
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         npdu.encode(&mut w).expect("Write NPDU to buffer");
         assert_eq!(
-            w.into_inner().to_vec(),
+            w,
             vec![
                 1, 32, 1, 38, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255
             ]
@@ -402,10 +878,10 @@ mod tests {
         };
         let npdu = NPDU::<Dummy, Dummy>::new(content, None, Some(source), NPDUPriority::Normal);
 
-        let mut w = BytesMut::new().writer();
+        let mut w = Vec::new();
         npdu.encode(&mut w).expect("Write NPDU to buffer");
         assert_eq!(
-            w.into_inner().to_vec(),
+            w,
             vec![
                 1, 8, 1, 38, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
             ]
@@ -418,7 +894,7 @@ mod tests {
         let dest = NPDUDest {
             net: 0x126,
             adr: vec![0; 16],
-            hops: 255,
+            hops: Some(255),
         };
         let source = NPDUSource {
             net: 0x126,
@@ -427,14 +903,307 @@ mod tests {
         let npdu =
             NPDU::<Dummy, Dummy>::new(content, Some(dest), Some(source), NPDUPriority::Normal);
 
-        let mut w = BytesMut::with_capacity(1024).writer();
+        let mut w = Vec::new();
         npdu.encode(&mut w).expect("Write NPDU to buffer");
         assert_eq!(
-            w.into_inner().to_vec(),
+            w,
             vec![
                 1, 40, 1, 38, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 38, 16, 0, 0,
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255
             ]
         );
     }
+
+    #[test]
+    fn test_network_message_with_source_only() {
+        let source = NPDUSource {
+            net: 0x126,
+            adr: vec![0x0a],
+        };
+        let npdu = NPDU::new(
+            NPDUContent::Message(NPDUMessage::NetworkNumberIs {
+                number: 0x126,
+                flag: 1,
+            }),
+            None,
+            Some(source),
+            NPDUPriority::Normal,
+        );
+
+        let data = npdu.encode_vec().expect("Write NPDU to buffer");
+        assert_eq!(data, vec![1, 0x88, 1, 38, 1, 0x0a, 0x13, 1, 38, 1]);
+        assert_eq!(data.len(), npdu.len());
+
+        let decoded = NPDU::decode_slice(&data).expect("Decode NPDU");
+        assert_eq!(decoded, npdu);
+    }
+
+    #[test]
+    fn test_encode_npdu_with_default_dest() {
+        let content = NPDUContent::<Dummy, Dummy>::APDU(Dummy::default());
+        let npdu = NPDU::<Dummy, Dummy>::new(
+            content,
+            Some(NPDUDest::default()),
+            None,
+            NPDUPriority::Normal,
+        );
+
+        let mut w = Vec::new();
+        npdu.encode(&mut w).expect("Write NPDU to buffer");
+        assert_eq!(w, vec![1, 32, 0, 0, 0, 255]);
+        assert_eq!(NPDUDest::default(), NPDUDest::new(0));
+    }
+
+    #[test]
+    fn test_builder_confirmed_request_sets_der() {
+        // ReadProperty analog-input,1 present-value
+        let apdu = APDU::new(0x00, 0x0c, vec![0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55]);
+        let npdu = NpduBuilder::new()
+            .destination(NPDUDest::new(0x126))
+            .confirmed_request(apdu);
+
+        assert!(npdu.data_expecting_reply);
+        assert_eq!(npdu.priority, NPDUPriority::Normal);
+        let data = npdu.encode_vec().expect("Write NPDU to buffer");
+        assert_eq!(data[1], 0b0010_0100);
+    }
+
+    #[test]
+    fn test_empty_npdu() {
+        let npdu: NPDU = NPDU::empty(NPDUPriority::Normal);
+
+        let data = npdu.encode_vec().expect("Write NPDU to buffer");
+        assert_eq!(data, vec![1, 0]);
+        assert_eq!(npdu.len(), 2);
+
+        let decoded = NPDU::decode_slice(&data).expect("Decode NPDU");
+        assert_eq!(decoded.content, NPDUContent::Empty);
+    }
+
+    #[test]
+    fn test_decode_npdu_with_dest() {
+        let data = vec![
+            1, 32, 1, 38, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255,
+        ];
+        let npdu = NPDU::decode_slice(&data).expect("Decode NPDU");
+
+        let dest = NPDUDest {
+            net: 0x126,
+            adr: vec![0; 16],
+            hops: Some(255),
+        };
+        assert_eq!(npdu.destination, Some(dest));
+        assert_eq!(npdu.source, None);
+        assert_eq!(npdu.content, NPDUContent::Empty);
+        assert_eq!(npdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_npdu_with_source() {
+        let data = vec![
+            1, 8, 1, 38, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let npdu = NPDU::decode_slice(&data).expect("Decode NPDU");
+
+        let source = NPDUSource {
+            net: 0x126,
+            adr: vec![0; 16],
+        };
+        assert_eq!(npdu.destination, None);
+        assert_eq!(npdu.source, Some(source));
+        assert_eq!(npdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_npdu_with_dest_and_source() {
+        let data = vec![
+            1, 40, 1, 38, 6, 1, 2, 3, 4, 5, 6, 0, 5, 1, 0x0a, 3, 0x10, 0x08,
+        ];
+        let npdu = NPDU::decode_slice(&data).expect("Decode NPDU");
+
+        let dest = NPDUDest {
+            net: 0x126,
+            adr: vec![1, 2, 3, 4, 5, 6],
+            hops: Some(3),
+        };
+        let source = NPDUSource {
+            net: 5,
+            adr: vec![0x0a],
+        };
+        assert_eq!(npdu.destination, Some(dest));
+        assert_eq!(npdu.source, Some(source));
+        assert_eq!(npdu.content, NPDUContent::APDU(APDU::new(1, 8, vec![])));
+        assert_eq!(npdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_what_is_network_number() {
+        let data = vec![1, 0x80, 0x12];
+        let npdu = NPDU::decode_slice(&data).expect("Decode NPDU");
+
+        assert_eq!(
+            npdu.content,
+            NPDUContent::Message(NPDUMessage::WhatIsNetworkNumber)
+        );
+        assert_eq!(npdu.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_i_am_router_to_network() {
+        let message = NPDUMessage::IAmRouterToNetwork(vec![1, 0x126, 0xfffe]);
+        let npdu: NPDU = NPDU::new(
+            NPDUContent::Message(message.clone()),
+            None,
+            None,
+            NPDUPriority::Normal,
+        );
+
+        let data = npdu.encode_vec().expect("Write NPDU to buffer");
+        assert_eq!(data, vec![1, 0x80, 0x01, 0, 1, 1, 38, 0xff, 0xfe]);
+        assert_eq!(data.len(), npdu.len());
+
+        let decoded = NPDU::decode_slice(&data).expect("Decode NPDU");
+        assert_eq!(decoded.content, NPDUContent::Message(message));
+    }
+
+    #[test]
+    fn test_network_message_parameters() {
+        let messages = vec![
+            NPDUMessage::WhoIsRouterToNetwork(None),
+            NPDUMessage::WhoIsRouterToNetwork(Some(5)),
+            NPDUMessage::RejectMessageToNetwork { reason: 1, dnet: 5 },
+            NPDUMessage::InitializeRoutingTableAck(vec![RoutingTablePort {
+                dnet: 5,
+                port_id: 1,
+                port_info: vec![0xaa, 0xbb],
+            }]),
+            NPDUMessage::Proprietary {
+                message_type: 0x80,
+                vendor_id: 999,
+                data: vec![1, 2, 3],
+            },
+        ];
+        for message in messages {
+            let data = message.encode_vec().expect("Write message to buffer");
+            assert_eq!(data.len(), message.len());
+            assert_eq!(NPDUMessage::decode_slice(&data).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_network_message_truncated() {
+        let err = NPDUMessage::decode_slice(&[0x13, 0x01]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+        let err = NPDUMessage::decode_slice(&[0x01, 0x00, 0x01, 0x02]).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        let err = NPDUMessage::decode_slice(&[0x06, 0x01, 0x00, 0x0a, 0x01, 0x02]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_npdu_unsupported_version() {
+        let err = NPDU::decode_slice(&[0x02, 0x00, 0x10, 0x08]).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        let err = NPDU::decode_slice(&[0x01]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_builder_default_hop_count() {
+        let apdu = APDU::new(0x00, 0x0c, vec![]);
+        let npdu = NpduBuilder::new()
+            .destination(NPDUDest::new(0x126))
+            .hop_count(5)
+            .confirmed_request(apdu.clone());
+        assert_eq!(npdu.destination.unwrap().hops(), 5);
+
+        let npdu = NpduBuilder::new()
+            .destination(NPDUDest::new(0x126))
+            .confirmed_request(apdu.clone());
+        assert_eq!(npdu.destination.unwrap().hops(), DEFAULT_HOP_COUNT);
+
+        // A hop count set on the destination is kept
+        let npdu = NpduBuilder::new()
+            .destination(NPDUDest::new(0x126).with_hops(3))
+            .hop_count(5)
+            .confirmed_request(apdu);
+        assert_eq!(npdu.destination.unwrap().hops(), 3);
+    }
+
+    #[test]
+    fn test_encode_npdu_into_fixed_buffer() {
+        let apdu = APDU::new(0x01, 8, vec![]);
+        let npdu: NPDU = NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff)),
+            None,
+            NPDUPriority::Normal,
+        );
+
+        let mut buf = [0u8; 16];
+        let mut writer = SliceWriter::new(&mut buf);
+        npdu.encode(&mut writer).unwrap();
+        assert_eq!(
+            writer.written(),
+            &[0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x08]
+        );
+        assert_eq!(writer.position(), npdu.len());
+
+        let mut short = [0u8; 4];
+        let err = npdu.encode(&mut SliceWriter::new(&mut short)).unwrap_err();
+        assert!(matches!(err, Error::BufferTooSmall), "{:?}", err);
+    }
+
+    #[test]
+    fn test_display_network_message() {
+        assert_eq!(
+            NPDUMessage::WhoIsRouterToNetwork(Some(5)).to_string(),
+            "Who-Is-Router-To-Network(net=5)"
+        );
+        assert_eq!(
+            NPDUMessage::WhoIsRouterToNetwork(None).to_string(),
+            "Who-Is-Router-To-Network"
+        );
+        assert_eq!(
+            NPDUMessage::IAmRouterToNetwork(vec![1, 2]).to_string(),
+            "I-Am-Router-To-Network(nets=[1, 2])"
+        );
+        assert_eq!(
+            NPDUMessage::RejectMessageToNetwork { reason: 1, dnet: 7 }.to_string(),
+            "Reject-Message-To-Network(reason=1, net=7)"
+        );
+        assert_eq!(
+            NPDUMessage::Reserved(0x14, vec![]).to_string(),
+            "Reserved(type=0x14)"
+        );
+    }
+
+    #[test]
+    fn test_display_priority() {
+        assert_eq!(NPDUPriority::LifeSafety.to_string(), "Life Safety");
+        assert_eq!(NPDUPriority::default().to_string(), "Normal");
+    }
+
+    #[test]
+    fn test_dest_and_source_with_mac_address() {
+        let mac = [0x0a, 0x00, 0x00, 0x05, 0xba, 0xc0];
+        let dest = NPDUDest::with_address(0x126, &mac).with_hops(16);
+        assert_eq!(dest.net(), 0x126);
+        assert_eq!(dest.adr(), &mac);
+        assert_eq!(dest.hops(), 16);
+
+        let source = NPDUSource::with_address(5, &mac[..2]);
+        let npdu: NPDU = NPDU::new(
+            NPDUContent::Empty,
+            Some(dest),
+            Some(source),
+            NPDUPriority::Normal,
+        );
+        let decoded = NPDU::decode_slice(&npdu.encode_vec().unwrap()).unwrap();
+
+        let dest = decoded.destination.unwrap();
+        assert_eq!((dest.net(), dest.adr(), dest.hops()), (0x126, &mac[..], 16));
+        let source = decoded.source.unwrap();
+        assert_eq!((source.net(), source.adr()), (5, &mac[..2]));
+    }
 }
@@ -0,0 +1,356 @@
+/// Routing of NPDUs between the networks of a BACnet router (6.5, 6.6)
+use crate::network::{NPDU, NPDUContent, NPDUMessage, NPDUPriority, NPDUSource};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use tracing::trace;
+
+/// DNET of a message broadcast to all networks (6.2.2)
+pub const GLOBAL_BROADCAST_NETWORK: u16 = 0xFFFF;
+
+/// Reject reason of a message to a network the router has no route to (6.4.4)
+pub const REJECT_NO_ROUTE: u8 = 1;
+
+/// Port of a router, directly connected to a network
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RouterPort {
+    pub id: u8,
+    pub network: u16,
+}
+
+/// Entry of the routing table for a network reached through another router
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Route {
+    pub port: u8,
+    /// MAC address of the next router on the network of `port`
+    pub next_router: Vec<u8>,
+}
+
+/// What a router does with a received NPDU
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Forward {
+    /// Pass the NPDU to the application layer of the router
+    Local(NPDU),
+    /// Send the NPDU out of `port` to `mac`, an empty address broadcasts on the port's network
+    Send { port: u8, mac: Vec<u8>, npdu: NPDU },
+}
+
+/// Router between directly connected networks, forwarding NPDUs along its routing table (6.5.4)
+#[derive(Clone, Debug, Default)]
+pub struct Router {
+    ports: Vec<RouterPort>,
+    routes: BTreeMap<u16, Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a port directly connected to `network`
+    pub fn port(mut self, id: u8, network: u16) -> Self {
+        self.ports.push(RouterPort { id, network });
+        self
+    }
+
+    /// Add a route to `network` through the router at `next_router` on `port`
+    pub fn route(mut self, network: u16, port: u8, next_router: Vec<u8>) -> Self {
+        self.add_route(network, port, next_router);
+        self
+    }
+
+    pub fn add_route(&mut self, network: u16, port: u8, next_router: Vec<u8>) {
+        self.routes.insert(network, Route { port, next_router });
+    }
+
+    pub fn ports(&self) -> &[RouterPort] {
+        &self.ports
+    }
+
+    pub fn routes(&self) -> &BTreeMap<u16, Route> {
+        &self.routes
+    }
+
+    /// Networks reachable through the ports other than `port`, directly or through another
+    /// router
+    pub fn reachable_networks(&self, port: u8) -> Vec<u16> {
+        let mut networks: Vec<u16> = self
+            .ports
+            .iter()
+            .filter(|p| p.id != port)
+            .map(|p| p.network)
+            .chain(
+                self.routes
+                    .iter()
+                    .filter(|(_, r)| r.port != port)
+                    .map(|(n, _)| *n),
+            )
+            .collect();
+        networks.sort_unstable();
+        networks.dedup();
+        networks
+    }
+
+    /// Route an NPDU received on `port` from the station at `source_mac`. A message to a
+    /// remote network has its hop count decremented and is discarded when it reaches zero.
+    pub fn receive(&mut self, port: u8, source_mac: &[u8], npdu: NPDU) -> Vec<Forward> {
+        let Some(network) = self.ports.iter().find(|p| p.id == port).map(|p| p.network) else {
+            trace!("NPDU received on unknown port {}", port);
+            return vec![];
+        };
+        let Some(mut destination) = npdu.destination.clone() else {
+            return match npdu.content {
+                NPDUContent::Message(_) => self.receive_message(port, source_mac, npdu),
+                _ => vec![Forward::Local(npdu)],
+            };
+        };
+
+        destination.hops = Some(destination.hops().saturating_sub(1));
+        if destination.hops() == 0 {
+            trace!("Hop count of NPDU to network {} exhausted", destination.net);
+            return vec![];
+        }
+        // Messages leaving the network they originate from carry the address of their sender
+        let source = npdu
+            .source
+            .clone()
+            .unwrap_or_else(|| NPDUSource::with_address(network, source_mac));
+
+        if destination.net == GLOBAL_BROADCAST_NETWORK {
+            let mut forwards = vec![Forward::Local(npdu.clone())];
+            forwards.extend(
+                self.ports
+                    .iter()
+                    .filter(|p| p.id != port)
+                    .map(|p| Forward::Send {
+                        port: p.id,
+                        mac: Vec::new(),
+                        npdu: NPDU {
+                            destination: Some(destination.clone()),
+                            source: Some(source.clone()),
+                            ..npdu.clone()
+                        },
+                    }),
+            );
+            return forwards;
+        }
+
+        if let Some(target) = self.ports.iter().find(|p| p.network == destination.net) {
+            if target.id == port {
+                trace!(
+                    "NPDU to network {} received on that network",
+                    destination.net
+                );
+                return vec![];
+            }
+            // Delivered on the destination network, without DNET and DADR
+            return vec![Forward::Send {
+                port: target.id,
+                mac: destination.adr,
+                npdu: NPDU {
+                    destination: None,
+                    source: Some(source),
+                    ..npdu
+                },
+            }];
+        }
+
+        match self.routes.get(&destination.net) {
+            Some(route) if route.port != port => vec![Forward::Send {
+                port: route.port,
+                mac: route.next_router.clone(),
+                npdu: NPDU {
+                    destination: Some(destination),
+                    source: Some(source),
+                    ..npdu
+                },
+            }],
+            _ => vec![Forward::Send {
+                port,
+                mac: source_mac.to_vec(),
+                npdu: NPDU::new(
+                    NPDUContent::Message(NPDUMessage::RejectMessageToNetwork {
+                        reason: REJECT_NO_ROUTE,
+                        dnet: destination.net,
+                    }),
+                    None,
+                    None,
+                    NPDUPriority::Normal,
+                ),
+            }],
+        }
+    }
+
+    /// Handle a network layer message to the router, answering Who-Is-Router-To-Network from
+    /// the routing table and learning routes from I-Am-Router-To-Network (6.6.3)
+    fn receive_message(&mut self, port: u8, source_mac: &[u8], npdu: NPDU) -> Vec<Forward> {
+        match &npdu.content {
+            NPDUContent::Message(NPDUMessage::WhoIsRouterToNetwork(requested)) => {
+                let networks: Vec<u16> = self
+                    .reachable_networks(port)
+                    .into_iter()
+                    .filter(|n| requested.is_none_or(|r| r == *n))
+                    .collect();
+                if networks.is_empty() {
+                    return vec![];
+                }
+                return vec![Forward::Send {
+                    port,
+                    mac: Vec::new(),
+                    npdu: NPDU::new(
+                        NPDUContent::Message(NPDUMessage::IAmRouterToNetwork(networks)),
+                        None,
+                        None,
+                        NPDUPriority::Normal,
+                    ),
+                }];
+            }
+            NPDUContent::Message(NPDUMessage::IAmRouterToNetwork(networks)) => {
+                for network in networks {
+                    if !self.ports.iter().any(|p| p.network == *network) {
+                        self.add_route(*network, port, source_mac.to_vec());
+                    }
+                }
+            }
+            _ => (),
+        }
+        vec![Forward::Local(npdu)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::APDU;
+    use crate::network::NPDUDest;
+
+    const ROUTER_B: [u8; 6] = [0x0a, 0x00, 0x00, 0x02, 0xba, 0xc0];
+    const SENDER: [u8; 6] = [0x0a, 0x00, 0x01, 0x05, 0xba, 0xc0];
+
+    /// Router between network 1 on port 1 and network 2 on port 2, reaching network 3
+    /// through ROUTER_B on network 2
+    fn router() -> Router {
+        Router::new()
+            .port(1, 1)
+            .port(2, 2)
+            .route(3, 2, ROUTER_B.to_vec())
+    }
+
+    fn read_property(net: u16, hops: u8) -> NPDU {
+        let apdu = APDU::new(0x00, 12, hex::decode("0c000000011955").unwrap());
+        let destination = NPDUDest::with_address(net, &[0x07]).with_hops(hops);
+        NPDU::new(apdu, Some(destination), None, NPDUPriority::Normal)
+    }
+
+    #[test]
+    fn test_forward_decrements_hop_count() {
+        let forwards = router().receive(1, &SENDER, read_property(3, 3));
+
+        let [Forward::Send { port, mac, npdu }] = &forwards[..] else {
+            panic!("Not forwarded: {:?}", forwards);
+        };
+        assert_eq!(*port, 2);
+        assert_eq!(mac, &ROUTER_B);
+        let destination = npdu.destination.as_ref().unwrap();
+        assert_eq!(destination.net, 3);
+        assert_eq!(destination.hops(), 2);
+        assert_eq!(npdu.source, Some(NPDUSource::with_address(1, &SENDER)));
+    }
+
+    #[test]
+    fn test_drop_at_hop_count_zero() {
+        assert_eq!(router().receive(1, &SENDER, read_property(3, 1)), vec![]);
+        assert_eq!(router().receive(1, &SENDER, read_property(2, 0)), vec![]);
+    }
+
+    #[test]
+    fn test_deliver_to_directly_connected_network() {
+        let forwards = router().receive(1, &SENDER, read_property(2, 255));
+
+        let [Forward::Send { port, mac, npdu }] = &forwards[..] else {
+            panic!("Not forwarded: {:?}", forwards);
+        };
+        assert_eq!((*port, &mac[..]), (2, &[0x07][..]));
+        assert_eq!(npdu.destination, None);
+        assert_eq!(npdu.source.as_ref().unwrap().net, 1);
+    }
+
+    #[test]
+    fn test_reject_unknown_network() {
+        let forwards = router().receive(1, &SENDER, read_property(9, 255));
+
+        let [Forward::Send { port, mac, npdu }] = &forwards[..] else {
+            panic!("Not rejected: {:?}", forwards);
+        };
+        assert_eq!((*port, &mac[..]), (1, &SENDER[..]));
+        assert_eq!(
+            npdu.content,
+            NPDUContent::Message(NPDUMessage::RejectMessageToNetwork {
+                reason: REJECT_NO_ROUTE,
+                dnet: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_global_broadcast() {
+        let forwards = router().receive(1, &SENDER, read_property(GLOBAL_BROADCAST_NETWORK, 255));
+
+        assert_eq!(forwards.len(), 2);
+        assert!(matches!(forwards[0], Forward::Local(_)));
+        let Forward::Send { port, mac, npdu } = &forwards[1] else {
+            panic!("Not broadcast: {:?}", forwards);
+        };
+        assert_eq!((*port, mac.len()), (2, 0));
+        assert_eq!(npdu.destination.as_ref().unwrap().hops(), 254);
+    }
+
+    #[test]
+    fn test_answer_who_is_router_to_network() {
+        let mut router = router();
+        let who_is = |net| {
+            NPDU::new(
+                NPDUContent::Message(NPDUMessage::WhoIsRouterToNetwork(net)),
+                None,
+                None,
+                NPDUPriority::Normal,
+            )
+        };
+        let i_am = |networks| {
+            vec![Forward::Send {
+                port: 1,
+                mac: vec![],
+                npdu: NPDU::new(
+                    NPDUContent::Message(NPDUMessage::IAmRouterToNetwork(networks)),
+                    None,
+                    None,
+                    NPDUPriority::Normal,
+                ),
+            }]
+        };
+
+        assert_eq!(router.receive(1, &SENDER, who_is(None)), i_am(vec![2, 3]));
+        assert_eq!(router.receive(1, &SENDER, who_is(Some(3))), i_am(vec![3]));
+        assert_eq!(router.receive(1, &SENDER, who_is(Some(1))), vec![]);
+    }
+
+    #[test]
+    fn test_learn_route_from_i_am_router_to_network() {
+        let mut router = router();
+        let i_am = NPDU::new(
+            NPDUContent::Message(NPDUMessage::IAmRouterToNetwork(vec![2, 4])),
+            None,
+            None,
+            NPDUPriority::Normal,
+        );
+        router.receive(2, &ROUTER_B, i_am);
+
+        assert_eq!(
+            router.routes().get(&4),
+            Some(&Route {
+                port: 2,
+                next_router: ROUTER_B.to_vec()
+            })
+        );
+        assert_eq!(router.routes().get(&2), None);
+    }
+}
@@ -0,0 +1,359 @@
+/// BACnet/IP device answering requests from other devices (Annex J)
+use crate::application::*;
+use crate::client::BACNET_IP_PORT;
+use crate::encoding::ObjectIdentifier;
+use crate::network::*;
+use crate::transport::bacnetip::*;
+use crate::{Decode, Encode};
+
+use async_std::net::UdpSocket;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
+
+use tracing::trace;
+
+/// Largest BVLC datagram, a 1476 octet APDU with NPCI and BVLL header
+const MAX_DATAGRAM: usize = 1497;
+
+/// Applies the clock of a received TimeSynchronization or UTCTimeSynchronization
+pub type ClockHandler = Arc<dyn Fn(&UnconfirmedService) + Send + Sync>;
+
+/// A device exposed on the network, answering Who-Is with I-Am
+#[derive(Clone)]
+pub struct Device {
+    pub device_identifier: ObjectIdentifier,
+    pub vendor_id: u16,
+    pub max_apdu_length_accepted: u32,
+    pub segmentation_supported: Segmentation,
+    /// Destination of broadcast I-Am replies
+    pub broadcast_address: SocketAddrV4,
+    /// Time synchronizations are ignored without a handler
+    pub clock: Option<ClockHandler>,
+}
+
+impl std::fmt::Debug for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Device")
+            .field("device_identifier", &self.device_identifier)
+            .field("vendor_id", &self.vendor_id)
+            .field("max_apdu_length_accepted", &self.max_apdu_length_accepted)
+            .field("segmentation_supported", &self.segmentation_supported)
+            .field("broadcast_address", &self.broadcast_address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Device {
+    pub fn new(device_identifier: ObjectIdentifier, vendor_id: u16) -> Self {
+        Self {
+            device_identifier,
+            vendor_id,
+            max_apdu_length_accepted: MaxApduLengthAccepted::default().max_bytes() as u32,
+            segmentation_supported: Segmentation::None,
+            broadcast_address: SocketAddrV4::new(Ipv4Addr::BROADCAST, BACNET_IP_PORT),
+            clock: None,
+        }
+    }
+
+    pub fn max_apdu_length_accepted(mut self, octets: u32) -> Self {
+        self.max_apdu_length_accepted = octets;
+        self
+    }
+
+    pub fn segmentation_supported(mut self, segmentation: Segmentation) -> Self {
+        self.segmentation_supported = segmentation;
+        self
+    }
+
+    pub fn broadcast_address(mut self, address: SocketAddrV4) -> Self {
+        self.broadcast_address = address;
+        self
+    }
+
+    /// Apply received TimeSynchronization and UTCTimeSynchronization requests with `clock`
+    pub fn clock(mut self, clock: impl Fn(&UnconfirmedService) + Send + Sync + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// I-Am announcing this device
+    pub fn i_am(&self) -> IAm {
+        IAm::new(
+            self.device_identifier,
+            self.max_apdu_length_accepted,
+            self.segmentation_supported,
+            self.vendor_id,
+        )
+    }
+
+    /// Handle a datagram received from `source`, returning the reply and where to send it.
+    /// A Who-Is sent directly to this device is answered with a unicast I-Am, otherwise the
+    /// I-Am is broadcast. Time synchronizations are passed to the clock handler.
+    pub fn handle(
+        &self,
+        source: SocketAddrV4,
+        data: &[u8],
+    ) -> std::io::Result<Option<(SocketAddrV4, Vec<u8>)>> {
+        let bvlc = BVLC::decode_slice(data)?;
+        let Some(NPDUContent::APDU(apdu)) = bvlc.function.npdu().map(|n| &n.content) else {
+            return Ok(None);
+        };
+        if apdu.pdu_type() != Some(BACnetPDU::UnconfirmedRequest) {
+            return Ok(None);
+        }
+        match apdu.service_choice {
+            WhoIs::SERVICE_CHOICE => self.answer_who_is(source, &bvlc, apdu),
+            TimeSynchronization::SERVICE_CHOICE => {
+                let request = TimeSynchronization::decode_slice(apdu.user_data())?;
+                self.synchronize(UnconfirmedService::TimeSynchronization(request));
+                Ok(None)
+            }
+            TimeSynchronization::UTC_SERVICE_CHOICE => {
+                let request = TimeSynchronization::decode_slice(apdu.user_data())?;
+                self.synchronize(UnconfirmedService::UtcTimeSynchronization(request));
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn answer_who_is(
+        &self,
+        source: SocketAddrV4,
+        bvlc: &BVLC,
+        apdu: &APDU,
+    ) -> std::io::Result<Option<(SocketAddrV4, Vec<u8>)>> {
+        let who_is = WhoIs::decode_slice(apdu.user_data())?;
+        if !who_is.matches(self.device_identifier.instance()) {
+            trace!(
+                "Who-Is {:?} not addressed to {}",
+                who_is, self.device_identifier
+            );
+            return Ok(None);
+        }
+
+        let apdu = APDU::new(
+            BACnetPDU::UnconfirmedRequest.as_u8(),
+            IAm::SERVICE_CHOICE,
+            self.i_am().encode_vec()?,
+        );
+        let (address, function) = match bvlc.function {
+            BVLCFunction::OriginalUnicastNPDU(_) => {
+                let npdu = NPDU::new(apdu, None, None, NPDUPriority::Normal);
+                let address = bvlc.originating_address(source);
+                (address, BVLCFunction::OriginalUnicastNPDU(npdu))
+            }
+            _ => {
+                let destination = NPDUDest::new(0xffff);
+                let npdu = NPDU::new(apdu, Some(destination), None, NPDUPriority::Normal);
+                (
+                    self.broadcast_address,
+                    BVLCFunction::OriginalBroadcastNPDU(npdu),
+                )
+            }
+        };
+        Ok(Some((address, BVLC::new(function).encode_vec()?)))
+    }
+
+    fn synchronize(&self, request: UnconfirmedService) {
+        match &self.clock {
+            Some(clock) => clock(&request),
+            None => trace!("No clock to apply {:?}", request),
+        }
+    }
+
+    /// Answer the requests received on `socket` until receiving fails
+    pub async fn serve(&self, socket: &UdpSocket) -> std::io::Result<()> {
+        socket.set_broadcast(true)?;
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        loop {
+            let (n, peer) = socket.recv_from(&mut buf).await?;
+            let SocketAddr::V4(peer) = peer else {
+                continue;
+            };
+            match self.handle(peer, &buf[..n]) {
+                Ok(Some((address, reply))) => {
+                    socket.send_to(&reply, address).await?;
+                }
+                Ok(None) => {}
+                Err(e) => trace!("Invalid datagram from {}: {}", peer, e),
+            }
+        }
+    }
+}
+
+/// Change of value filter of an analog value subscribed with a COV increment (13.1.4). A value
+/// is notified once it moved by at least the increment from the value notified last
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CovFilter {
+    pub increment: f32,
+    last_notified: Option<f32>,
+}
+
+impl CovFilter {
+    pub fn new(increment: f32) -> Self {
+        Self {
+            increment,
+            last_notified: None,
+        }
+    }
+
+    /// Whether a change to `value` is notified, the first value always is
+    pub fn update(&mut self, value: f32) -> bool {
+        let notify = self
+            .last_notified
+            .is_none_or(|last| (value - last).abs() >= self.increment);
+        if notify {
+            self.last_notified = Some(value);
+        }
+        notify
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::ObjectType;
+    use async_std::task;
+
+    const SOURCE: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 47808);
+
+    fn device() -> Device {
+        Device::new(ObjectIdentifier::new(ObjectType::Device, 1).unwrap(), 15)
+    }
+
+    fn who_is(function: fn(NPDU) -> BVLCFunction, request: WhoIs) -> Vec<u8> {
+        let apdu = APDU::new(
+            BACnetPDU::UnconfirmedRequest.as_u8(),
+            WhoIs::SERVICE_CHOICE,
+            request.encode_vec().unwrap(),
+        );
+        let npdu = NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff)),
+            None,
+            NPDUPriority::Normal,
+        );
+        BVLC::new(function(npdu)).encode_vec().unwrap()
+    }
+
+    #[test]
+    fn test_who_is_answered_with_i_am_broadcast() {
+        let data = hex::decode("810b000c0120ffff00ff1008").unwrap();
+        let (address, reply) = device().handle(SOURCE, &data).unwrap().unwrap();
+
+        assert_eq!(
+            address,
+            SocketAddrV4::new(Ipv4Addr::BROADCAST, BACNET_IP_PORT)
+        );
+        // I-Am device,1 max-apdu 1476, no segmentation, vendor 15
+        assert_eq!(
+            reply,
+            hex::decode("810b00180120ffff00ff1000c4020000012205c49103210f").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_directed_who_is_answered_with_unicast_i_am() {
+        let data = who_is(BVLCFunction::OriginalUnicastNPDU, WhoIs::range(1, 1));
+        let (address, reply) = device().handle(SOURCE, &data).unwrap().unwrap();
+
+        assert_eq!(address, SOURCE);
+        assert_eq!(
+            reply,
+            hex::decode("810a001401001000c4020000012205c49103210f").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_who_is_outside_range_ignored() {
+        let data = who_is(BVLCFunction::OriginalBroadcastNPDU, WhoIs::range(2, 100));
+        assert_eq!(device().handle(SOURCE, &data).unwrap(), None);
+    }
+
+    #[test]
+    fn test_other_requests_ignored() {
+        // I-Am of another device
+        let data = hex::decode("810b00180120ffff00ff1000c4020000022205c49103210f").unwrap();
+        assert_eq!(device().handle(SOURCE, &data).unwrap(), None);
+        assert!(device().handle(SOURCE, &[0x81, 0x0b]).is_err());
+    }
+
+    #[test]
+    fn test_time_synchronization_applied() {
+        let applied = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let device = device().clock({
+            let applied = applied.clone();
+            move |request: &UnconfirmedService| applied.lock().unwrap().push(request.clone())
+        });
+        let request = TimeSynchronization::new(
+            crate::encoding::Date::new(2024, 3, 15, 5).unwrap(),
+            crate::encoding::Time::new(14, 30, 5, 25),
+        );
+        for service_choice in [
+            TimeSynchronization::SERVICE_CHOICE,
+            TimeSynchronization::UTC_SERVICE_CHOICE,
+        ] {
+            let apdu = APDU::new(
+                BACnetPDU::UnconfirmedRequest.as_u8(),
+                service_choice,
+                request.encode_vec().unwrap(),
+            );
+            let npdu = NPDU::new(apdu, None, None, NPDUPriority::Normal);
+            let data = BVLC::new(BVLCFunction::OriginalUnicastNPDU(npdu))
+                .encode_vec()
+                .unwrap();
+            assert_eq!(device.handle(SOURCE, &data).unwrap(), None);
+        }
+
+        assert_eq!(
+            *applied.lock().unwrap(),
+            vec![
+                UnconfirmedService::TimeSynchronization(request),
+                UnconfirmedService::UtcTimeSynchronization(request)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serve_who_is() {
+        task::block_on(async {
+            let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let SocketAddr::V4(client_address) = client.local_addr().unwrap() else {
+                unreachable!()
+            };
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let server_address = socket.local_addr().unwrap();
+            let server = device().broadcast_address(client_address);
+            task::spawn(async move { server.serve(&socket).await });
+
+            let data = who_is(BVLCFunction::OriginalBroadcastNPDU, WhoIs::new());
+            client.send_to(&data, server_address).await.unwrap();
+
+            let mut buf = vec![0u8; MAX_DATAGRAM];
+            let (n, _) = client.recv_from(&mut buf).await.unwrap();
+            let bvlc = BVLC::decode_slice(&buf[..n]).unwrap();
+            let Some(NPDUContent::APDU(apdu)) = bvlc.function.npdu().map(|n| &n.content) else {
+                panic!("Reply without APDU: {:?}", bvlc);
+            };
+            assert_eq!(apdu.service_choice, IAm::SERVICE_CHOICE);
+            assert_eq!(
+                IAm::decode_slice(apdu.user_data()).unwrap(),
+                device().i_am()
+            );
+        });
+    }
+
+    #[test]
+    fn test_cov_filter_increment() {
+        let mut filter = CovFilter::new(1.0);
+        assert!(filter.update(20.0));
+
+        // Changes below the increment are not notified, nor do they move the reference value
+        assert!(!filter.update(20.5));
+        assert!(!filter.update(20.9));
+        assert!(filter.update(21.0));
+        assert!(!filter.update(20.2));
+        assert!(filter.update(19.9));
+    }
+}
@@ -1,8 +1,8 @@
 /// Implement of the data link and physical layer
 ///
-/// - [ ] Ethernet (ISO 8802-3)             Clause 7
+/// - [x] Ethernet (ISO 8802-3)             Clause 7
 /// - [ ] ARCNET (ATA 878.1)                Clause 8
-/// - [ ] MS/TP                             Clause 9
+/// - [x] MS/TP                             Clause 9
 /// - [ ] PTP                               Clause 10
 /// - [ ] LonTalk (ISO/IEC 14908.1)         Clause 11
 /// - [x] BACnet/IP                         Annex J
@@ -13,5 +13,8 @@
 /// See Figure 4-2. BACnet collapsed architecture.
 ///
 ///
+#[cfg(feature = "std")]
 pub mod bacnetip;
 pub mod bacnetsc;
+pub mod ethernet;
+pub mod mstp;
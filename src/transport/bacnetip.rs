@@ -1,8 +1,15 @@
 /// Implements BACnet/IP (Annex J)
+use crate::io::Read;
 use crate::network::*;
-use crate::{Decode, Encode};
+use crate::{Decode, Encode, Error};
 
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::ops::Range;
+
+pub mod bbmd;
+pub mod foreign_device;
+pub use bbmd::*;
+pub use foreign_device::*;
 
 const BACNETIP: u8 = 0x81;
 
@@ -10,39 +17,308 @@ pub trait AsU8 {
     fn as_u8(&self) -> u8;
 }
 
+/// Result code of a BVLC-Result message (J.2.1.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BVLCResultCode {
+    SuccessfulCompletion,               // = 0x0000,
+    WriteBroadcastDistributionTableNAK, // = 0x0010,
+    ReadBroadcastDistributionTableNAK,  // = 0x0020,
+    RegisterForeignDeviceNAK,           // = 0x0030,
+    ReadForeignDeviceTableNAK,          // = 0x0040,
+    DeleteForeignDeviceTableEntryNAK,   // = 0x0050,
+    DistributeBroadcastToNetworkNAK,    // = 0x0060,
+    Other(u16),
+}
+
+impl From<u16> for BVLCResultCode {
+    fn from(v: u16) -> Self {
+        match v {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0010 => Self::WriteBroadcastDistributionTableNAK,
+            0x0020 => Self::ReadBroadcastDistributionTableNAK,
+            0x0030 => Self::RegisterForeignDeviceNAK,
+            0x0040 => Self::ReadForeignDeviceTableNAK,
+            0x0050 => Self::DeleteForeignDeviceTableEntryNAK,
+            0x0060 => Self::DistributeBroadcastToNetworkNAK,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<BVLCResultCode> for u16 {
+    fn from(val: BVLCResultCode) -> Self {
+        match val {
+            BVLCResultCode::SuccessfulCompletion => 0x0000,
+            BVLCResultCode::WriteBroadcastDistributionTableNAK => 0x0010,
+            BVLCResultCode::ReadBroadcastDistributionTableNAK => 0x0020,
+            BVLCResultCode::RegisterForeignDeviceNAK => 0x0030,
+            BVLCResultCode::ReadForeignDeviceTableNAK => 0x0040,
+            BVLCResultCode::DeleteForeignDeviceTableEntryNAK => 0x0050,
+            BVLCResultCode::DistributeBroadcastToNetworkNAK => 0x0060,
+            BVLCResultCode::Other(v) => v,
+        }
+    }
+}
+
+/// Entry of a Broadcast Distribution Table (J.4.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BdtEntry {
+    pub address: SocketAddrV4,
+    pub mask: Ipv4Addr,
+}
+
+impl Encode for BdtEntry {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        encode_address(&self.address, writer)?;
+        writer.write_all(&self.mask.octets())?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        10 // B/IP Address(6) + Broadcast Distribution Mask(4)
+    }
+}
+
+impl Decode for BdtEntry {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let address = decode_address(reader)?;
+        let mask = Ipv4Addr::from(reader.read_u32()?);
+        Ok(Self { address, mask })
+    }
+}
+
+/// Entry of a Foreign Device Table (J.5.2.1)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FdtEntry {
+    pub address: SocketAddrV4,
+    /// Time-to-live supplied at registration, in seconds
+    pub ttl: u16,
+    /// Seconds remaining before the entry is purged
+    pub time_remaining: u16,
+}
+
+impl Encode for FdtEntry {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        encode_address(&self.address, writer)?;
+        writer.write_u16(self.ttl)?;
+        writer.write_u16(self.time_remaining)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        10 // B/IP Address(6) + Time-to-Live(2) + Time Remaining(2)
+    }
+}
+
+impl Decode for FdtEntry {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let address = decode_address(reader)?;
+        let ttl = reader.read_u16()?;
+        let time_remaining = reader.read_u16()?;
+        Ok(Self {
+            address,
+            ttl,
+            time_remaining,
+        })
+    }
+}
+
 /// BACnet Virtual Link Control Function
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BVLCFunction {
+    Result(BVLCResultCode),
+    WriteBroadcastDistributionTable(Vec<BdtEntry>),
+    ReadBroadcastDistributionTable,
+    ReadBroadcastDistributionTableAck(Vec<BdtEntry>),
+    /// Broadcast forwarded by a BBMD, prefixed with the B/IP address of the originating device
+    ForwardedNPDU {
+        originating_address: SocketAddrV4,
+        npdu: NPDU,
+    },
+    /// Registration of a foreign device with a BBMD for the given time-to-live
+    RegisterForeignDevice {
+        ttl_seconds: u16,
+    },
+    ReadForeignDeviceTable,
+    ReadForeignDeviceTableAck(Vec<FdtEntry>),
+    /// Removal of the foreign device with the given B/IP address from the FDT
+    DeleteForeignDeviceTableEntry(SocketAddrV4),
+    /// Broadcast sent by a foreign device for the BBMD to distribute
+    DistributeBroadcastToNetwork(NPDU),
     OriginalBroadcastNPDU(NPDU),
     OriginalUnicastNPDU(NPDU),
 }
 
+impl BVLCFunction {
+    /// The NPDU carried by this function, if any
+    pub fn npdu(&self) -> Option<&NPDU> {
+        match self {
+            Self::Result(_)
+            | Self::WriteBroadcastDistributionTable(_)
+            | Self::ReadBroadcastDistributionTable
+            | Self::ReadBroadcastDistributionTableAck(_)
+            | Self::RegisterForeignDevice { .. }
+            | Self::ReadForeignDeviceTable
+            | Self::ReadForeignDeviceTableAck(_)
+            | Self::DeleteForeignDeviceTableEntry(_) => None,
+            Self::ForwardedNPDU { npdu, .. } => Some(npdu),
+            Self::DistributeBroadcastToNetwork(n)
+            | Self::OriginalBroadcastNPDU(n)
+            | Self::OriginalUnicastNPDU(n) => Some(n),
+        }
+    }
+}
+
 impl AsU8 for BVLCFunction {
     fn as_u8(&self) -> u8 {
         match self {
+            Self::Result(_) => 0x00,
+            Self::WriteBroadcastDistributionTable(_) => 0x01,
+            Self::ReadBroadcastDistributionTable => 0x02,
+            Self::ReadBroadcastDistributionTableAck(_) => 0x03,
+            Self::ForwardedNPDU { .. } => 0x04,
+            Self::RegisterForeignDevice { .. } => 0x05,
+            Self::ReadForeignDeviceTable => 0x06,
+            Self::ReadForeignDeviceTableAck(_) => 0x07,
+            Self::DeleteForeignDeviceTableEntry(_) => 0x08,
+            Self::DistributeBroadcastToNetwork(_) => 0x09,
             Self::OriginalBroadcastNPDU(_) => 0x0b,
             Self::OriginalUnicastNPDU(_) => 0x0a,
         }
     }
 }
 
+/// Formatted as the function name (J.2) followed by its parameters or the carried NPDU content,
+/// e.g. "Original-Broadcast-NPDU(Unconfirmed-Request(Who-Is))"
+impl core::fmt::Display for BVLCFunction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Result(code) => write!(f, "BVLC-Result({:?})", code),
+            Self::WriteBroadcastDistributionTable(entries) => write!(
+                f,
+                "Write-Broadcast-Distribution-Table(entries={})",
+                entries.len()
+            ),
+            Self::ReadBroadcastDistributionTable => {
+                f.write_str("Read-Broadcast-Distribution-Table")
+            }
+            Self::ReadBroadcastDistributionTableAck(entries) => write!(
+                f,
+                "Read-Broadcast-Distribution-Table-Ack(entries={})",
+                entries.len()
+            ),
+            Self::ForwardedNPDU {
+                originating_address,
+                npdu,
+            } => write!(
+                f,
+                "Forwarded-NPDU(from={}, {})",
+                originating_address, npdu.content
+            ),
+            Self::RegisterForeignDevice { ttl_seconds } => {
+                write!(f, "Register-Foreign-Device(ttl={})", ttl_seconds)
+            }
+            Self::ReadForeignDeviceTable => f.write_str("Read-Foreign-Device-Table"),
+            Self::ReadForeignDeviceTableAck(entries) => write!(
+                f,
+                "Read-Foreign-Device-Table-Ack(entries={})",
+                entries.len()
+            ),
+            Self::DeleteForeignDeviceTableEntry(address) => {
+                write!(f, "Delete-Foreign-Device-Table-Entry({})", address)
+            }
+            Self::DistributeBroadcastToNetwork(npdu) => {
+                write!(f, "Distribute-Broadcast-To-Network({})", npdu.content)
+            }
+            Self::OriginalBroadcastNPDU(npdu) => {
+                write!(f, "Original-Broadcast-NPDU({})", npdu.content)
+            }
+            Self::OriginalUnicastNPDU(npdu) => write!(f, "Original-Unicast-NPDU({})", npdu.content),
+        }
+    }
+}
+
+/// Write a B/IP address (6 octets: IP address followed by the UDP port)
+fn encode_address<T: crate::io::Write + Sized>(
+    address: &SocketAddrV4,
+    writer: &mut T,
+) -> crate::Result<()> {
+    writer.write_all(&address.ip().octets())?;
+    writer.write_u16(address.port())?;
+    Ok(())
+}
+
+/// Read a B/IP address (6 octets: IP address followed by the UDP port)
+fn decode_address<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<SocketAddrV4> {
+    let ip = Ipv4Addr::from(reader.read_u32()?);
+    let port = reader.read_u16()?;
+    Ok(SocketAddrV4::new(ip, port))
+}
+
 impl Encode for BVLCFunction {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
         match self {
-            Self::OriginalBroadcastNPDU(n) | Self::OriginalUnicastNPDU(n) => n.encode(writer)?,
+            Self::Result(code) => writer.write_u16((*code).into())?,
+            Self::WriteBroadcastDistributionTable(entries)
+            | Self::ReadBroadcastDistributionTableAck(entries) => {
+                for entry in entries {
+                    entry.encode(writer)?;
+                }
+            }
+            Self::ReadBroadcastDistributionTable => (),
+            Self::RegisterForeignDevice { ttl_seconds } => writer.write_u16(*ttl_seconds)?,
+            Self::ReadForeignDeviceTable => (),
+            Self::ReadForeignDeviceTableAck(entries) => {
+                for entry in entries {
+                    entry.encode(writer)?;
+                }
+            }
+            Self::DeleteForeignDeviceTableEntry(address) => encode_address(address, writer)?,
+            Self::ForwardedNPDU {
+                originating_address,
+                npdu,
+            } => {
+                encode_address(originating_address, writer)?;
+                npdu.encode(writer)?;
+            }
+            Self::DistributeBroadcastToNetwork(n)
+            | Self::OriginalBroadcastNPDU(n)
+            | Self::OriginalUnicastNPDU(n) => n.encode(writer)?,
         }
         Ok(())
     }
 
     fn len(&self) -> usize {
         match self {
-            Self::OriginalBroadcastNPDU(n) | Self::OriginalUnicastNPDU(n) => n.len(),
+            Self::Result(_) => 2, // Result Code(2)
+            Self::WriteBroadcastDistributionTable(entries)
+            | Self::ReadBroadcastDistributionTableAck(entries) => {
+                entries.iter().map(|e| e.len()).sum()
+            }
+            Self::ReadBroadcastDistributionTable => 0,
+            Self::RegisterForeignDevice { .. } => 2, // Time-to-Live(2)
+            Self::ReadForeignDeviceTable => 0,
+            Self::ReadForeignDeviceTableAck(entries) => entries.iter().map(|e| e.len()).sum(),
+            Self::DeleteForeignDeviceTableEntry(_) => 6, // B/IP Address(6)
+            Self::ForwardedNPDU { npdu, .. } => 6 + npdu.len(), // B/IP Address(6) + NPDU
+            Self::DistributeBroadcastToNetwork(n)
+            | Self::OriginalBroadcastNPDU(n)
+            | Self::OriginalUnicastNPDU(n) => n.len(),
         }
     }
 }
 
 /// A Struct containing a BACnet Virtual Link Control (Annex J).
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BVLC<F = BVLCFunction> {
     bvlc_type: u8,
     pub function: F,
@@ -61,11 +337,59 @@ impl<F> BVLC<F> {
     }
 }
 
+/// Byte ranges occupied by each protocol layer within a decoded frame
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodeSpans {
+    pub bvlc: Range<usize>,
+    pub npdu: Range<usize>,
+    pub apdu: Range<usize>,
+}
+
+impl BVLC {
+    /// Address of the device that originated the NPDU, for a Forwarded-NPDU this is the address
+    /// carried in the header rather than the BBMD the datagram was received from
+    pub fn originating_address(&self, received_from: SocketAddrV4) -> SocketAddrV4 {
+        match self.function {
+            BVLCFunction::ForwardedNPDU {
+                originating_address,
+                ..
+            } => originating_address,
+            _ => received_from,
+        }
+    }
+
+    /// Decode a frame and record where the BVLC header, NPDU header and APDU are located in it.
+    /// A network layer message is part of the NPDU span and leaves the APDU span empty
+    pub fn decode_with_spans(slice: &[u8]) -> crate::Result<(Self, DecodeSpans)> {
+        let mut reader = slice;
+        let (bvlc, npci) = Self::decode_with_npci(&mut reader)?;
+        let end = slice.len();
+        let spans = match (npci, bvlc.function.npdu().map(|n| &n.content)) {
+            (Some(npci), Some(NPDUContent::APDU(_))) => DecodeSpans {
+                bvlc: 0..npci.start,
+                npdu: npci.clone(),
+                apdu: npci.end..end,
+            },
+            (Some(npci), _) => DecodeSpans {
+                bvlc: 0..npci.start,
+                npdu: npci.start..end,
+                apdu: end..end,
+            },
+            (None, _) => DecodeSpans {
+                bvlc: 0..end,
+                npdu: end..end,
+                apdu: end..end,
+            },
+        };
+        Ok((bvlc, spans))
+    }
+}
+
 impl<F: Encode + AsU8> Encode for BVLC<F> {
-    fn encode<T: std::io::Write + Sized>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
         writer.write_u8(self.bvlc_type)?;
         writer.write_u8(self.function.as_u8())?;
-        writer.write_u16::<BigEndian>(self.len() as u16)?;
+        writer.write_u16(self.len() as u16)?;
         self.function.encode(writer)?;
         Ok(())
     }
@@ -80,35 +404,133 @@ impl<F: Encode + AsU8> Encode for BVLC<F> {
     }
 }
 
-impl Decode for BVLC {
-    fn decode<T: std::io::Read + Sized>(reader: &mut T) -> std::io::Result<Self> {
-        let bvlc_type = reader.read_u8()?;
-        if bvlc_type != BACNETIP {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("BVLC type not supported: {}", bvlc_type),
-            ));
-        }
-        let function = reader.read_u8()?;
-        let _lenght = reader.read_u16::<BigEndian>()?; // TODO: Check lenght
+/// Decode a table of fixed size entries filling the whole payload
+fn decode_entries<E: Decode<Error = Error>>(payload: &[u8]) -> crate::Result<Vec<E>> {
+    let mut reader = std::io::Cursor::new(payload);
+    let mut entries = Vec::new();
+    while (reader.position() as usize) < payload.len() {
+        entries.push(E::decode(&mut reader)?);
+    }
+    Ok(entries)
+}
+
+impl BVLCFunction {
+    /// Decode the function specific payload following the BVLC header, together with the range
+    /// of the NPCI in the payload for functions carrying an NPDU
+    fn decode_payload(function: u8, payload: &[u8]) -> crate::Result<(Self, Option<Range<usize>>)> {
+        let mut reader = std::io::Cursor::new(payload);
+        let npdu = |start: usize| -> crate::Result<(NPDU, Option<Range<usize>>)> {
+            let (npdu, npci_len) = NPDU::decode_with_npci_len(&payload[start..])?;
+            Ok((npdu, Some(start..start + npci_len)))
+        };
         let function = match function {
-            0x0b => {
-                let npdu = NPDU::decode(reader)?;
-                Ok(BVLCFunction::OriginalBroadcastNPDU(npdu))
+            0x00 => {
+                let code = reader.read_u16()?;
+                Ok(Self::Result(BVLCResultCode::from(code)))
+            }
+            0x01 => Ok(Self::WriteBroadcastDistributionTable(decode_entries(
+                payload,
+            )?)),
+            0x02 => Ok(Self::ReadBroadcastDistributionTable),
+            0x03 => Ok(Self::ReadBroadcastDistributionTableAck(decode_entries(
+                payload,
+            )?)),
+            0x04 => {
+                let originating_address = decode_address(&mut reader)?;
+                let (npdu, npci) = npdu(reader.position() as usize)?;
+                let function = Self::ForwardedNPDU {
+                    originating_address,
+                    npdu,
+                };
+                return Ok((function, npci));
             }
-            0x0a => {
-                let npdu = NPDU::decode(reader)?;
-                Ok(BVLCFunction::OriginalUnicastNPDU(npdu))
+            0x05 => Ok(Self::RegisterForeignDevice {
+                ttl_seconds: reader.read_u16()?,
+            }),
+            0x06 => Ok(Self::ReadForeignDeviceTable),
+            0x07 => Ok(Self::ReadForeignDeviceTableAck(decode_entries(payload)?)),
+            0x08 => Ok(Self::DeleteForeignDeviceTableEntry(decode_address(
+                &mut reader,
+            )?)),
+            0x09..=0x0b => {
+                let (npdu, npci) = npdu(0)?;
+                let function = match function {
+                    0x09 => Self::DistributeBroadcastToNetwork(npdu),
+                    0x0a => Self::OriginalUnicastNPDU(npdu),
+                    _ => Self::OriginalBroadcastNPDU(npdu),
+                };
+                return Ok((function, npci));
             }
-            t => Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("BVLC Function not supported: {}", t),
-            )),
+            t => Err(Error::Unsupported(format!(
+                "BVLC Function not supported: {}",
+                t
+            ))),
         };
-        Ok(Self::new(function?))
+        Ok((function?, None))
+    }
+}
+
+impl BVLC {
+    /// Decode a BVLC, together with the range of the NPCI in the frame for functions carrying an
+    /// NPDU
+    fn decode_with_npci<T: crate::io::Read + Sized>(
+        reader: &mut T,
+    ) -> crate::Result<(Self, Option<Range<usize>>)> {
+        let bvlc_type = reader.read_u8()?;
+        if bvlc_type != BACNETIP {
+            return Err(Error::Unsupported(format!(
+                "BVLC type not supported: {}",
+                bvlc_type
+            )));
+        }
+        let function = reader.read_u8()?;
+        let length = reader.read_u16()? as usize;
+        let payload_length = length
+            .checked_sub(4)
+            .ok_or_else(|| Error::InvalidValue(format!("BVLC length too short: {}", length)))?;
+        let mut payload = Vec::with_capacity(payload_length);
+        reader.read_to_end(&mut payload)?;
+        if payload.len() < payload_length {
+            return Err(Error::Truncated);
+        }
+        if payload.len() > payload_length {
+            return Err(Error::InvalidValue(format!(
+                "BVLC length mismatch: {} but {} bytes received",
+                length,
+                payload.len() + 4
+            )));
+        }
+        let (function, npci) = BVLCFunction::decode_payload(function, &payload)?;
+        let npci = npci.map(|r| r.start + 4..r.end + 4);
+        Ok((Self::new(function), npci))
+    }
+}
+
+impl Decode for BVLC {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        Ok(Self::decode_with_npci(reader)?.0)
     }
 }
 
+/// Event produced by decode_stream for every datagram
+#[derive(Debug)]
+pub enum DecodedEvent {
+    Frame { source: SocketAddrV4, bvlc: BVLC },
+    Error { source: SocketAddrV4, error: Error },
+}
+
+/// Decode a sequence of captured B/IP datagrams, a datagram that fails to decode yields an error event
+pub fn decode_stream(
+    frames: impl Iterator<Item = (SocketAddrV4, Vec<u8>)>,
+) -> impl Iterator<Item = DecodedEvent> {
+    frames.map(|(source, data)| match BVLC::decode_slice(&data) {
+        Ok(bvlc) => DecodedEvent::Frame { source, bvlc },
+        Err(error) => DecodedEvent::Error { source, error },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,10 +560,319 @@ mod tests {
         let data = hex::decode("00000000").unwrap();
         let err = BVLC::decode(&mut std::io::Cursor::new(&data)).unwrap_err();
 
-        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        assert_eq!(err.to_string(), "BVLC type not supported: 0".to_string());
+    }
+
+    #[test]
+    fn test_decode_with_spans_who_is() {
+        let data = hex::decode("810b000c0120ffff00ff1008").unwrap();
+        let (_, spans) = BVLC::decode_with_spans(&data).expect("Decode BVLC");
+
+        assert_eq!(spans.bvlc, 0..4);
+        assert_eq!(spans.npdu, 4..10);
+        assert_eq!(spans.apdu, 10..12);
+        assert_eq!(spans.bvlc.end, spans.npdu.start);
+        assert_eq!(spans.npdu.end, spans.apdu.start);
+        assert_eq!(spans.apdu.end, data.len());
+    }
+
+    #[test]
+    fn test_decode_with_spans_network_message() {
+        // Who-Is-Router-To-Network, the message belongs to the NPDU
+        let data = hex::decode("810a0007018000").unwrap();
+        let (bvlc, spans) = BVLC::decode_with_spans(&data).expect("Decode BVLC");
+
+        assert!(matches!(
+            bvlc.function.npdu().map(|n| &n.content),
+            Some(NPDUContent::Message(_))
+        ));
+        assert_eq!(spans.bvlc, 0..4);
+        assert_eq!(spans.npdu, 4..7);
+        assert_eq!(spans.apdu, 7..7);
+
+        // BVLC-Result, no NPDU
+        let data = hex::decode("810000060000").unwrap();
+        let (_, spans) = BVLC::decode_with_spans(&data).expect("Decode BVLC");
+        assert_eq!(spans.bvlc, 0..6);
+        assert!(spans.npdu.is_empty());
+        assert!(spans.apdu.is_empty());
+    }
+
+    #[test]
+    fn test_decode_who_is() {
+        let data = hex::decode("810b000c0120ffff00ff1008").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+
+        assert!(matches!(
+            bvlc.function,
+            BVLCFunction::OriginalBroadcastNPDU(_)
+        ));
+        assert_eq!(bvlc.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_truncated_bvlc() {
+        let data = hex::decode("810b000c0120ffff00").unwrap();
+        let err = BVLC::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+
+        let err = BVLC::decode_slice(&data[..3]).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_bvlc_length_mismatch() {
+        let data = hex::decode("810b000a0120ffff00ff1008").unwrap();
+        let err = BVLC::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+
+        let err = BVLC::decode_slice(&hex::decode("810b0002").unwrap()).unwrap_err();
+        assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_unknown_bvlc_function() {
+        let data = hex::decode("81ff0004").unwrap();
+        let err = BVLC::decode_slice(&data).unwrap_err();
+
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        assert_eq!(
+            err.to_string(),
+            "BVLC Function not supported: 255".to_string()
+        );
+    }
+
+    #[test]
+    fn test_forwarded_npdu() {
+        let data = hex::decode("81040012c0a80164bac00120ffff00ff1008").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+
+        let npdu = match &bvlc.function {
+            BVLCFunction::ForwardedNPDU {
+                originating_address,
+                npdu,
+            } => {
+                assert_eq!(
+                    *originating_address,
+                    SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 100), 0xBAC0)
+                );
+                npdu
+            }
+            f => panic!("Not a Forwarded-NPDU: {:?}", f),
+        };
+        assert_eq!(
+            npdu.content,
+            NPDUContent::APDU(crate::application::APDU::new(1, 8, vec![]))
+        );
+        assert_eq!(bvlc.encode_vec().unwrap(), data);
+
+        let (_, spans) = BVLC::decode_with_spans(&data).expect("Decode BVLC");
+        assert_eq!(spans.bvlc, 0..10);
+        assert_eq!(spans.npdu, 10..16);
+        assert_eq!(spans.apdu, 16..18);
+    }
+
+    #[test]
+    fn test_forwarded_i_am_originating_address() {
+        let bbmd = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 1), 0xBAC0);
+        let device = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 100), 0xBAC0);
+        let data = hex::decode("8104001ac0a80164bac001001000c4020002572204009100210f").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+
+        let apdu = match &bvlc.function.npdu().expect("Forwarded NPDU").content {
+            NPDUContent::APDU(apdu) => apdu,
+            c => panic!("Not an APDU: {:?}", c),
+        };
+        assert_eq!(apdu.service_choice, 0); // I-Am
+        assert_eq!(bvlc.originating_address(bbmd), device);
+
+        let bvlc = BVLC::new(BVLCFunction::OriginalUnicastNPDU(
+            bvlc.function.npdu().unwrap().clone(),
+        ));
+        assert_eq!(bvlc.originating_address(device), device);
+    }
+
+    #[test]
+    fn test_encode_distribute_broadcast_to_network() {
+        let apdu = crate::application::APDU::new(1, 8, vec![]);
+        let npdu = NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff)),
+            None,
+            NPDUPriority::Normal,
+        );
+        let bvlc = BVLC::new(BVLCFunction::DistributeBroadcastToNetwork(npdu));
+
+        let data = bvlc.encode_vec().expect("Write BVLC to buffer");
+        assert_eq!(data, hex::decode("8109000c0120ffff00ff1008").unwrap());
+    }
+
+    #[test]
+    fn test_decode_distribute_broadcast_to_network() {
+        let data = hex::decode("8109000c0120ffff00ff1008").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+
+        let npdu = match bvlc.function {
+            BVLCFunction::DistributeBroadcastToNetwork(npdu) => npdu,
+            f => panic!("Not a Distribute-Broadcast-To-Network: {:?}", f),
+        };
+        assert_eq!(npdu.destination, Some(NPDUDest::new(0xffff)));
+        assert_eq!(
+            npdu.content,
+            NPDUContent::APDU(crate::application::APDU::new(1, 8, vec![]))
+        );
+    }
+
+    #[test]
+    fn test_register_foreign_device() {
+        let bvlc = BVLC::new(BVLCFunction::RegisterForeignDevice { ttl_seconds: 900 });
+
+        let data = bvlc.encode_vec().expect("Write BVLC to buffer");
+        assert_eq!(data, vec![0x81, 0x05, 0x00, 0x06, 0x03, 0x84]);
+        assert_eq!(BVLC::decode_slice(&data).unwrap(), bvlc);
+    }
+
+    #[test]
+    fn test_decode_bvlc_result() {
+        let bvlc = BVLC::decode_slice(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x00]).unwrap();
+        assert_eq!(
+            bvlc.function,
+            BVLCFunction::Result(BVLCResultCode::SuccessfulCompletion)
+        );
+
+        let bvlc = BVLC::decode_slice(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x30]).unwrap();
+        assert_eq!(
+            bvlc.function,
+            BVLCFunction::Result(BVLCResultCode::RegisterForeignDeviceNAK)
+        );
+
+        let bvlc = BVLC::decode_slice(&[0x81, 0x00, 0x00, 0x06, 0x12, 0x34]).unwrap();
+        assert_eq!(
+            bvlc.function,
+            BVLCFunction::Result(BVLCResultCode::Other(0x1234))
+        );
+        assert_eq!(
+            bvlc.encode_vec().unwrap(),
+            vec![0x81, 0x00, 0x00, 0x06, 0x12, 0x34]
+        );
+    }
+
+    #[test]
+    fn test_encode_write_broadcast_distribution_table() {
+        let entries = vec![
+            BdtEntry {
+                address: SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 0xBAC0),
+                mask: Ipv4Addr::new(255, 255, 255, 255),
+            },
+            BdtEntry {
+                address: SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 0xBAC0),
+                mask: Ipv4Addr::new(255, 255, 255, 0),
+            },
+        ];
+        let bvlc = BVLC::new(BVLCFunction::WriteBroadcastDistributionTable(entries));
+
+        let data = bvlc.encode_vec().expect("Write BVLC to buffer");
+        assert_eq!(
+            data,
+            hex::decode("81010018c0a8010abac0ffffffff0a000001bac0ffffff00").unwrap()
+        );
+        assert_eq!(BVLC::decode_slice(&data).unwrap(), bvlc);
+    }
+
+    #[test]
+    fn test_decode_read_broadcast_distribution_table() {
+        let bvlc = BVLC::decode_slice(&hex::decode("81020004").unwrap()).unwrap();
+        assert_eq!(bvlc.function, BVLCFunction::ReadBroadcastDistributionTable);
+
+        let data = hex::decode("8103000ec0a8010abac0ffffffff").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+        assert_eq!(
+            bvlc.function,
+            BVLCFunction::ReadBroadcastDistributionTableAck(vec![BdtEntry {
+                address: SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 0xBAC0),
+                mask: Ipv4Addr::new(255, 255, 255, 255),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_decode_partial_bdt_entry() {
+        let data = hex::decode("8103000cc0a8010abac0ffff").unwrap();
+        let err = BVLC::decode_slice(&data).unwrap_err();
+        assert!(matches!(err, Error::Truncated), "{:?}", err);
+    }
+
+    #[test]
+    fn test_decode_stream() {
+        let client = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 2), 0xBAC0);
+        let device = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 100), 0xBAC0);
+        let frames = vec![
+            (client, "810b000c0120ffff00ff1008"), // Who-Is
+            (device, "810b00180120ffff00ff1000c4020002572204009100210f"), // I-Am
+            (client, "810a001101040005010c0c000000011955"), // ReadProperty
+            (device, "810a0004ff"),               // Garbage
+        ];
+        let frames = frames
+            .into_iter()
+            .map(|(a, f)| (a, hex::decode(f).unwrap()));
+
+        let events: Vec<_> = decode_stream(frames).collect();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], DecodedEvent::Frame { source, .. } if source == client));
+        assert!(matches!(events[1], DecodedEvent::Frame { source, .. } if source == device));
+        assert!(matches!(events[2], DecodedEvent::Frame { source, .. } if source == client));
+        assert!(matches!(events[3], DecodedEvent::Error { source, .. } if source == device));
+    }
+
+    #[test]
+    fn test_decode_read_foreign_device_table_ack() {
+        let data = hex::decode("81070018c0a80164bac00384012c0a000005bac0003c0005").unwrap();
+        let bvlc = BVLC::decode_slice(&data).expect("Decode BVLC");
+
+        let entries = match &bvlc.function {
+            BVLCFunction::ReadForeignDeviceTableAck(entries) => entries,
+            f => panic!("Not a Read-Foreign-Device-Table-Ack: {:?}", f),
+        };
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].address,
+            SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 100), 0xBAC0)
+        );
+        assert_eq!(entries[0].ttl, 900);
+        assert_eq!(entries[0].time_remaining, 300);
+        assert_eq!(entries[1].ttl, 60);
+        assert_eq!(entries[1].time_remaining, 5);
+        assert_eq!(bvlc.encode_vec().unwrap(), data);
+    }
+
+    #[test]
+    fn test_delete_foreign_device_table_entry() {
+        let address = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 5), 0xBAC0);
+        let bvlc = BVLC::new(BVLCFunction::DeleteForeignDeviceTableEntry(address));
+
+        let data = bvlc.encode_vec().expect("Write BVLC to buffer");
+        assert_eq!(data, hex::decode("8108000a0a000005bac0").unwrap());
+        assert_eq!(BVLC::decode_slice(&data).unwrap(), bvlc);
+
+        let bvlc = BVLC::decode_slice(&hex::decode("81060004").unwrap()).unwrap();
+        assert_eq!(bvlc.function, BVLCFunction::ReadForeignDeviceTable);
+    }
+
+    #[test]
+    fn test_display_bvlc_function() {
+        let bvlc = BVLC::decode_slice(&hex::decode("810b000c0120ffff00ff1008").unwrap()).unwrap();
+        assert_eq!(
+            bvlc.function.to_string(),
+            "Original-Broadcast-NPDU(Unconfirmed-Request(Who-Is))"
+        );
+        assert_eq!(
+            BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 }.to_string(),
+            "Register-Foreign-Device(ttl=60)"
+        );
         assert_eq!(
-            err.into_inner().unwrap().to_string(),
-            "BVLC type not supported: 0".to_string()
+            BVLCFunction::Result(BVLCResultCode::RegisterForeignDeviceNAK).to_string(),
+            "BVLC-Result(RegisterForeignDeviceNAK)"
         );
     }
 }
@@ -0,0 +1,331 @@
+use crate::network::NPDU;
+use crate::transport::bacnetip::{BVLC, BVLCFunction, BVLCResultCode, BdtEntry, FdtEntry};
+
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::time::{Duration, Instant};
+
+/// Time a foreign device registration is kept beyond its time-to-live (J.5.2.3)
+pub const FDT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug)]
+struct ForeignDevice {
+    address: SocketAddrV4,
+    ttl: u16,
+    expires: Instant,
+}
+
+/// BACnet Broadcast Management Device (J.4), distributing broadcasts between the IP subnets
+/// of its Broadcast Distribution Table and to the registered foreign devices
+#[derive(Clone, Debug)]
+pub struct Bbmd {
+    /// B/IP address of this BBMD
+    address: SocketAddrV4,
+    /// Broadcast address of the local IP subnet
+    broadcast_address: SocketAddrV4,
+    bdt: Vec<BdtEntry>,
+    fdt: Vec<ForeignDevice>,
+}
+
+impl Bbmd {
+    pub fn new(address: SocketAddrV4, broadcast_address: SocketAddrV4, bdt: Vec<BdtEntry>) -> Self {
+        Self {
+            address,
+            broadcast_address,
+            bdt,
+            fdt: Vec::new(),
+        }
+    }
+
+    pub fn bdt(&self) -> &[BdtEntry] {
+        &self.bdt
+    }
+
+    /// Foreign Device Table with the time remaining at `now`
+    pub fn fdt(&self, now: Instant) -> Vec<FdtEntry> {
+        self.fdt
+            .iter()
+            .filter(|d| d.expires > now)
+            .map(|d| FdtEntry {
+                address: d.address,
+                ttl: d.ttl,
+                time_remaining: d
+                    .expires
+                    .saturating_duration_since(now)
+                    .as_secs()
+                    .min(u16::MAX as u64) as u16,
+            })
+            .collect()
+    }
+
+    /// Remove the foreign devices whose registration expired at `now`
+    pub fn purge(&mut self, now: Instant) {
+        self.fdt.retain(|d| d.expires > now);
+    }
+
+    /// Handle a BVLC message received from `source`, returning the messages to send and their
+    /// destinations
+    pub fn handle(
+        &mut self,
+        source: SocketAddrV4,
+        bvlc: BVLC,
+        now: Instant,
+    ) -> Vec<(SocketAddrV4, BVLC)> {
+        self.purge(now);
+        match bvlc.function {
+            BVLCFunction::OriginalBroadcastNPDU(npdu) => {
+                let forwarded = forwarded(source, npdu);
+                let mut out = self.to_peers(&forwarded);
+                out.extend(self.to_foreign_devices(&forwarded, None));
+                out
+            }
+            BVLCFunction::ForwardedNPDU {
+                originating_address,
+                npdu,
+            } => {
+                let forwarded = forwarded(originating_address, npdu);
+                let mut out = vec![(self.broadcast_address, forwarded.clone())];
+                out.extend(self.to_foreign_devices(&forwarded, None));
+                out
+            }
+            BVLCFunction::DistributeBroadcastToNetwork(npdu) => {
+                if !self.fdt.iter().any(|d| d.address == source) {
+                    return reply(source, BVLCResultCode::DistributeBroadcastToNetworkNAK);
+                }
+                let forwarded = forwarded(source, npdu);
+                let mut out = vec![(self.broadcast_address, forwarded.clone())];
+                out.extend(self.to_peers(&forwarded));
+                out.extend(self.to_foreign_devices(&forwarded, Some(source)));
+                out
+            }
+            BVLCFunction::RegisterForeignDevice { ttl_seconds } => {
+                self.register(source, ttl_seconds, now);
+                reply(source, BVLCResultCode::SuccessfulCompletion)
+            }
+            BVLCFunction::DeleteForeignDeviceTableEntry(address) => {
+                let before = self.fdt.len();
+                self.fdt.retain(|d| d.address != address);
+                match self.fdt.len() < before {
+                    true => reply(source, BVLCResultCode::SuccessfulCompletion),
+                    false => reply(source, BVLCResultCode::DeleteForeignDeviceTableEntryNAK),
+                }
+            }
+            BVLCFunction::WriteBroadcastDistributionTable(bdt) => {
+                self.bdt = bdt;
+                reply(source, BVLCResultCode::SuccessfulCompletion)
+            }
+            BVLCFunction::ReadBroadcastDistributionTable => vec![(
+                source,
+                BVLC::new(BVLCFunction::ReadBroadcastDistributionTableAck(
+                    self.bdt.clone(),
+                )),
+            )],
+            BVLCFunction::ReadForeignDeviceTable => vec![(
+                source,
+                BVLC::new(BVLCFunction::ReadForeignDeviceTableAck(self.fdt(now))),
+            )],
+            BVLCFunction::Result(_)
+            | BVLCFunction::ReadBroadcastDistributionTableAck(_)
+            | BVLCFunction::ReadForeignDeviceTableAck(_)
+            | BVLCFunction::OriginalUnicastNPDU(_) => vec![],
+        }
+    }
+
+    fn register(&mut self, address: SocketAddrV4, ttl: u16, now: Instant) {
+        let expires = now + Duration::from_secs(ttl as u64) + FDT_GRACE_PERIOD;
+        match self.fdt.iter_mut().find(|d| d.address == address) {
+            Some(device) => {
+                device.ttl = ttl;
+                device.expires = expires;
+            }
+            None => self.fdt.push(ForeignDevice {
+                address,
+                ttl,
+                expires,
+            }),
+        }
+    }
+
+    /// Forward to the other BBMDs of the BDT
+    fn to_peers(&self, bvlc: &BVLC) -> Vec<(SocketAddrV4, BVLC)> {
+        self.bdt
+            .iter()
+            .filter(|e| e.address != self.address)
+            .map(|e| (forward_address(e), bvlc.clone()))
+            .collect()
+    }
+
+    /// Forward to the registered foreign devices, except `exclude`
+    fn to_foreign_devices(
+        &self,
+        bvlc: &BVLC,
+        exclude: Option<SocketAddrV4>,
+    ) -> Vec<(SocketAddrV4, BVLC)> {
+        self.fdt
+            .iter()
+            .filter(|d| Some(d.address) != exclude)
+            .map(|d| (d.address, bvlc.clone()))
+            .collect()
+    }
+}
+
+fn forwarded(originating_address: SocketAddrV4, npdu: NPDU) -> BVLC {
+    BVLC::new(BVLCFunction::ForwardedNPDU {
+        originating_address,
+        npdu,
+    })
+}
+
+fn reply(destination: SocketAddrV4, code: BVLCResultCode) -> Vec<(SocketAddrV4, BVLC)> {
+    vec![(destination, BVLC::new(BVLCFunction::Result(code)))]
+}
+
+/// Destination of broadcasts forwarded to a BDT entry (J.4.3.2), the directed broadcast
+/// address of its subnet or the BBMD itself for an all ones mask
+fn forward_address(entry: &BdtEntry) -> SocketAddrV4 {
+    let ip = u32::from(*entry.address.ip()) | !u32::from(entry.mask);
+    SocketAddrV4::new(Ipv4Addr::from(ip), entry.address.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{NPDU, NPDUPriority};
+
+    const BBMD: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 1), 47808);
+    const LOCAL_BROADCAST: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 255), 47808);
+    const PEER: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(10, 0, 2, 1), 47808);
+    const FOREIGN: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(172, 16, 0, 9), 47808);
+    const DEVICE: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 20), 47808);
+
+    fn bbmd() -> Bbmd {
+        let all_ones = Ipv4Addr::new(255, 255, 255, 255);
+        Bbmd::new(
+            BBMD,
+            LOCAL_BROADCAST,
+            vec![
+                BdtEntry {
+                    address: BBMD,
+                    mask: all_ones,
+                },
+                BdtEntry {
+                    address: PEER,
+                    mask: all_ones,
+                },
+            ],
+        )
+    }
+
+    fn npdu() -> NPDU {
+        NPDU::empty(NPDUPriority::Normal)
+    }
+
+    #[test]
+    fn test_register_foreign_device() {
+        let now = Instant::now();
+        let mut bbmd = bbmd();
+        let register = BVLC::new(BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 });
+
+        let out = bbmd.handle(FOREIGN, register, now);
+        assert_eq!(
+            out,
+            vec![(
+                FOREIGN,
+                BVLC::new(BVLCFunction::Result(BVLCResultCode::SuccessfulCompletion))
+            )]
+        );
+        assert_eq!(
+            bbmd.fdt(now),
+            vec![FdtEntry {
+                address: FOREIGN,
+                ttl: 60,
+                time_remaining: 90,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_broadcast_forwarded_to_peer_and_foreign_device() {
+        let now = Instant::now();
+        let mut bbmd = bbmd();
+        let register = BVLC::new(BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 });
+        bbmd.handle(FOREIGN, register, now);
+
+        let broadcast = BVLC::new(BVLCFunction::OriginalBroadcastNPDU(npdu()));
+        let out = bbmd.handle(DEVICE, broadcast, now);
+        assert_eq!(
+            out,
+            vec![
+                (PEER, forwarded(DEVICE, npdu())),
+                (FOREIGN, forwarded(DEVICE, npdu())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forwarded_npdu_broadcast_locally() {
+        let mut bbmd = bbmd();
+        let forwarded_npdu = forwarded(DEVICE, npdu());
+
+        let out = bbmd.handle(PEER, forwarded_npdu.clone(), Instant::now());
+        assert_eq!(out, vec![(LOCAL_BROADCAST, forwarded_npdu)]);
+    }
+
+    #[test]
+    fn test_distribute_broadcast_requires_registration() {
+        let now = Instant::now();
+        let mut bbmd = bbmd();
+        let distribute = BVLC::new(BVLCFunction::DistributeBroadcastToNetwork(npdu()));
+
+        let out = bbmd.handle(FOREIGN, distribute.clone(), now);
+        assert_eq!(
+            out,
+            vec![(
+                FOREIGN,
+                BVLC::new(BVLCFunction::Result(
+                    BVLCResultCode::DistributeBroadcastToNetworkNAK
+                ))
+            )]
+        );
+
+        let register = BVLC::new(BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 });
+        bbmd.handle(FOREIGN, register, now);
+        let out = bbmd.handle(FOREIGN, distribute, now);
+        assert_eq!(
+            out,
+            vec![
+                (LOCAL_BROADCAST, forwarded(FOREIGN, npdu())),
+                (PEER, forwarded(FOREIGN, npdu())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_foreign_device_expires_after_ttl() {
+        let now = Instant::now();
+        let mut bbmd = bbmd();
+        let register = BVLC::new(BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 });
+        bbmd.handle(FOREIGN, register, now);
+
+        // Still registered during the grace period
+        let later = now + Duration::from_secs(60) + FDT_GRACE_PERIOD - Duration::from_secs(1);
+        assert_eq!(bbmd.fdt(later).len(), 1);
+
+        let expired = now + Duration::from_secs(60) + FDT_GRACE_PERIOD;
+        let broadcast = BVLC::new(BVLCFunction::OriginalBroadcastNPDU(npdu()));
+        let out = bbmd.handle(DEVICE, broadcast, expired);
+        assert_eq!(out, vec![(PEER, forwarded(DEVICE, npdu()))]);
+        assert!(bbmd.fdt(expired).is_empty());
+    }
+
+    #[test]
+    fn test_forward_address_directed_broadcast() {
+        let entry = BdtEntry {
+            address: PEER,
+            mask: Ipv4Addr::new(255, 255, 255, 0),
+        };
+        assert_eq!(
+            forward_address(&entry),
+            SocketAddrV4::new(Ipv4Addr::new(10, 0, 2, 255), 47808)
+        );
+    }
+}
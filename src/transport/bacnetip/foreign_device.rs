@@ -0,0 +1,418 @@
+use crate::network::NPDU;
+use crate::transport::bacnetip::{BVLC, BVLCFunction, BVLCResultCode};
+use crate::{Decode, Encode};
+
+use async_std::channel::{self, Receiver, Sender, TrySendError};
+use async_std::future;
+use async_std::net::UdpSocket;
+use async_std::sync::Arc;
+use async_std::task::{self, JoinHandle};
+use std::future::Future;
+use std::net::{SocketAddr, SocketAddrV4};
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use tracing::trace;
+
+/// Time to wait for the BVLC-Result of a registration
+pub const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Datagrams queued for receive() by default, the oldest is dropped when the queue is full
+pub const INCOMING_CAPACITY: usize = 64;
+
+/// A BVLC received with its source
+type Datagram = (SocketAddrV4, BVLC);
+
+/// A foreign device (J.5.2), registered with a remote BBMD to send and receive broadcasts
+///
+/// The socket is shared by the registration and the traffic of the device. Read it through
+/// receive(), which hands the BVLC-Results of the BBMD to a pending registration, while a
+/// registration queues the other datagrams it reads for receive(). The queue is bounded, once it
+/// is full the oldest datagram is dropped for each new one.
+#[derive(Clone, Debug)]
+pub struct ForeignDeviceClient {
+    socket: Arc<UdpSocket>,
+    bbmd: SocketAddrV4,
+    ttl_seconds: u16,
+    timeout: Duration,
+    /// BVLC-Results received from the BBMD
+    results: (Sender<BVLCResultCode>, Receiver<BVLCResultCode>),
+    /// Datagrams other than BVLC-Results of the BBMD, with their source
+    incoming: (Sender<Datagram>, Receiver<Datagram>),
+}
+
+impl ForeignDeviceClient {
+    /// Client registering with `bbmd` for `ttl_seconds`, which must not be 0
+    pub fn new(
+        socket: Arc<UdpSocket>,
+        bbmd: SocketAddrV4,
+        ttl_seconds: u16,
+    ) -> std::io::Result<Self> {
+        if ttl_seconds == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Time-to-live of a registration must not be 0",
+            ));
+        }
+        Ok(Self {
+            socket,
+            bbmd,
+            ttl_seconds,
+            timeout: REGISTRATION_TIMEOUT,
+            results: channel::unbounded(),
+            incoming: channel::bounded(INCOMING_CAPACITY),
+        })
+    }
+
+    /// Time to wait for the BBMD to answer a registration, defaults to REGISTRATION_TIMEOUT
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of datagrams queued for receive(), defaults to INCOMING_CAPACITY
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.incoming = channel::bounded(capacity.max(1));
+        self
+    }
+
+    /// Interval of the re-registrations, half the time-to-live
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.ttl_seconds as u64) / 2
+    }
+
+    /// Register with the BBMD, failing if the registration is rejected or not answered
+    pub async fn register(&self) -> std::io::Result<()> {
+        // Results of earlier registrations that timed out
+        while self.results.1.try_recv().is_ok() {}
+
+        let register = BVLC::new(BVLCFunction::RegisterForeignDevice {
+            ttl_seconds: self.ttl_seconds,
+        });
+        self.send(register).await?;
+
+        let result = future::timeout(self.timeout, async {
+            loop {
+                let result = race(async { Ok(self.results.1.recv().await.ok()) }, async {
+                    self.read_socket().await.map(|_| None)
+                });
+                if let Some(code) = result.await? {
+                    return Ok(code);
+                }
+            }
+        });
+        match result.await {
+            Ok(Ok(BVLCResultCode::SuccessfulCompletion)) => Ok(()),
+            Ok(Ok(code)) => Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionRefused,
+                format!("Registration with {} rejected: {:?}", self.bbmd, code),
+            )),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("No registration result from {}", self.bbmd),
+            )),
+        }
+    }
+
+    /// Receive the next datagram other than a BVLC-Result of the BBMD, including those read
+    /// while awaiting a registration
+    pub async fn receive(&self) -> std::io::Result<Datagram> {
+        loop {
+            let received = race(async { Ok(self.incoming.1.recv().await.ok()) }, async {
+                self.read_socket().await.map(|_| None)
+            });
+            if let Some(received) = received.await? {
+                return Ok(received);
+            }
+        }
+    }
+
+    /// Read one datagram, passing it to the registration or to receive()
+    async fn read_socket(&self) -> std::io::Result<()> {
+        let mut buf = vec![0u8; 1500];
+        let (n, peer) = self.socket.recv_from(&mut buf).await?;
+        let SocketAddr::V4(peer) = peer else {
+            return Ok(());
+        };
+        match BVLC::decode_slice(&buf[..n]) {
+            Ok(BVLC {
+                function: BVLCFunction::Result(code),
+                ..
+            }) if peer == self.bbmd => {
+                let _ = self.results.0.try_send(code);
+            }
+            Ok(bvlc) => self.enqueue((peer, bvlc)),
+            Err(e) => trace!("Invalid BVLC from {}: {}", peer, e),
+        }
+        Ok(())
+    }
+
+    /// Queue a datagram for receive(), dropping the oldest if the queue is full
+    fn enqueue(&self, mut datagram: Datagram) {
+        while let Err(TrySendError::Full(d)) = self.incoming.0.try_send(datagram) {
+            if let Ok((source, _)) = self.incoming.1.try_recv() {
+                trace!("Queue full, dropped datagram from {}", source);
+            }
+            datagram = d;
+        }
+    }
+
+    /// Register and spawn a task re-registering every interval(). The task ends with the error
+    /// of the first registration that fails.
+    pub async fn keep_registered(&self) -> std::io::Result<JoinHandle<std::io::Error>> {
+        self.register().await?;
+        let client = self.clone();
+        Ok(task::spawn(async move {
+            loop {
+                task::sleep(client.interval()).await;
+                if let Err(e) = client.register().await {
+                    return e;
+                }
+                trace!("Re-registered with {}", client.bbmd);
+            }
+        }))
+    }
+
+    /// Broadcast an NPDU through the BBMD
+    pub async fn distribute_broadcast(&self, npdu: NPDU) -> std::io::Result<()> {
+        self.send(BVLC::new(BVLCFunction::DistributeBroadcastToNetwork(npdu)))
+            .await
+    }
+
+    async fn send(&self, bvlc: BVLC) -> std::io::Result<()> {
+        self.socket.send_to(&bvlc.encode_vec()?, self.bbmd).await?;
+        Ok(())
+    }
+}
+
+/// Await whichever of `a` and `b` completes first, dropping the other
+async fn race<T>(a: impl Future<Output = T>, b: impl Future<Output = T>) -> T {
+    let (mut a, mut b) = (pin!(a), pin!(b));
+    std::future::poll_fn(|cx| match a.as_mut().poll(cx) {
+        Poll::Ready(t) => Poll::Ready(t),
+        Poll::Pending => b.as_mut().poll(cx),
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NPDUPriority;
+    use std::time::Instant;
+
+    async fn sockets() -> (Arc<UdpSocket>, UdpSocket, SocketAddrV4) {
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let bbmd = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let SocketAddr::V4(address) = bbmd.local_addr().unwrap() else {
+            unreachable!()
+        };
+        (Arc::new(client), bbmd, address)
+    }
+
+    /// Receive a BVLC at the mock BBMD and answer it with `code`
+    async fn answer(bbmd: &UdpSocket, code: BVLCResultCode) -> BVLC {
+        let mut buf = vec![0u8; 1500];
+        let (n, peer) = bbmd.recv_from(&mut buf).await.unwrap();
+        let result = BVLC::new(BVLCFunction::Result(code));
+        bbmd.send_to(&result.encode_vec().unwrap(), peer)
+            .await
+            .unwrap();
+        BVLC::decode_slice(&buf[..n]).unwrap()
+    }
+
+    #[test]
+    fn test_register() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60).unwrap();
+
+            let mock =
+                task::spawn(
+                    async move { answer(&bbmd, BVLCResultCode::SuccessfulCompletion).await },
+                );
+            client.register().await.unwrap();
+            assert_eq!(
+                mock.await.function,
+                BVLCFunction::RegisterForeignDevice { ttl_seconds: 60 }
+            );
+        });
+    }
+
+    #[test]
+    fn test_zero_ttl_rejected() {
+        task::block_on(async {
+            let (socket, _bbmd, address) = sockets().await;
+            let err = ForeignDeviceClient::new(socket, address, 0).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        });
+    }
+
+    #[test]
+    fn test_register_queues_other_datagrams() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60).unwrap();
+
+            let forwarded = BVLC::new(BVLCFunction::ForwardedNPDU {
+                originating_address: SocketAddrV4::new([192, 168, 1, 5].into(), 47808),
+                npdu: NPDU::empty(NPDUPriority::Normal),
+            });
+            let frame = forwarded.encode_vec().unwrap();
+            let mock = task::spawn(async move {
+                // A broadcast forwarded by the BBMD arrives ahead of the result
+                let mut buf = vec![0u8; 1500];
+                let (_, peer) = bbmd.recv_from(&mut buf).await.unwrap();
+                bbmd.send_to(&frame, peer).await.unwrap();
+                let result = BVLC::new(BVLCFunction::Result(BVLCResultCode::SuccessfulCompletion));
+                bbmd.send_to(&result.encode_vec().unwrap(), peer)
+                    .await
+                    .unwrap();
+            });
+            client.register().await.unwrap();
+            mock.await;
+
+            let (source, bvlc) = client.receive().await.unwrap();
+            assert_eq!(source, address);
+            assert_eq!(bvlc, forwarded);
+        });
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60)
+                .unwrap()
+                .capacity(2);
+
+            let forwarded: Vec<BVLC> = (1..=3)
+                .map(|host| {
+                    BVLC::new(BVLCFunction::ForwardedNPDU {
+                        originating_address: SocketAddrV4::new([192, 168, 1, host].into(), 47808),
+                        npdu: NPDU::empty(NPDUPriority::Normal),
+                    })
+                })
+                .collect();
+            let frames: Vec<Vec<u8>> = forwarded.iter().map(|b| b.encode_vec().unwrap()).collect();
+            let mock = task::spawn(async move {
+                let mut buf = vec![0u8; 1500];
+                let (_, peer) = bbmd.recv_from(&mut buf).await.unwrap();
+                for frame in frames {
+                    bbmd.send_to(&frame, peer).await.unwrap();
+                }
+                let result = BVLC::new(BVLCFunction::Result(BVLCResultCode::SuccessfulCompletion));
+                bbmd.send_to(&result.encode_vec().unwrap(), peer)
+                    .await
+                    .unwrap();
+            });
+            client.register().await.unwrap();
+            mock.await;
+
+            assert_eq!(client.receive().await.unwrap().1, forwarded[1]);
+            assert_eq!(client.receive().await.unwrap().1, forwarded[2]);
+        });
+    }
+
+    #[test]
+    fn test_register_while_receiving() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60).unwrap();
+
+            // receive() reading the socket hands the result to the registration
+            let receiver = client.clone();
+            let receiving = task::spawn(async move { receiver.receive().await });
+            task::sleep(Duration::from_millis(20)).await;
+
+            let mock =
+                task::spawn(
+                    async move { answer(&bbmd, BVLCResultCode::SuccessfulCompletion).await },
+                );
+            client.register().await.unwrap();
+            mock.await;
+            assert!(
+                future::timeout(Duration::from_millis(50), receiving)
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    #[test]
+    fn test_register_rejected() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60).unwrap();
+
+            let mock = task::spawn(async move {
+                answer(&bbmd, BVLCResultCode::RegisterForeignDeviceNAK).await
+            });
+            let err = client.register().await.unwrap_err();
+            mock.await;
+            assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+        });
+    }
+
+    #[test]
+    fn test_register_timeout() {
+        task::block_on(async {
+            let (socket, _bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60)
+                .unwrap()
+                .timeout(Duration::from_millis(50));
+
+            let err = client.register().await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        });
+    }
+
+    #[test]
+    fn test_keep_registered_re_registers_at_half_ttl() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 2).unwrap();
+
+            let mock = task::spawn(async move {
+                answer(&bbmd, BVLCResultCode::SuccessfulCompletion).await;
+                let registered = Instant::now();
+                let bvlc = answer(&bbmd, BVLCResultCode::SuccessfulCompletion).await;
+                let elapsed = registered.elapsed();
+                // Reject the third registration to end the task
+                answer(&bbmd, BVLCResultCode::RegisterForeignDeviceNAK).await;
+                (bvlc, elapsed)
+            });
+            let keepalive = client.keep_registered().await.unwrap();
+            let (bvlc, elapsed) = mock.await;
+
+            assert_eq!(
+                bvlc.function,
+                BVLCFunction::RegisterForeignDevice { ttl_seconds: 2 }
+            );
+            assert!(elapsed >= Duration::from_millis(900), "{:?}", elapsed);
+            assert!(elapsed < Duration::from_millis(1900), "{:?}", elapsed);
+            assert_eq!(
+                keepalive.await.kind(),
+                std::io::ErrorKind::ConnectionRefused
+            );
+        });
+    }
+
+    #[test]
+    fn test_distribute_broadcast() {
+        task::block_on(async {
+            let (socket, bbmd, address) = sockets().await;
+            let client = ForeignDeviceClient::new(socket, address, 60).unwrap();
+
+            client
+                .distribute_broadcast(NPDU::empty(NPDUPriority::Normal))
+                .await
+                .unwrap();
+            let mut buf = vec![0u8; 1500];
+            let (n, _) = bbmd.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[..n], hex::decode("810900060100").unwrap());
+        });
+    }
+}
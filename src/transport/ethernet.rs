@@ -0,0 +1,169 @@
+/// Implements BACnet over ISO 8802-2 (LLC) on ISO 8802-3 (Ethernet) (Clause 7)
+use crate::network::NPDU;
+use crate::{Decode, Encode, Error};
+use alloc::{format, vec};
+
+/// 6 octet MAC address of an Ethernet station
+pub type MacAddress = [u8; 6];
+
+/// MAC address all stations receive
+pub const BROADCAST_MAC: MacAddress = [0xFF; 6];
+
+/// Link service access point assigned to BACnet, used as DSAP and SSAP (7.2)
+pub const BACNET_LSAP: u8 = 0x82;
+
+/// LLC control of an Unnumbered Information command (7.1)
+pub const LLC_UI: u8 = 0x03;
+
+/// Octets of the LLC header: DSAP, SSAP, control
+const LLC_HEADER_LEN: usize = 3;
+
+/// Largest value of the 8802-3 length field, values above it are an EtherType
+const MAX_LENGTH: usize = 1500;
+
+/// An 8802-3 frame carrying an NPDU behind an 8802-2 LLC header (7.1)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EthernetFrame {
+    pub destination: MacAddress,
+    pub source: MacAddress,
+    pub npdu: NPDU,
+}
+
+impl EthernetFrame {
+    pub fn new(destination: MacAddress, source: MacAddress, npdu: NPDU) -> Self {
+        Self {
+            destination,
+            source,
+            npdu,
+        }
+    }
+
+    /// Frame sent to every station on the LAN
+    pub fn broadcast(source: MacAddress, npdu: NPDU) -> Self {
+        Self::new(BROADCAST_MAC, source, npdu)
+    }
+}
+
+impl Encode for EthernetFrame {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        writer.write_all(&self.destination)?;
+        writer.write_all(&self.source)?;
+        writer.write_u16((LLC_HEADER_LEN + self.npdu.len()) as u16)?;
+        writer.write_all(&[BACNET_LSAP, BACNET_LSAP, LLC_UI])?;
+        self.npdu.encode(writer)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 6 + 6; // Destination, Source
+        l += 2; // Length
+        l += LLC_HEADER_LEN; // DSAP, SSAP, Control
+        l += self.npdu.len();
+        l
+    }
+}
+
+impl Decode for EthernetFrame {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut destination = [0u8; 6];
+        reader.read_exact(&mut destination)?;
+        let mut source = [0u8; 6];
+        reader.read_exact(&mut source)?;
+        let length = reader.read_u16()? as usize;
+        if length < LLC_HEADER_LEN {
+            return Err(Error::InvalidValue(format!(
+                "Length too short for an LLC header: {}",
+                length
+            )));
+        }
+        if length > MAX_LENGTH {
+            return Err(Error::InvalidValue(format!(
+                "Length exceeds an 8802-3 frame: {}",
+                length
+            )));
+        }
+        let mut llc = [0u8; LLC_HEADER_LEN];
+        reader.read_exact(&mut llc)?;
+        if llc != [BACNET_LSAP, BACNET_LSAP, LLC_UI] {
+            return Err(Error::Unsupported(format!(
+                "Not a BACnet LLC header: {:02x?}",
+                llc
+            )));
+        }
+        // Frames shorter than the minimum are padded, the padding follows the length
+        let mut npdu = vec![0u8; length - LLC_HEADER_LEN];
+        reader.read_exact(&mut npdu)?;
+        Ok(Self {
+            destination,
+            source,
+            npdu: NPDU::decode_slice(&npdu)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::APDU;
+    use crate::network::{NPDUDest, NPDUPriority};
+
+    const SOURCE: MacAddress = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+    fn who_is() -> NPDU {
+        let apdu = APDU::new(0x01, 8, vec![]);
+        NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff)),
+            None,
+            NPDUPriority::Normal,
+        )
+    }
+
+    #[test]
+    fn test_who_is_round_trip() {
+        let frame = EthernetFrame::broadcast(SOURCE, who_is());
+
+        let data = frame.encode_vec().unwrap();
+        assert_eq!(
+            data,
+            hex::decode("ffffffffffff001122334455000b8282030120ffff00ff1008").unwrap()
+        );
+        assert_eq!(data.len(), frame.len());
+        assert_eq!(EthernetFrame::decode_slice(&data).unwrap(), frame);
+    }
+
+    #[test]
+    fn test_decode_padded_frame() {
+        let mut data = EthernetFrame::broadcast(SOURCE, who_is())
+            .encode_vec()
+            .unwrap();
+        data.resize(60, 0);
+        assert_eq!(EthernetFrame::decode_slice(&data).unwrap().npdu, who_is());
+    }
+
+    #[test]
+    fn test_decode_invalid_frames() {
+        let data = hex::decode("ffffffffffff001122334455000b8282030120ffff00ff1008").unwrap();
+        assert!(EthernetFrame::decode_slice(&data[..data.len() - 1]).is_err());
+
+        let mut other_lsap = data.clone();
+        other_lsap[14] = 0xAA;
+        assert!(EthernetFrame::decode_slice(&other_lsap).is_err());
+
+        let mut short_length = data.clone();
+        short_length[13] = 0x02;
+        assert!(EthernetFrame::decode_slice(&short_length).is_err());
+
+        // 0x05dd (1501) is no longer a length, 0x0800 is the IPv4 EtherType
+        for length in [[0x05, 0xdd], [0x08, 0x00]] {
+            let mut long_length = data.clone();
+            long_length[12..14].copy_from_slice(&length);
+            long_length.resize(1600, 0);
+            let err = EthernetFrame::decode_slice(&long_length).unwrap_err();
+            assert!(matches!(err, Error::InvalidValue(_)), "{:?}", err);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+/// Implements MS/TP frames (Clause 9)
+use crate::network::NPDU;
+use crate::{Decode, Encode, Error};
+use alloc::{format, string::ToString, vec, vec::Vec};
+
+/// Preamble starting every frame (9.3)
+pub const PREAMBLE: [u8; 2] = [0x55, 0xFF];
+
+/// Largest data field of a frame (9.3)
+pub const MAX_DATA_LENGTH: usize = 501;
+
+/// Address of a broadcast frame (9.3)
+pub const BROADCAST_ADDRESS: u8 = 0xFF;
+
+/// MS/TP Frame Type (9.3)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FrameType {
+    Token,                       // = 0;
+    PollForMaster,               // = 1;
+    ReplyToPollForMaster,        // = 2;
+    TestRequest,                 // = 3;
+    TestResponse,                // = 4;
+    BACnetDataExpectingReply,    // = 5;
+    BACnetDataNotExpectingReply, // = 6;
+    ReplyPostponed,              // = 7;
+    /// Reserved or proprietary frame type
+    Other(u8),
+}
+
+impl From<u8> for FrameType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Token,
+            1 => Self::PollForMaster,
+            2 => Self::ReplyToPollForMaster,
+            3 => Self::TestRequest,
+            4 => Self::TestResponse,
+            5 => Self::BACnetDataExpectingReply,
+            6 => Self::BACnetDataNotExpectingReply,
+            7 => Self::ReplyPostponed,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<FrameType> for u8 {
+    fn from(value: FrameType) -> Self {
+        match value {
+            FrameType::Token => 0,
+            FrameType::PollForMaster => 1,
+            FrameType::ReplyToPollForMaster => 2,
+            FrameType::TestRequest => 3,
+            FrameType::TestResponse => 4,
+            FrameType::BACnetDataExpectingReply => 5,
+            FrameType::BACnetDataNotExpectingReply => 6,
+            FrameType::ReplyPostponed => 7,
+            FrameType::Other(v) => v,
+        }
+    }
+}
+
+/// Accumulate one octet into the header CRC, x^8 + x^7 + 1 (Annex G.1)
+pub fn header_crc(data: u8, crc: u8) -> u8 {
+    let mut crc = (crc ^ data) as u16;
+    crc = crc
+        ^ (crc << 1)
+        ^ (crc << 2)
+        ^ (crc << 3)
+        ^ (crc << 4)
+        ^ (crc << 5)
+        ^ (crc << 6)
+        ^ (crc << 7);
+    ((crc & 0xFE) ^ ((crc >> 8) & 1)) as u8
+}
+
+/// Accumulate one octet into the data CRC, CRC-CCITT x^16 + x^12 + x^5 + 1 (Annex G.2)
+pub fn data_crc(data: u8, crc: u16) -> u16 {
+    let low = (crc & 0xFF) ^ data as u16;
+    (crc >> 8)
+        ^ (low << 8)
+        ^ (low << 3)
+        ^ (low << 12)
+        ^ (low >> 4)
+        ^ (low & 0x0F)
+        ^ ((low & 0x0F) << 7)
+}
+
+/// A MS/TP frame (9.3), the data of BACnet data frames is an NPDU
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MstpFrame {
+    pub frame_type: FrameType,
+    pub destination: u8,
+    pub source: u8,
+    pub data: Vec<u8>,
+}
+
+impl MstpFrame {
+    pub fn new(frame_type: FrameType, destination: u8, source: u8) -> Self {
+        Self {
+            frame_type,
+            destination,
+            source,
+            data: Vec::new(),
+        }
+    }
+
+    /// Frame passing the token to `destination`
+    pub fn token(destination: u8, source: u8) -> Self {
+        Self::new(FrameType::Token, destination, source)
+    }
+
+    /// BACnet data frame carrying `npdu`, the frame type follows its data_expecting_reply
+    pub fn npdu(destination: u8, source: u8, npdu: &NPDU) -> crate::Result<Self> {
+        let frame_type = match npdu.data_expecting_reply {
+            true => FrameType::BACnetDataExpectingReply,
+            false => FrameType::BACnetDataNotExpectingReply,
+        };
+        Ok(Self {
+            data: npdu.encode_vec()?,
+            ..Self::new(frame_type, destination, source)
+        })
+    }
+
+    /// The NPDU carried by a BACnet data frame
+    pub fn decode_npdu(&self) -> crate::Result<NPDU> {
+        match self.frame_type {
+            FrameType::BACnetDataExpectingReply | FrameType::BACnetDataNotExpectingReply => {
+                NPDU::decode_slice(&self.data)
+            }
+            t => Err(Error::Unsupported(format!(
+                "Frame type without NPDU: {:?}",
+                t
+            ))),
+        }
+    }
+
+    fn header(&self) -> [u8; 5] {
+        let length = (self.data.len() as u16).to_be_bytes();
+        [
+            self.frame_type.into(),
+            self.destination,
+            self.source,
+            length[0],
+            length[1],
+        ]
+    }
+}
+
+impl Encode for MstpFrame {
+    fn encode<T: crate::io::Write + Sized>(&self, writer: &mut T) -> crate::Result<()> {
+        if self.data.len() > MAX_DATA_LENGTH {
+            return Err(Error::InvalidValue(format!(
+                "Data too long for a frame: {}",
+                self.data.len()
+            )));
+        }
+        writer.write_all(&PREAMBLE)?;
+        let header = self.header();
+        writer.write_all(&header)?;
+        writer.write_u8(!header.iter().fold(0xFF, |crc, b| header_crc(*b, crc)))?;
+        if !self.data.is_empty() {
+            writer.write_all(&self.data)?;
+            let crc = !self.data.iter().fold(0xFFFF, |crc, b| data_crc(*b, crc));
+            writer.write_all(&crc.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        let mut l = 0;
+        l += 2; // Preamble
+        l += 5; // Frame Type, Destination, Source, Length
+        l += 1; // Header CRC
+        if !self.data.is_empty() {
+            l += self.data.len() + 2; // Data, Data CRC
+        }
+        l
+    }
+}
+
+impl Decode for MstpFrame {
+    type Error = Error;
+
+    fn decode<T: crate::io::Read + Sized>(reader: &mut T) -> crate::Result<Self> {
+        let mut preamble = [0u8; 2];
+        reader.read_exact(&mut preamble)?;
+        if preamble != PREAMBLE {
+            return Err(Error::InvalidValue(format!(
+                "Invalid preamble: {:02x?}",
+                preamble
+            )));
+        }
+        let mut header = [0u8; 6];
+        reader.read_exact(&mut header)?;
+        // The CRC over the header including its CRC octet leaves the remainder 0x55
+        if header.iter().fold(0xFF, |crc, b| header_crc(*b, crc)) != 0x55 {
+            return Err(Error::InvalidValue("Header CRC mismatch".to_string()));
+        }
+        let length = u16::from_be_bytes([header[3], header[4]]) as usize;
+        if length > MAX_DATA_LENGTH {
+            return Err(Error::InvalidValue(format!(
+                "Data length too long: {}",
+                length
+            )));
+        }
+        let mut data = vec![0u8; length];
+        if length > 0 {
+            reader.read_exact(&mut data)?;
+            let mut crc = [0u8; 2];
+            reader.read_exact(&mut crc)?;
+            // The CRC over the data including its CRC octets leaves the remainder 0xF0B8
+            if data
+                .iter()
+                .chain(&crc)
+                .fold(0xFFFF, |crc, b| data_crc(*b, crc))
+                != 0xF0B8
+            {
+                return Err(Error::InvalidValue("Data CRC mismatch".to_string()));
+            }
+        }
+        Ok(Self {
+            frame_type: header[0].into(),
+            destination: header[1],
+            source: header[2],
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::APDU;
+    use crate::network::{NPDUDest, NPDUPriority, NpduBuilder};
+
+    #[test]
+    fn test_header_crc_vector() {
+        // Token from 0x05 to 0x10 (Annex G.1)
+        let crc = [0x00, 0x10, 0x05, 0x00, 0x00]
+            .iter()
+            .fold(0xFF, |crc, b| header_crc(*b, crc));
+        assert_eq!(crc, 0x73);
+        assert_eq!(!crc, 0x8C);
+        assert_eq!(header_crc(0x8C, crc), 0x55);
+    }
+
+    #[test]
+    fn test_data_crc_vector() {
+        // Annex G.2
+        let crc = [0x01, 0x22, 0x30]
+            .iter()
+            .fold(0xFFFF, |crc, b| data_crc(*b, crc));
+        assert_eq!(crc, 0x42EF);
+        assert_eq!(!crc, 0xBD10);
+        assert_eq!(data_crc(0xBD, data_crc(0x10, crc)), 0xF0B8);
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let frame = MstpFrame::token(0x10, 0x05);
+        let data = frame.encode_vec().unwrap();
+        assert_eq!(data, hex::decode("55ff00100500008c").unwrap());
+        assert_eq!(data.len(), frame.len());
+        assert_eq!(MstpFrame::decode_slice(&data).unwrap(), frame);
+    }
+
+    #[test]
+    fn test_data_expecting_reply_round_trip() {
+        // ReadProperty analog-input,1 present-value
+        let apdu = APDU::new(0x00, 12, hex::decode("0c000000011955").unwrap());
+        let npdu = NpduBuilder::new().confirmed_request(apdu);
+        let frame = MstpFrame::npdu(0x01, 0x7F, &npdu).unwrap();
+        assert_eq!(frame.frame_type, FrameType::BACnetDataExpectingReply);
+
+        let data = frame.encode_vec().unwrap();
+        assert_eq!(&data[..7], &[0x55, 0xFF, 0x05, 0x01, 0x7F, 0x00, 0x0D]);
+        assert_eq!(data.len(), frame.len());
+
+        let decoded = MstpFrame::decode_slice(&data).unwrap();
+        assert_eq!(decoded, frame);
+        assert_eq!(decoded.decode_npdu().unwrap(), npdu);
+    }
+
+    #[test]
+    fn test_broadcast_not_expecting_reply() {
+        let apdu = APDU::new(0x01, 8, vec![]);
+        let npdu = NPDU::new(
+            apdu,
+            Some(NPDUDest::new(0xffff)),
+            None,
+            NPDUPriority::Normal,
+        );
+        let frame = MstpFrame::npdu(BROADCAST_ADDRESS, 0x01, &npdu).unwrap();
+        assert_eq!(frame.frame_type, FrameType::BACnetDataNotExpectingReply);
+        let decoded = MstpFrame::decode_slice(&frame.encode_vec().unwrap()).unwrap();
+        assert_eq!(decoded.decode_npdu().unwrap(), npdu);
+    }
+
+    #[test]
+    fn test_decode_corrupted_frames() {
+        let token = hex::decode("55ff00100500008c").unwrap();
+        assert!(MstpFrame::decode_slice(&token[..7]).is_err());
+
+        let mut bad_preamble = token.clone();
+        bad_preamble[0] = 0x54;
+        assert!(MstpFrame::decode_slice(&bad_preamble).is_err());
+
+        let mut bad_header = token.clone();
+        bad_header[3] = 0x11;
+        assert!(MstpFrame::decode_slice(&bad_header).is_err());
+
+        let npdu = NPDU::empty(NPDUPriority::Normal);
+        let mut data = MstpFrame::npdu(0x01, 0x02, &npdu)
+            .unwrap()
+            .encode_vec()
+            .unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0x01;
+        assert!(MstpFrame::decode_slice(&data).is_err());
+    }
+
+    #[test]
+    fn test_encode_data_too_long() {
+        let mut frame = MstpFrame::new(FrameType::BACnetDataNotExpectingReply, 1, 2);
+        frame.data = vec![0; MAX_DATA_LENGTH + 1];
+        assert!(frame.encode_vec().is_err());
+    }
+}